@@ -5,7 +5,7 @@ use raytracer::{
     camera::{view_transform, Camera},
     linear::Tuple,
     objects::{Shape, Sphere},
-    Color, Material, World,
+    Color, Material, World, DEFAULT_LIGHT,
 };
 
 fn default_camera(size: usize) -> Camera {
@@ -28,8 +28,9 @@ pub fn criterion_benchmark(c: &mut Criterion) {
 
         sphere
     };
-    let mut world = World::default();
-    world.objects = vec![&sphere];
+    let mut world = World::new();
+    world.add_light(&*DEFAULT_LIGHT);
+    world.add_object(&sphere);
 
     let camera = default_camera(50);
 
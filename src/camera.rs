@@ -1,7 +1,7 @@
 use rayon::prelude::*;
 
 use crate::{
-    canvas::Canvas,
+    canvas::{Canvas, GeometryBuffer, GeometrySample},
     linear::{Matrix, Tuple},
     Ray, World,
 };
@@ -182,6 +182,9 @@ impl Camera {
 
     /// Render a world using the camera.
     ///
+    /// Pixels where the camera doesn't see any object are transparent, as
+    /// described by [`World::color_and_alpha_at`].
+    ///
     /// # Arguments
     ///
     /// * `world` - The world to render.
@@ -205,19 +208,60 @@ impl Camera {
     /// let _image = camera.render(&world);
     /// ```
     pub fn render(&self, world: &World) -> Canvas {
-        let pixels = (0..self.hsize)
+        let (pixels, alpha): (Vec<_>, Vec<_>) = (0..self.hsize)
             .into_par_iter()
             .map(|x| {
                 (0..self.vsize)
                     .into_par_iter()
                     .map(move |y| {
                         let ray = self.ray_for_pixel(x, y);
-                        world.color_at(&ray)
+                        world.color_and_alpha_at(&ray)
+                    })
+                    .unzip::<_, _, Vec<_>, Vec<_>>()
+            })
+            .unzip();
+
+        Canvas::from_pixels_with_alpha(pixels, alpha)
+    }
+
+    /// Find the surface seen through each pixel when rendering a world.
+    ///
+    /// The result lines up with the image produced by
+    /// [`render`][Self::render], and can be used to draw an
+    /// [`Outline`][crate::canvas::Outline] over it. Like in the image,
+    /// [shadow catchers][crate::Material::with_shadow_catcher] are
+    /// invisible, so the surface behind them is found instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to render.
+    pub fn render_geometry(&self, world: &World) -> GeometryBuffer {
+        let samples = (0..self.hsize)
+            .into_par_iter()
+            .map(|x| {
+                (0..self.vsize)
+                    .into_par_iter()
+                    .map(move |y| {
+                        let ray = self.ray_for_pixel(x, y);
+                        let intersections = world.intersect(&ray);
+                        let hit = intersections
+                            .iter()
+                            .find(|i| i.t() >= 0.0 && !i.object().material().is_shadow_catcher());
+
+                        hit.map(|hit| {
+                            let info = hit.prepare_info(&ray);
+
+                            GeometrySample::new(
+                                hit.t(),
+                                info.shading_normal_vec(),
+                                world.object_id(hit.object()),
+                            )
+                        })
                     })
                     .collect::<Vec<_>>()
             })
             .collect::<Vec<_>>();
 
-        Canvas::from_pixels(pixels)
+        GeometryBuffer::from_samples(samples)
     }
 }
@@ -1,19 +1,27 @@
 use super::colors::Color;
 
+mod outline;
 pub mod renderers;
 
+pub use outline::{GeometryBuffer, GeometrySample, Outline};
+
 /// A canvas contains a 2D array of pixels.
+///
+/// Each pixel also has an alpha value describing how opaque it is, from 0 for
+/// fully transparent to 1 for fully opaque. This is used when compositing
+/// renders over other images.
 #[derive(Debug)]
 pub struct Canvas {
     width: usize,
     height: usize,
 
     pixels: Vec<Vec<Color>>,
+    alpha: Vec<Vec<f64>>,
 }
 
 impl Canvas {
     /// Construct a new canvas of the given size. The canvas will be filled with
-    /// black pixels by default.
+    /// opaque black pixels by default.
     ///
     /// # Arguments
     ///
@@ -28,6 +36,7 @@ impl Canvas {
     ///
     /// assert_eq!(c.width(), 10);
     /// assert_eq!(c.height(), 20);
+    /// assert_eq!(c.alpha_at(3, 4), 1.0);
     /// ```
     pub fn new(width: usize, height: usize) -> Self {
         let mut columns = Vec::with_capacity(width);
@@ -36,21 +45,49 @@ impl Canvas {
             columns.push(row);
         }
 
-        Self {
-            width,
-            height,
-            pixels: columns,
-        }
+        Self::from_pixels(columns)
     }
 
+    /// Construct a canvas from columns of opaque pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - The columns of pixels, from left to right. Each column
+    ///   lists its pixels from top to bottom.
     pub fn from_pixels(pixels: Vec<Vec<Color>>) -> Self {
+        let alpha = pixels
+            .iter()
+            .map(|column| vec![1.0; column.len()])
+            .collect();
+
+        Self::from_pixels_with_alpha(pixels, alpha)
+    }
+
+    /// Construct a canvas from columns of pixels and their alpha values.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - The columns of pixels, in the same layout as
+    ///   [`from_pixels`][Self::from_pixels].
+    /// * `alpha` - The alpha value of each pixel, in the same layout.
+    ///
+    /// # Panics
+    ///
+    /// If the pixels and alpha values have different dimensions.
+    pub fn from_pixels_with_alpha(pixels: Vec<Vec<Color>>, alpha: Vec<Vec<f64>>) -> Self {
         let width = pixels.len();
-        let height = pixels.get(0).map(Vec::len).unwrap_or(0);
+        let height = pixels.first().map(Vec::len).unwrap_or(0);
+
+        assert!(
+            alpha.len() == width && alpha.iter().all(|column| column.len() == height),
+            "Alpha values must match the dimensions of the pixels."
+        );
 
         Self {
             width,
             height,
             pixels,
+            alpha,
         }
     }
 
@@ -84,4 +121,25 @@ impl Canvas {
     pub fn write_pixel(&mut self, x: usize, y: usize, color: Color) {
         self.pixels[x][y] = color;
     }
+
+    /// Get the alpha value of the pixel at a specific location on the canvas.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x-coordinate of the pixel.
+    /// * `y` - The y-coordinate of the pixel.
+    pub fn alpha_at(&self, x: usize, y: usize) -> f64 {
+        self.alpha[x][y]
+    }
+
+    /// Set the alpha value of a specific pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x-coordinate of the pixel to write.
+    /// * `y` - The y-coordinate of the pixel to write.
+    /// * `alpha` - How opaque the pixel is, from 0 to 1.
+    pub fn write_alpha(&mut self, x: usize, y: usize, alpha: f64) {
+        self.alpha[x][y] = alpha;
+    }
 }
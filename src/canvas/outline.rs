@@ -0,0 +1,348 @@
+use std::f64::consts::FRAC_PI_4;
+
+use crate::{linear::Tuple, Color};
+
+use super::Canvas;
+
+/// Information about the surface seen through a pixel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeometrySample {
+    depth: f64,
+    normal: Tuple,
+    object_id: Option<usize>,
+}
+
+impl GeometrySample {
+    /// Create a new geometry sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The distance from the camera to the surface.
+    /// * `normal` - The surface's normal vector, facing the camera.
+    /// * `object_id` - The ID of the object the surface belongs to.
+    pub fn new(depth: f64, normal: Tuple, object_id: Option<usize>) -> Self {
+        Self {
+            depth,
+            normal,
+            object_id,
+        }
+    }
+
+    pub fn depth(&self) -> f64 {
+        self.depth
+    }
+
+    pub fn normal(&self) -> Tuple {
+        self.normal
+    }
+
+    pub fn object_id(&self) -> Option<usize> {
+        self.object_id
+    }
+}
+
+/// The surfaces seen through each pixel of a rendered image.
+///
+/// Geometry buffers are produced by
+/// [`Camera::render_geometry`][crate::camera::Camera::render_geometry] and
+/// are used to find the edges of objects for post-processing effects like
+/// outlines.
+#[derive(Clone, Debug)]
+pub struct GeometryBuffer {
+    width: usize,
+    height: usize,
+
+    // Pixels that don't see any surface have no sample.
+    samples: Vec<Vec<Option<GeometrySample>>>,
+}
+
+impl GeometryBuffer {
+    /// Create a geometry buffer from columns of samples, in the same layout
+    /// as [`Canvas::from_pixels`].
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The columns of samples.
+    pub fn from_samples(samples: Vec<Vec<Option<GeometrySample>>>) -> Self {
+        let width = samples.len();
+        let height = samples.first().map(Vec::len).unwrap_or(0);
+
+        Self {
+            width,
+            height,
+            samples,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the sample for a specific pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x-coordinate of the pixel.
+    /// * `y` - The y-coordinate of the pixel.
+    pub fn sample_at(&self, x: usize, y: usize) -> Option<GeometrySample> {
+        self.samples[x][y]
+    }
+}
+
+/// A post-processing pass that draws lines along the silhouettes and creases
+/// of objects, giving renders an inked look.
+///
+/// A pixel is part of an outline if the surface seen through it differs
+/// enough from the surface seen through one of its neighbours. Lines are
+/// drawn on the side of the edge closest to the camera, so an object's
+/// silhouette is drawn on the object rather than on whatever is behind it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Outline {
+    color: Color,
+    depth_threshold: f64,
+    crease_angle: f64,
+}
+
+impl Outline {
+    /// Create an outline pass that draws lines of a specific color.
+    ///
+    /// By default, lines are drawn where the depth jumps by more than 10%,
+    /// between different objects, and along creases sharper than 45 degrees.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{
+    /// #     camera::{view_transform, Camera},
+    /// #     canvas::Outline,
+    /// #     linear::Tuple,
+    /// #     Color, World,
+    /// # };
+    /// let world = World::default();
+    /// let camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2).with_transform(
+    ///     view_transform(
+    ///         &Tuple::new_point(0, 0, -5),
+    ///         &Tuple::new_point(0, 0, 0),
+    ///         &Tuple::new_vector(0, 1, 0),
+    ///     ),
+    /// );
+    ///
+    /// let mut image = camera.render(&world);
+    /// let geometry = camera.render_geometry(&world);
+    ///
+    /// Outline::new(Color::new(0, 0, 0)).apply(&mut image, &geometry);
+    /// ```
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            depth_threshold: 0.1,
+            crease_angle: FRAC_PI_4,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    pub fn depth_threshold(&self) -> f64 {
+        self.depth_threshold
+    }
+
+    /// Create a copy of the outline with a different depth threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth_threshold` - How much the depth of neighbouring pixels must
+    ///   differ, as a fraction of the nearer depth, to draw a line.
+    pub fn with_depth_threshold(&self, depth_threshold: f64) -> Self {
+        Self {
+            depth_threshold,
+            ..*self
+        }
+    }
+
+    pub fn crease_angle(&self) -> f64 {
+        self.crease_angle
+    }
+
+    /// Create a copy of the outline with a different crease angle.
+    ///
+    /// # Arguments
+    ///
+    /// * `crease_angle` - The angle, in radians, that the normals of
+    ///   neighbouring pixels must differ by to draw a line.
+    pub fn with_crease_angle(&self, crease_angle: f64) -> Self {
+        Self {
+            crease_angle,
+            ..*self
+        }
+    }
+
+    /// Draw the outlines onto a canvas.
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - The rendered image to draw onto.
+    /// * `geometry` - The geometry seen by each pixel of the image. This must
+    ///   be the same size as the canvas.
+    ///
+    /// # Panics
+    ///
+    /// If the canvas and geometry buffer are different sizes.
+    pub fn apply(&self, canvas: &mut Canvas, geometry: &GeometryBuffer) {
+        assert!(
+            canvas.width() == geometry.width() && canvas.height() == geometry.height(),
+            "Canvas is {}x{} but geometry buffer is {}x{}",
+            canvas.width(),
+            canvas.height(),
+            geometry.width(),
+            geometry.height()
+        );
+
+        for x in 0..geometry.width() {
+            for y in 0..geometry.height() {
+                if self.is_edge(geometry, x, y) {
+                    canvas.write_pixel(x, y, self.color);
+                }
+            }
+        }
+    }
+
+    fn is_edge(&self, geometry: &GeometryBuffer, x: usize, y: usize) -> bool {
+        let sample = match geometry.sample_at(x, y) {
+            Some(sample) => sample,
+            // Nothing is closer than the background, so lines are never
+            // drawn on it.
+            None => return false,
+        };
+
+        let neighbours = [
+            (x.checked_sub(1), Some(y)),
+            (Some(x + 1).filter(|&x| x < geometry.width()), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), Some(y + 1).filter(|&y| y < geometry.height())),
+        ];
+
+        neighbours.iter().any(|neighbour| match neighbour {
+            (Some(nx), Some(ny)) => match geometry.sample_at(*nx, *ny) {
+                Some(other) => self.is_discontinuity(&sample, &other),
+                None => true,
+            },
+            _ => false,
+        })
+    }
+
+    fn is_discontinuity(&self, sample: &GeometrySample, other: &GeometrySample) -> bool {
+        // Only the nearer side of a depth edge is drawn.
+        if sample.depth > other.depth {
+            return false;
+        }
+
+        let depth_jump = (other.depth - sample.depth) / sample.depth.max(1e-9);
+        let angle = sample.normal.dot(other.normal).clamp(-1.0, 1.0).acos();
+
+        sample.object_id != other.object_id
+            || depth_jump > self.depth_threshold
+            || angle > self.crease_angle
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn flat(depth: f64) -> Option<GeometrySample> {
+        Some(GeometrySample::new(
+            depth,
+            Tuple::new_vector(0, 0, -1),
+            Some(0),
+        ))
+    }
+
+    fn outlined(samples: Vec<Vec<Option<GeometrySample>>>) -> Vec<(usize, usize)> {
+        let geometry = GeometryBuffer::from_samples(samples);
+        let mut canvas = Canvas::new(geometry.width(), geometry.height());
+        let ink = Color::new(1, 1, 1);
+
+        Outline::new(ink).apply(&mut canvas, &geometry);
+
+        let mut edges = Vec::new();
+        for x in 0..canvas.width() {
+            for y in 0..canvas.height() {
+                if canvas.pixel_at(x, y) == ink {
+                    edges.push((x, y));
+                }
+            }
+        }
+
+        edges
+    }
+
+    #[test]
+    fn apply_flat_surface_has_no_outline() {
+        let edges = outlined(vec![vec![flat(5.0); 3]; 3]);
+
+        assert!(edges.is_empty());
+    }
+
+    #[test]
+    fn apply_silhouette_against_background() {
+        let edges = outlined(vec![vec![None, None], vec![flat(5.0), flat(5.0)]]);
+
+        assert_eq!(edges, vec![(1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn apply_depth_jump_drawn_on_near_side() {
+        let edges = outlined(vec![vec![flat(5.0)], vec![flat(8.0)], vec![flat(8.1)]]);
+
+        assert_eq!(edges, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn apply_crease() {
+        let sloped = Some(GeometrySample::new(
+            5.0,
+            Tuple::new_vector(1, 0, -2).normalized(),
+            Some(0),
+        ));
+        let steep = Some(GeometrySample::new(
+            5.0,
+            Tuple::new_vector(1.0, 0.0, -0.2).normalized(),
+            Some(0),
+        ));
+
+        // A gentle slope is within the default crease angle, but the steep
+        // surface is almost perpendicular to the camera.
+        assert!(outlined(vec![vec![flat(5.0)], vec![sloped]]).is_empty());
+        assert_eq!(outlined(vec![vec![flat(5.0)], vec![steep]]).len(), 2);
+    }
+
+    #[test]
+    fn apply_different_objects() {
+        let other = Some(GeometrySample::new(
+            5.0,
+            Tuple::new_vector(0, 0, -1),
+            Some(1),
+        ));
+
+        assert_eq!(outlined(vec![vec![flat(5.0)], vec![other]]).len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_mismatched_sizes() {
+        let geometry = GeometryBuffer::from_samples(vec![vec![flat(1.0)]]);
+        let mut canvas = Canvas::new(2, 2);
+
+        Outline::new(Color::new(0, 0, 0)).apply(&mut canvas, &geometry);
+    }
+}
@@ -36,7 +36,7 @@ pub fn render_as_ppm<T: std::io::Write>(
         let mut column = 0;
         for x in 0..canvas.width() {
             let pixel = canvas.pixel_at(x, y);
-            let pixel_values = vec![pixel.red(), pixel.green(), pixel.blue()];
+            let pixel_values = [pixel.red(), pixel.green(), pixel.blue()];
 
             for color_value in pixel_values.iter() {
                 let scaled_value = scale_color_value(*color_value).to_string();
@@ -67,6 +67,62 @@ pub fn render_as_ppm<T: std::io::Write>(
     Ok(())
 }
 
+/// Render a canvas as a PAM image, including its alpha channel.
+///
+/// PAM is an extension of the PPM format that supports transparency, which
+/// makes it suitable for compositing renders over other images. Pixels are
+/// stored as binary red, green, blue, and alpha bytes.
+///
+/// # Arguments
+///
+/// * `canvas` - The canvas to render.
+/// * `dest` - The output buffer to write the image data to.
+///
+/// # Examples
+///
+/// ```
+/// # use raytracer::canvas::{renderers::render_as_pam, Canvas};
+/// let mut canvas = Canvas::new(1, 1);
+/// canvas.write_alpha(0, 0, 0.0);
+///
+/// let mut output = Vec::new();
+/// render_as_pam(&canvas, &mut output).unwrap();
+///
+/// assert!(output.ends_with(b"ENDHDR\n\x00\x00\x00\x00"));
+/// ```
+pub fn render_as_pam<T: std::io::Write>(
+    canvas: &Canvas,
+    mut dest: T,
+) -> Result<(), std::io::Error> {
+    writeln!(dest, "P7")?;
+    writeln!(dest, "WIDTH {}", canvas.width())?;
+    writeln!(dest, "HEIGHT {}", canvas.height())?;
+    writeln!(dest, "DEPTH 4")?;
+    writeln!(dest, "MAXVAL {}", PPM_COLOR_MAX)?;
+    writeln!(dest, "TUPLTYPE RGB_ALPHA")?;
+    writeln!(dest, "ENDHDR")?;
+
+    let mut row = Vec::with_capacity(canvas.width() * 4);
+    for y in 0..canvas.height() {
+        row.clear();
+        for x in 0..canvas.width() {
+            let pixel = canvas.pixel_at(x, y);
+            let values = [
+                pixel.red(),
+                pixel.green(),
+                pixel.blue(),
+                canvas.alpha_at(x, y),
+            ];
+
+            row.extend(values.iter().map(|value| scale_color_value(*value) as u8));
+        }
+
+        dest.write_all(&row)?;
+    }
+
+    Ok(())
+}
+
 fn scale_color_value(value: f64) -> i64 {
     ((PPM_COLOR_MAX as f64 * value).round() as i64).clamp(0, PPM_COLOR_MAX as i64)
 }
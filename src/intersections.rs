@@ -1,13 +1,15 @@
 use float_cmp::approx_eq;
 use std::{fmt::Debug, ops};
 
-use crate::{linear::Tuple, objects::Shape, Ray};
+use crate::{linear::Tuple, objects::Shape, Color, Fresnel, Ray};
 
 /// A representation of a ray's intersection with a world object.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Intersection<'a> {
     t: f64,
     object: &'a dyn Shape,
+    face: Option<usize>,
+    uv: Option<(f64, f64)>,
 }
 
 impl<'a> Intersection<'a> {
@@ -31,7 +33,36 @@ impl<'a> Intersection<'a> {
     /// assert_eq!(intersection.object(), &sphere);
     /// ```
     pub fn new(t: f64, object: &'a dyn Shape) -> Self {
-        Self { t, object }
+        Self {
+            t,
+            object,
+            face: None,
+            uv: None,
+        }
+    }
+
+    /// Construct a new intersection with one face of an object made up of
+    /// several faces, such as a mesh.
+    ///
+    /// Recording the face lets the object find its normal without having to
+    /// work out which face the point is on.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time when the intersection occurred.
+    /// * `object` - The object that was intersected.
+    /// * `face` - The index of the face that was hit, as numbered by the
+    ///   object.
+    /// * `u`, `v` - The coordinates of the hit within the face, such as the
+    ///   barycentric coordinates within a triangle or the parametric
+    ///   coordinates on a patch.
+    pub fn with_face(t: f64, object: &'a dyn Shape, face: usize, u: f64, v: f64) -> Self {
+        Self {
+            t,
+            object,
+            face: Some(face),
+            uv: Some((u, v)),
+        }
     }
 
     pub fn t(&self) -> f64 {
@@ -42,8 +73,25 @@ impl<'a> Intersection<'a> {
         self.object
     }
 
+    /// Get the index of the face that was hit, if the object recorded it.
+    pub fn face(&self) -> Option<usize> {
+        self.face
+    }
+
+    /// Get the coordinates of the hit within its face, if the object recorded
+    /// them.
+    pub fn uv(&self) -> Option<(f64, f64)> {
+        self.uv
+    }
+
     /// Precompute information about an intersection.
     ///
+    /// The intersection is treated as the only one along the ray, so the ray
+    /// is assumed to pass between the intersected object and empty space when
+    /// computing refraction. Use
+    /// [`prepare_info_with`][Self::prepare_info_with] when the other
+    /// intersections along the ray are known.
+    ///
     /// # Arguments
     ///
     /// * `ray` - The ray used in the intersection.
@@ -69,10 +117,60 @@ impl<'a> Intersection<'a> {
     /// assert_eq!(info.eye_vec(), Tuple::new_vector(0, 0, -1));
     /// assert_eq!(info.normal_vec(), Tuple::new_vector(0, 0, -1));
     /// ```
-    pub fn prepare_info(&self, ray: &Ray) -> IntersectionInfo {
+    pub fn prepare_info(&self, ray: &Ray) -> IntersectionInfo<'a> {
+        self.prepare_info_with(ray, &Intersections::new(vec![*self]))
+    }
+
+    /// Precompute information about an intersection, using all of the
+    /// intersections along the ray to work out which materials the ray passes
+    /// between.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray used in the intersection.
+    /// * `intersections` - Every intersection along the ray, including this
+    ///   one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{
+    ///     intersections::{Intersection, Intersections},
+    ///     linear::{Matrix, Tuple},
+    ///     objects::{Sphere, Shape},
+    ///     Material, Ray,
+    /// };
+    /// let mut glass = Sphere::default();
+    /// glass.set_material(Material::default().with_refractive_index(1.5));
+    ///
+    /// let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    /// let intersections = Intersections::new(vec![
+    ///     Intersection::new(4.0, &glass),
+    ///     Intersection::new(6.0, &glass),
+    /// ]);
+    ///
+    /// let entering = intersections[0].prepare_info_with(&r, &intersections);
+    /// let leaving = intersections[1].prepare_info_with(&r, &intersections);
+    ///
+    /// assert_eq!((entering.n1(), entering.n2()), (1.0, 1.5));
+    /// assert_eq!((leaving.n1(), leaving.n2()), (1.5, 1.0));
+    /// ```
+    pub fn prepare_info_with(
+        &self,
+        ray: &Ray,
+        intersections: &Intersections<'a>,
+    ) -> IntersectionInfo<'a> {
         let point = ray.position_at(self.t);
         let eye_vec = -ray.direction();
-        let mut normal_vec = self.object.normal_at(&point);
+        let mut normal_vec = self.object.normal_at_hit(&point, self);
+        let mut shading_normal_vec = match self.object.material().normal_map() {
+            Some(normal_map) => {
+                let frame = self.object.tangent_frame_at_hit(&point, self);
+
+                normal_map.normal_at_shape(self.object, &point, &frame)
+            }
+            None => normal_vec,
+        };
 
         // The normal vector always points to the outside of the shape. If the
         // hit comes from inside the shape, the eye vector and normal vector
@@ -82,20 +180,73 @@ impl<'a> Intersection<'a> {
         let inside = normal_vec.dot(eye_vec) < 0.0;
         if inside {
             normal_vec = -normal_vec;
+            shading_normal_vec = -shading_normal_vec;
         }
 
         // Compute the offset point used for shadow calculations.
         let over_point = point + normal_vec * 1e-5;
 
+        let under_point = point - normal_vec * 1e-5;
+
+        let reflect_vec = ray.direction().reflected_over(&normal_vec);
+
+        let (from, to) = self.media(intersections);
+        let refractive_index =
+            |medium: Option<&dyn Shape>| medium.map_or(1.0, |m| m.material().refractive_index());
+
         IntersectionInfo {
             t: self.t,
             object: self.object,
             point,
             over_point,
+            under_point,
             eye_vec,
             normal_vec,
+            shading_normal_vec,
+            reflect_vec,
             inside,
+            n1: refractive_index(from),
+            n2: refractive_index(to),
+            refracted_medium: to,
+        }
+    }
+
+    /// Find the objects that the ray is inside of on either side of the
+    /// intersection.
+    ///
+    /// The ray is tracked as it enters and leaves each object, so that when it
+    /// reaches this intersection we know which object it is leaving and which
+    /// one it is entering. `None` represents empty space.
+    fn media(
+        &self,
+        intersections: &Intersections<'a>,
+    ) -> (Option<&'a dyn Shape>, Option<&'a dyn Shape>) {
+        let mut containers: Vec<&'a dyn Shape> = Vec::new();
+
+        for intersection in intersections.intersections.iter() {
+            let is_hit = intersection == self;
+            let before = containers.last().copied();
+
+            // Hitting an object we're inside of means the ray is leaving it,
+            // otherwise the ray is entering it.
+            match containers
+                .iter()
+                .position(|object| *object == intersection.object)
+            {
+                Some(index) => {
+                    containers.remove(index);
+                }
+                None => containers.push(intersection.object),
+            }
+
+            if is_hit {
+                return (before, containers.last().copied());
+            }
         }
+
+        // The intersection wasn't in the list, so the best we can do is treat
+        // the object as being surrounded by empty space.
+        (None, Some(self.object))
     }
 }
 
@@ -116,6 +267,12 @@ pub struct IntersectionInfo<'a> {
     eye_vec: Tuple,
     inside: bool,
     normal_vec: Tuple,
+    shading_normal_vec: Tuple,
+    reflect_vec: Tuple,
+    under_point: Tuple,
+    n1: f64,
+    n2: f64,
+    refracted_medium: Option<&'a dyn Shape>,
 }
 
 impl<'a> IntersectionInfo<'a> {
@@ -142,17 +299,157 @@ impl<'a> IntersectionInfo<'a> {
         self.over_point
     }
 
+    /// Get a point slightly offset from the computed intersection point
+    /// against the direction of the normal vector, just beneath the surface.
+    ///
+    /// This is where refracted rays start from, for the same reason that
+    /// [`over_point`][Self::over_point] is used for shadows.
+    pub fn under_point(&self) -> Tuple {
+        self.under_point
+    }
+
     pub fn eye_vec(&self) -> Tuple {
         self.eye_vec
     }
 
+    /// Get the geometric normal vector of the surface at the intersection.
+    ///
+    /// This follows the actual surface of the object, so it's used to offset
+    /// points for shadows and refraction.
     pub fn normal_vec(&self) -> Tuple {
         self.normal_vec
     }
 
+    /// Get the normal vector used to shade the surface at the intersection.
+    ///
+    /// This is the same as the [geometric normal][Self::normal_vec] unless the
+    /// object's material has a [normal map][crate::Material::normal_map].
+    pub fn shading_normal_vec(&self) -> Tuple {
+        self.shading_normal_vec
+    }
+
+    /// Get the direction the ray is reflected in when it bounces off the
+    /// surface.
+    pub fn reflect_vec(&self) -> Tuple {
+        self.reflect_vec
+    }
+
     pub fn inside(&self) -> bool {
         self.inside
     }
+
+    /// Get the refractive index of the material the ray is leaving.
+    pub fn n1(&self) -> f64 {
+        self.n1
+    }
+
+    /// Get the refractive index of the material the ray is entering.
+    pub fn n2(&self) -> f64 {
+        self.n2
+    }
+
+    /// Get the object that light refracted at the intersection travels
+    /// through, or `None` if it travels through empty space.
+    pub fn refracted_medium(&self) -> Option<&'a dyn Shape> {
+        self.refracted_medium
+    }
+
+    /// Approximate the fraction of light that is reflected rather than
+    /// refracted at the intersection, using Schlick's approximation.
+    ///
+    /// The reflectance increases towards 1 as the eye vector approaches a
+    /// grazing angle with the surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{
+    ///     intersections::Intersection,
+    ///     linear::Tuple,
+    ///     objects::{Sphere, Shape},
+    ///     Material, Ray,
+    /// };
+    /// let mut glass = Sphere::default();
+    /// glass.set_material(Material::default().with_refractive_index(1.5));
+    /// let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    ///
+    /// let info = Intersection::new(4.0, &glass).prepare_info(&r);
+    ///
+    /// assert!((info.schlick() - 0.04).abs() < 1e-9);
+    /// ```
+    pub fn schlick(&self) -> f64 {
+        let mut cos = self.eye_vec.dot(self.normal_vec);
+
+        // Total internal reflection can only occur when leaving a denser
+        // material.
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n.powi(2) * (1.0 - cos.powi(2));
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+
+            // Use the angle of the transmitted ray instead, since the
+            // approximation is only accurate for the angle in the less dense
+            // material.
+            cos = (1.0 - sin2_t).sqrt();
+        }
+
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
+
+    /// Compute the fraction of light that is reflected rather than refracted
+    /// at the intersection, using the exact Fresnel equations for unpolarized
+    /// light.
+    ///
+    /// This is slightly more expensive than [`schlick`][Self::schlick], and
+    /// differs from it most for materials with high refractive indices.
+    pub fn fresnel(&self) -> f64 {
+        let cos_i = self.eye_vec.dot(self.normal_vec);
+        let sin2_t = (self.n1 / self.n2).powi(2) * (1.0 - cos_i.powi(2));
+        if sin2_t > 1.0 {
+            return 1.0;
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+
+        // Average the reflectance of light polarized perpendicular and
+        // parallel to the plane of incidence.
+        let perpendicular =
+            (self.n1 * cos_i - self.n2 * cos_t) / (self.n1 * cos_i + self.n2 * cos_t);
+        let parallel = (self.n1 * cos_t - self.n2 * cos_i) / (self.n1 * cos_t + self.n2 * cos_i);
+
+        (perpendicular.powi(2) + parallel.powi(2)) / 2.0
+    }
+
+    /// Compute the fraction of light reflected at the intersection using the
+    /// intersected object's choice of [`Fresnel`] model.
+    pub fn reflectance(&self) -> f64 {
+        match self.object.material().fresnel() {
+            Fresnel::Schlick => self.schlick(),
+            Fresnel::Exact => self.fresnel(),
+        }
+    }
+
+    /// Compute the fraction of the red, green, and blue components of light
+    /// reflected at the intersection.
+    ///
+    /// If the intersected object's material is coated with a
+    /// [thin film][crate::ThinFilm], the reflectance of each component
+    /// depends on the angle the surface is viewed from. Otherwise, every
+    /// component matches the [`reflectance`][Self::reflectance].
+    pub fn spectral_reflectance(&self) -> Color {
+        match self.object.material().thin_film() {
+            Some(film) => film.reflectance(self.n1, self.n2, self.eye_vec.dot(self.normal_vec)),
+            None => {
+                let reflectance = self.reflectance();
+
+                Color::new(reflectance, reflectance, reflectance)
+            }
+        }
+    }
 }
 
 /// A collection of intersections.
@@ -213,7 +510,7 @@ impl<'a> Intersections<'a> {
     /// Determine which intersection from the collection is the first to be hit.
     ///
     /// This is always the intersection with the lowest non-negative `t` value.
-    pub fn hit(&self) -> Option<&Intersection> {
+    pub fn hit(&self) -> Option<&Intersection<'a>> {
         self.intersections.iter().find(|i| i.t >= 0.0)
     }
 
@@ -222,6 +519,11 @@ impl<'a> Intersections<'a> {
         self.intersections.is_empty()
     }
 
+    /// Iterate over the intersections in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Intersection<'a>> {
+        self.intersections.iter()
+    }
+
     /// Find the number of elements in the intersection collection.
     pub fn len(&self) -> usize {
         self.intersections.len()
@@ -6,7 +6,10 @@ pub mod canvas;
 pub mod intersections;
 pub mod lights;
 pub mod linear;
+pub mod loaders;
+pub mod noise;
 pub mod objects;
+pub mod patterns;
 
 mod colors;
 mod materials;
@@ -14,6 +17,8 @@ mod rays;
 mod world;
 
 pub use colors::Color;
-pub use materials::Material;
+pub use materials::{
+    DiffuseModel, Fresnel, Material, Preset, ShadingModel, SpecularModel, ThinFilm, UnknownPreset,
+};
 pub use rays::Ray;
 pub use world::{World, DEFAULT_LIGHT, DEFAULT_SPHERE_1, DEFAULT_SPHERE_2};
@@ -0,0 +1,77 @@
+use crate::{linear::Tuple, Color};
+
+use super::Light;
+
+/// A directional light is infinitely far away, so its light arrives from the
+/// same direction everywhere, like sunlight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectionalLight {
+    direction: Tuple,
+    intensity: Color,
+}
+
+impl DirectionalLight {
+    /// Create a new directional light.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The direction the light travels in.
+    /// * `intensity` - The color emitted by the light.
+    ///
+    /// # Panics
+    ///
+    /// If `direction` has no length, since the light wouldn't travel anywhere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{lights::{DirectionalLight, Light}, linear::Tuple, Color};
+    /// let sun = DirectionalLight::new(Tuple::new_vector(0, -2, 0), Color::new(1, 1, 1));
+    ///
+    /// assert_eq!(
+    ///     sun.direction_from(&Tuple::new_point(5, 0, 5)),
+    ///     Tuple::new_vector(0, 1, 0)
+    /// );
+    /// assert_eq!(sun.distance_from(&Tuple::new_point(5, 0, 5)), f64::INFINITY);
+    /// ```
+    pub fn new(direction: Tuple, intensity: Color) -> Self {
+        assert!(
+            direction.magnitude() > 0.0,
+            "Directional light direction must not be zero."
+        );
+
+        Self {
+            direction: direction.normalized(),
+            intensity,
+        }
+    }
+
+    pub fn direction(&self) -> Tuple {
+        self.direction
+    }
+}
+
+impl Light for DirectionalLight {
+    fn direction_from(&self, _point: &Tuple) -> Tuple {
+        -self.direction
+    }
+
+    fn distance_from(&self, _point: &Tuple) -> f64 {
+        f64::INFINITY
+    }
+
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn new_zero_direction() {
+        DirectionalLight::new(Tuple::new_vector(0, 0, 0), Color::new(1, 1, 1));
+    }
+}
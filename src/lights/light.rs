@@ -0,0 +1,53 @@
+use crate::{linear::Tuple, Color};
+
+/// A source of light that illuminates the objects in a world.
+pub trait Light: std::fmt::Debug + Send + Sync {
+    /// Find the direction from a point towards the light.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point being lit.
+    fn direction_from(&self, point: &Tuple) -> Tuple;
+
+    /// Find the distance from a point to the light, which is infinite for
+    /// lights that are infinitely far away.
+    ///
+    /// Objects only cast shadows if they are closer to the point than the
+    /// light is.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point being lit.
+    fn distance_from(&self, point: &Tuple) -> f64;
+
+    /// Retrieve the color and brightness of the light.
+    fn intensity(&self) -> Color;
+
+    /// Find the intensity of the light arriving at a point.
+    ///
+    /// By default, every point receives the light's full
+    /// [`intensity`][Self::intensity].
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point being lit.
+    fn intensity_at(&self, _point: &Tuple) -> Color {
+        self.intensity()
+    }
+
+    /// Find the directions and distances from a point to the samples that
+    /// make up the light, which are used to check how much of the light
+    /// reaches the point.
+    ///
+    /// By default, the light is a single sample in the
+    /// [direction][Self::direction_from] of the light. Lights with a size can
+    /// return several samples across their surface, so that points where
+    /// only some of the samples are blocked are partly shadowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point being lit.
+    fn samples_from(&self, point: &Tuple) -> Vec<(Tuple, f64)> {
+        vec![(self.direction_from(point), self.distance_from(point))]
+    }
+}
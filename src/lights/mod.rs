@@ -1,3 +1,9 @@
+mod directional_light;
+mod light;
 mod point_light;
+mod spot_light;
 
+pub use directional_light::DirectionalLight;
+pub use light::Light;
 pub use point_light::PointLight;
+pub use spot_light::SpotLight;
@@ -1,5 +1,7 @@
 use crate::{linear::Tuple, Color};
 
+use super::Light;
+
 /// A point light emits a specific intensity from a single point in space.
 #[derive(Clone, Copy, Debug)]
 pub struct PointLight {
@@ -45,6 +47,20 @@ impl PointLight {
     }
 }
 
+impl Light for PointLight {
+    fn direction_from(&self, point: &Tuple) -> Tuple {
+        (self.position - *point).normalized()
+    }
+
+    fn distance_from(&self, point: &Tuple) -> f64 {
+        (self.position - *point).magnitude()
+    }
+
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+}
+
 impl PartialEq for PointLight {
     fn eq(&self, other: &Self) -> bool {
         self.position == other.position && self.intensity == other.intensity
@@ -0,0 +1,217 @@
+use crate::{linear::Tuple, Color};
+
+use super::Light;
+
+/// A spot light shines from a single point in a cone, like a flashlight or a
+/// stage light.
+///
+/// Points inside the inner cone receive the light's full intensity, which
+/// fades smoothly to nothing at the edge of the outer cone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpotLight {
+    position: Tuple,
+    direction: Tuple,
+    inner_angle: f64,
+    outer_angle: f64,
+    intensity: Color,
+}
+
+impl SpotLight {
+    /// Create a new spot light.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The location of the light source.
+    /// * `direction` - The direction the light is pointing in.
+    /// * `inner_angle` - The angle, in radians, between the direction and the
+    ///   edge of the fully lit cone.
+    /// * `outer_angle` - The angle, in radians, between the direction and the
+    ///   edge of the cone that receives any light. This should be at least as
+    ///   large as the inner angle.
+    /// * `intensity` - The color emitted by the light.
+    ///
+    /// # Panics
+    ///
+    /// If `direction` has no length, since the light wouldn't point anywhere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::f64::consts::PI;
+    /// # use raytracer::{lights::{Light, SpotLight}, linear::Tuple, Color};
+    /// let spot = SpotLight::new(
+    ///     Tuple::new_point(0, 10, 0),
+    ///     Tuple::new_vector(0, -1, 0),
+    ///     PI / 8.0,
+    ///     PI / 6.0,
+    ///     Color::new(1, 1, 1),
+    /// );
+    ///
+    /// assert_eq!(spot.intensity_at(&Tuple::new_point(0, 0, 0)), Color::new(1, 1, 1));
+    /// assert_eq!(spot.intensity_at(&Tuple::new_point(10, 0, 0)), Color::new(0, 0, 0));
+    /// ```
+    pub fn new(
+        position: Tuple,
+        direction: Tuple,
+        inner_angle: f64,
+        outer_angle: f64,
+        intensity: Color,
+    ) -> Self {
+        assert!(
+            direction.magnitude() > 0.0,
+            "Spot light direction must not be zero."
+        );
+
+        Self {
+            position,
+            direction: direction.normalized(),
+            inner_angle,
+            outer_angle,
+            intensity,
+        }
+    }
+
+    pub fn position(&self) -> Tuple {
+        self.position
+    }
+
+    pub fn direction(&self) -> Tuple {
+        self.direction
+    }
+
+    pub fn inner_angle(&self) -> f64 {
+        self.inner_angle
+    }
+
+    pub fn outer_angle(&self) -> f64 {
+        self.outer_angle
+    }
+
+    /// Find how much of the light's intensity reaches a point, based on how
+    /// far it is from the center of the cone.
+    fn falloff(&self, point: &Tuple) -> f64 {
+        // The light shines out from its position, so that point is as lit as
+        // the middle of the cone.
+        let to_point = *point - self.position;
+        if to_point.magnitude() == 0.0 {
+            return 1.0;
+        }
+
+        let cos_angle = to_point.normalized().dot(self.direction);
+        let cos_inner = self.inner_angle.cos();
+        let cos_outer = self.outer_angle.cos();
+
+        if cos_angle >= cos_inner {
+            return 1.0;
+        }
+        if cos_angle <= cos_outer {
+            return 0.0;
+        }
+
+        // Smoothstep between the edges of the cones, so the edge of the light
+        // doesn't have a visible seam.
+        let t = (cos_angle - cos_outer) / (cos_inner - cos_outer);
+
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+impl Light for SpotLight {
+    fn direction_from(&self, point: &Tuple) -> Tuple {
+        (self.position - *point).normalized()
+    }
+
+    fn distance_from(&self, point: &Tuple) -> f64 {
+        (self.position - *point).magnitude()
+    }
+
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn intensity_at(&self, point: &Tuple) -> Color {
+        self.intensity * self.falloff(point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    fn overhead_spot() -> SpotLight {
+        SpotLight::new(
+            Tuple::new_point(0, 1, 0),
+            Tuple::new_vector(0, -1, 0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::new(1, 1, 1),
+        )
+    }
+
+    #[test]
+    fn intensity_at_inside_inner_cone() {
+        let spot = overhead_spot();
+
+        assert_eq!(
+            spot.intensity_at(&Tuple::new_point(0.1, 0.0, 0.0)),
+            Color::new(1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn intensity_at_outside_outer_cone() {
+        let spot = overhead_spot();
+
+        assert_eq!(
+            spot.intensity_at(&Tuple::new_point(2, 0, 0)),
+            Color::new(0, 0, 0)
+        );
+        assert_eq!(
+            spot.intensity_at(&Tuple::new_point(0, 2, 0)),
+            Color::new(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn intensity_at_fades_between_cones() {
+        let spot = overhead_spot();
+        let brightness = |x: f64| spot.intensity_at(&Tuple::new_point(x, 0.0, 0.0)).red();
+
+        // The edges of the cones are at x = 0.414 and x = 1.
+        let near = brightness(0.5);
+        let middle = brightness(0.7);
+        let far = brightness(0.9);
+
+        assert!(1.0 > near && near > middle && middle > far && far > 0.0);
+    }
+
+    #[test]
+    fn direction_and_distance_from() {
+        let spot = overhead_spot();
+        let point = Tuple::new_point(0, -3, 0);
+
+        assert_eq!(spot.direction_from(&point), Tuple::new_vector(0, 1, 0));
+        assert_eq!(spot.distance_from(&point), 4.0);
+    }
+
+    #[test]
+    fn intensity_at_light_position() {
+        let spot = overhead_spot();
+
+        assert_eq!(spot.intensity_at(&spot.position()), Color::new(1, 1, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_zero_direction() {
+        SpotLight::new(
+            Tuple::new_point(0, 1, 0),
+            Tuple::new_vector(0, 0, 0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::new(1, 1, 1),
+        );
+    }
+}
@@ -228,7 +228,7 @@ impl Matrix {
     /// `row` - The row index of the element to find the cofactor of.
     /// `col` - The column index of the element to find the cofactor of.
     pub fn cofactor(&self, row: usize, col: usize) -> f64 {
-        if (row + col) % 2 == 0 {
+        if (row + col).is_multiple_of(2) {
             self.minor(row, col)
         } else {
             -self.minor(row, col)
@@ -0,0 +1,18 @@
+//! Loaders for geometry and images produced by other tools.
+//!
+//! Loaders read from any [`Read`][std::io::Read] source. Malformed input is
+//! reported as an error with the kind [`InvalidData`][std::io::ErrorKind]
+//! rather than causing a panic.
+
+mod ply;
+mod ppm;
+mod stl;
+
+pub use ply::load_ply;
+pub use ppm::load_ppm;
+pub use stl::load_stl;
+
+/// Construct the error returned when a file doesn't match its format.
+fn malformed<S: Into<String>>(message: S) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.into())
+}
@@ -0,0 +1,435 @@
+use std::{convert::TryInto, io::Read};
+
+use crate::{linear::Tuple, objects::Mesh};
+
+use super::malformed;
+
+/// The encoding used for the body of a PLY file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// The type of a single value in a PLY file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    fn parse(name: &str) -> Result<Self, std::io::Error> {
+        match name {
+            "char" | "int8" => Ok(Self::I8),
+            "uchar" | "uint8" => Ok(Self::U8),
+            "short" | "int16" => Ok(Self::I16),
+            "ushort" | "uint16" => Ok(Self::U16),
+            "int" | "int32" => Ok(Self::I32),
+            "uint" | "uint32" => Ok(Self::U32),
+            "float" | "float32" => Ok(Self::F32),
+            "double" | "float64" => Ok(Self::F64),
+            _ => Err(malformed(format!("Unknown PLY property type '{}'.", name))),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Property {
+    Scalar {
+        name: String,
+        value_type: ScalarType,
+    },
+    List {
+        name: String,
+        count_type: ScalarType,
+        value_type: ScalarType,
+    },
+}
+
+impl Property {
+    fn name(&self) -> &str {
+        match self {
+            Self::Scalar { name, .. } | Self::List { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+impl Element {
+    fn property_index(&self, name: &str) -> Option<usize> {
+        self.properties.iter().position(|p| p.name() == name)
+    }
+}
+
+/// Reads values from the body of a PLY file regardless of its encoding.
+enum BodyReader<'a> {
+    Ascii(std::str::SplitWhitespace<'a>),
+    Binary {
+        data: &'a [u8],
+        offset: usize,
+        big_endian: bool,
+    },
+}
+
+impl<'a> BodyReader<'a> {
+    fn read(&mut self, value_type: ScalarType) -> Result<f64, std::io::Error> {
+        match self {
+            Self::Ascii(tokens) => {
+                let token = tokens
+                    .next()
+                    .ok_or_else(|| malformed("Unexpected end of PLY data."))?;
+
+                token
+                    .parse()
+                    .map_err(|_| malformed(format!("Expected a number but found '{}'.", token)))
+            }
+            Self::Binary {
+                data,
+                offset,
+                big_endian,
+            } => {
+                let size = value_type.size();
+                let bytes = data
+                    .get(*offset..*offset + size)
+                    .ok_or_else(|| malformed("Unexpected end of PLY data."))?;
+                *offset += size;
+
+                Ok(decode(bytes, value_type, *big_endian))
+            }
+        }
+    }
+}
+
+/// Decode a binary value that is known to have the correct number of bytes.
+fn decode(bytes: &[u8], value_type: ScalarType, big_endian: bool) -> f64 {
+    macro_rules! from_bytes {
+        ($t:ty) => {{
+            let bytes = bytes.try_into().unwrap();
+            if big_endian {
+                <$t>::from_be_bytes(bytes) as f64
+            } else {
+                <$t>::from_le_bytes(bytes) as f64
+            }
+        }};
+    }
+
+    match value_type {
+        ScalarType::I8 => from_bytes!(i8),
+        ScalarType::U8 => from_bytes!(u8),
+        ScalarType::I16 => from_bytes!(i16),
+        ScalarType::U16 => from_bytes!(u16),
+        ScalarType::I32 => from_bytes!(i32),
+        ScalarType::U32 => from_bytes!(u32),
+        ScalarType::F32 => from_bytes!(f32),
+        ScalarType::F64 => from_bytes!(f64),
+    }
+}
+
+/// Load a mesh from a PLY file.
+///
+/// ASCII and both binary encodings are supported. Vertex positions are read
+/// from the `x`, `y`, and `z` properties of the `vertex` element. If the
+/// vertices also have `nx`, `ny`, and `nz` properties, they are used to smoothly
+/// shade the mesh. Faces with more than three vertices are split into
+/// triangles, and any other elements are ignored.
+///
+/// # Arguments
+///
+/// * `source` - The reader to load the file from.
+///
+/// # Examples
+///
+/// ```
+/// # use raytracer::loaders::load_ply;
+/// let ply = "ply
+/// format ascii 1.0
+/// element vertex 4
+/// property float x
+/// property float y
+/// property float z
+/// element face 1
+/// property list uchar int vertex_indices
+/// end_header
+/// 0 0 0
+/// 1 0 0
+/// 1 1 0
+/// 0 1 0
+/// 4 0 1 2 3
+/// ";
+///
+/// let mesh = load_ply(ply.as_bytes()).unwrap();
+///
+/// // The square face is split into two triangles.
+/// assert_eq!(mesh.face_count(), 2);
+/// ```
+pub fn load_ply<R: Read>(mut source: R) -> Result<Mesh, std::io::Error> {
+    let mut contents = Vec::new();
+    source.read_to_end(&mut contents)?;
+
+    let (format, elements, body_start) = parse_header(&contents)?;
+    let body = &contents[body_start..];
+
+    let mut reader = match format {
+        Format::Ascii => BodyReader::Ascii(
+            std::str::from_utf8(body)
+                .map_err(|_| malformed("ASCII PLY data contains invalid UTF-8."))?
+                .split_whitespace(),
+        ),
+        Format::BinaryLittleEndian | Format::BinaryBigEndian => BodyReader::Binary {
+            data: body,
+            offset: 0,
+            big_endian: format == Format::BinaryBigEndian,
+        },
+    };
+
+    let mut vertices = Vec::new();
+    let mut normals = Vec::new();
+    let mut faces = Vec::new();
+
+    for element in elements.iter() {
+        match element.name.as_str() {
+            "vertex" => read_vertices(&mut reader, element, &mut vertices, &mut normals)?,
+            "face" => read_faces(&mut reader, element, &mut faces)?,
+            _ => skip_element(&mut reader, element)?,
+        }
+    }
+
+    if let Some(index) = faces.iter().flatten().find(|&&i| i >= vertices.len()) {
+        return Err(malformed(format!(
+            "Face refers to vertex {}, but there are only {} vertices.",
+            index,
+            vertices.len()
+        )));
+    }
+
+    if normals.is_empty() {
+        Ok(Mesh::new(vertices, faces))
+    } else {
+        Ok(Mesh::with_normals(vertices, normals, faces))
+    }
+}
+
+/// Parse the header of a PLY file.
+///
+/// Returns the body's encoding, the elements making up the body, and the
+/// offset of the first byte of the body.
+fn parse_header(contents: &[u8]) -> Result<(Format, Vec<Element>, usize), std::io::Error> {
+    const END_HEADER: &[u8] = b"\nend_header";
+
+    // The header ends at the first line that is exactly `end_header`.
+    let header_end = contents
+        .windows(END_HEADER.len())
+        .enumerate()
+        .filter(|(_, window)| *window == END_HEADER)
+        .map(|(i, _)| i + 1)
+        .find(|&i| {
+            matches!(
+                contents.get(i + END_HEADER.len() - 1),
+                None | Some(b'\n') | Some(b'\r')
+            )
+        })
+        .ok_or_else(|| malformed("PLY header is missing 'end_header'."))?;
+
+    // The body starts on the line after `end_header`.
+    let body_start = contents[header_end..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|p| header_end + p + 1)
+        .unwrap_or(contents.len());
+
+    let header = std::str::from_utf8(&contents[..header_end])
+        .map_err(|_| malformed("PLY header contains invalid UTF-8."))?;
+    let mut lines = header.lines().map(str::trim);
+
+    if lines.next() != Some("ply") {
+        return Err(malformed("File does not start with 'ply'."));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+
+    for line in lines {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            [] | ["comment", ..] | ["obj_info", ..] => (),
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::BinaryLittleEndian,
+                    "binary_big_endian" => Format::BinaryBigEndian,
+                    _ => return Err(malformed(format!("Unknown PLY format '{}'.", name))),
+                })
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| malformed(format!("Invalid element count '{}'.", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, value_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| malformed("PLY property declared before any element."))?
+                .properties
+                .push(Property::List {
+                    name: name.to_string(),
+                    count_type: ScalarType::parse(count_type)?,
+                    value_type: ScalarType::parse(value_type)?,
+                }),
+            ["property", value_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| malformed("PLY property declared before any element."))?
+                .properties
+                .push(Property::Scalar {
+                    name: name.to_string(),
+                    value_type: ScalarType::parse(value_type)?,
+                }),
+            _ => return Err(malformed(format!("Invalid PLY header line '{}'.", line))),
+        }
+    }
+
+    let format = format.ok_or_else(|| malformed("PLY header is missing a format."))?;
+
+    Ok((format, elements, body_start))
+}
+
+/// Read one instance of an element, returning the values of its scalar
+/// properties and the contents of its list properties.
+fn read_row(
+    reader: &mut BodyReader,
+    element: &Element,
+) -> Result<(Vec<f64>, Vec<Vec<f64>>), std::io::Error> {
+    let mut scalars = vec![0.0; element.properties.len()];
+    let mut lists = vec![Vec::new(); element.properties.len()];
+
+    for (i, property) in element.properties.iter().enumerate() {
+        match property {
+            Property::Scalar { value_type, .. } => scalars[i] = reader.read(*value_type)?,
+            Property::List {
+                count_type,
+                value_type,
+                ..
+            } => {
+                let count = reader.read(*count_type)?;
+                if count < 0.0 || count.fract() != 0.0 {
+                    return Err(malformed(format!("Invalid list length {}.", count)));
+                }
+
+                for _ in 0..count as usize {
+                    lists[i].push(reader.read(*value_type)?);
+                }
+            }
+        }
+    }
+
+    Ok((scalars, lists))
+}
+
+fn read_vertices(
+    reader: &mut BodyReader,
+    element: &Element,
+    vertices: &mut Vec<Tuple>,
+    normals: &mut Vec<Tuple>,
+) -> Result<(), std::io::Error> {
+    let position = ["x", "y", "z"]
+        .iter()
+        .map(|name| element.property_index(name))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| malformed("PLY vertices must have 'x', 'y', and 'z' properties."))?;
+    let normal = ["nx", "ny", "nz"]
+        .iter()
+        .map(|name| element.property_index(name))
+        .collect::<Option<Vec<_>>>();
+
+    for _ in 0..element.count {
+        let (values, _) = read_row(reader, element)?;
+
+        vertices.push(Tuple::new_point(
+            values[position[0]],
+            values[position[1]],
+            values[position[2]],
+        ));
+
+        if let Some(normal) = &normal {
+            // Normals are normalized by the mesh, which also repairs any that
+            // have no length.
+            normals.push(Tuple::new_vector(
+                values[normal[0]],
+                values[normal[1]],
+                values[normal[2]],
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn read_faces(
+    reader: &mut BodyReader,
+    element: &Element,
+    faces: &mut Vec<[usize; 3]>,
+) -> Result<(), std::io::Error> {
+    let indices = element
+        .property_index("vertex_indices")
+        .or_else(|| element.property_index("vertex_index"))
+        .ok_or_else(|| malformed("PLY faces must have a 'vertex_indices' property."))?;
+
+    for _ in 0..element.count {
+        let (_, lists) = read_row(reader, element)?;
+        let polygon = &lists[indices];
+
+        if polygon.len() < 3 {
+            return Err(malformed(format!(
+                "PLY face has {} vertices, but at least 3 are required.",
+                polygon.len()
+            )));
+        }
+
+        if polygon.iter().any(|&i| i < 0.0 || i.fract() != 0.0) {
+            return Err(malformed("PLY face has an invalid vertex index."));
+        }
+
+        // Split polygons into a fan of triangles around the first vertex.
+        for i in 1..polygon.len() - 1 {
+            faces.push([
+                polygon[0] as usize,
+                polygon[i] as usize,
+                polygon[i + 1] as usize,
+            ]);
+        }
+    }
+
+    Ok(())
+}
+
+fn skip_element(reader: &mut BodyReader, element: &Element) -> Result<(), std::io::Error> {
+    for _ in 0..element.count {
+        read_row(reader, element)?;
+    }
+
+    Ok(())
+}
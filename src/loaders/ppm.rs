@@ -0,0 +1,175 @@
+use std::io::Read;
+
+use crate::{canvas::Canvas, Color};
+
+use super::malformed;
+
+/// Load an image from a PPM file.
+///
+/// Both the plain (P3) and raw (P6) variants of the format are supported,
+/// including raw files with 16-bit samples. Color values are scaled by the
+/// image's maximum value so that they fall within `[0, 1]`.
+///
+/// # Arguments
+///
+/// * `source` - The reader to load the file from.
+///
+/// # Examples
+///
+/// ```
+/// # use raytracer::{loaders::load_ppm, Color};
+/// let ppm = "P3
+/// 2 1
+/// 255
+/// 255 0 0  0 0 255
+/// ";
+///
+/// let canvas = load_ppm(ppm.as_bytes()).unwrap();
+///
+/// assert_eq!(canvas.width(), 2);
+/// assert_eq!(canvas.height(), 1);
+/// assert_eq!(canvas.pixel_at(1, 0), Color::new(0, 0, 1));
+/// ```
+pub fn load_ppm<R: Read>(mut source: R) -> Result<Canvas, std::io::Error> {
+    let mut contents = Vec::new();
+    source.read_to_end(&mut contents)?;
+
+    let mut reader = HeaderReader {
+        contents: &contents,
+        position: 0,
+    };
+
+    let magic = reader.token()?;
+    let plain = match magic {
+        b"P3" => true,
+        b"P6" => false,
+        _ => {
+            return Err(malformed(format!(
+                "Unsupported PPM format '{}'; expected 'P3' or 'P6'.",
+                String::from_utf8_lossy(magic)
+            )))
+        }
+    };
+
+    let width = reader.number()? as usize;
+    let height = reader.number()? as usize;
+    let max_value = reader.number()?;
+    if max_value == 0 || max_value > 65535 {
+        return Err(malformed(format!(
+            "PPM maximum value must be between 1 and 65535 but found {}.",
+            max_value
+        )));
+    }
+
+    let sample_count = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(3))
+        .ok_or_else(|| malformed("PPM image dimensions are too large."))?;
+
+    let samples = if plain {
+        // Don't trust the header's dimensions for the allocation; a sample
+        // takes at least two bytes, so the file's size bounds the count.
+        let mut samples = Vec::with_capacity(sample_count.min(contents.len() / 2));
+        for _ in 0..sample_count {
+            samples.push(reader.number()?);
+        }
+
+        samples
+    } else {
+        // A single whitespace character separates the header from the raster.
+        let start = reader.position + 1;
+        let sample_size = if max_value < 256 { 1 } else { 2 };
+        let raster = sample_count
+            .checked_mul(sample_size)
+            .and_then(|length| contents.get(start..start.checked_add(length)?))
+            .ok_or_else(|| malformed("Unexpected end of file in PPM raster."))?;
+
+        raster
+            .chunks_exact(sample_size)
+            .map(|bytes| match bytes {
+                [value] => *value as u32,
+                // Multi-byte samples are stored most significant byte first.
+                [high, low] => u16::from_be_bytes([*high, *low]) as u32,
+                _ => unreachable!(),
+            })
+            .collect()
+    };
+
+    if let Some(sample) = samples.iter().find(|s| **s > max_value) {
+        return Err(malformed(format!(
+            "PPM sample {} is larger than the maximum value {}.",
+            sample, max_value
+        )));
+    }
+
+    let scale = max_value as f64;
+    let mut canvas = Canvas::new(width, height);
+    for (index, rgb) in samples.chunks_exact(3).enumerate() {
+        let color = Color::new(
+            rgb[0] as f64 / scale,
+            rgb[1] as f64 / scale,
+            rgb[2] as f64 / scale,
+        );
+
+        // Pixels are stored a row at a time, starting from the top left.
+        canvas.write_pixel(index % width, index / width, color);
+    }
+
+    Ok(canvas)
+}
+
+/// Reads the whitespace separated tokens of a PPM header, skipping comments.
+struct HeaderReader<'a> {
+    contents: &'a [u8],
+    position: usize,
+}
+
+impl<'a> HeaderReader<'a> {
+    fn token(&mut self) -> Result<&'a [u8], std::io::Error> {
+        // Skip whitespace and comments, which run until the end of the line.
+        while let Some(byte) = self.contents.get(self.position) {
+            if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else if *byte == b'#' {
+                while let Some(byte) = self.contents.get(self.position) {
+                    if *byte == b'\n' {
+                        break;
+                    }
+
+                    self.position += 1;
+                }
+            } else {
+                break;
+            }
+        }
+
+        let start = self.position;
+        while let Some(byte) = self.contents.get(self.position) {
+            if byte.is_ascii_whitespace() || *byte == b'#' {
+                break;
+            }
+
+            self.position += 1;
+        }
+
+        if start == self.position {
+            Err(malformed("Unexpected end of file in PPM data."))
+        } else {
+            Ok(&self.contents[start..self.position])
+        }
+    }
+
+    fn number(&mut self) -> Result<u32, std::io::Error> {
+        let token = self.token()?;
+
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| {
+                malformed(format!(
+                    "Expected a number but found '{}'.",
+                    String::from_utf8_lossy(token)
+                ))
+            })
+    }
+}
@@ -0,0 +1,194 @@
+use std::{convert::TryInto, io::Read};
+
+use crate::{linear::Tuple, objects::Mesh};
+
+use super::malformed;
+
+/// The size of the header at the start of a binary STL file.
+const BINARY_HEADER_LENGTH: usize = 80;
+
+/// The size of each triangle record in a binary STL file: a normal and three
+/// vertices of three 32-bit floats each, followed by a 16-bit attribute count.
+const BINARY_TRIANGLE_LENGTH: usize = 50;
+
+/// Load a mesh from an STL file.
+///
+/// Both the ASCII and binary variants of the format are supported. The
+/// normals stored in the file are ignored in favour of the normals implied by
+/// the winding of each face, since many exporters write them incorrectly.
+///
+/// # Arguments
+///
+/// * `source` - The reader to load the file from.
+///
+/// # Examples
+///
+/// ```
+/// # use raytracer::loaders::load_stl;
+/// let stl = "
+/// solid triangle
+///   facet normal 0 0 1
+///     outer loop
+///       vertex 0 0 0
+///       vertex 1 0 0
+///       vertex 0 1 0
+///     endloop
+///   endfacet
+/// endsolid triangle
+/// ";
+///
+/// let mesh = load_stl(stl.as_bytes()).unwrap();
+///
+/// assert_eq!(mesh.face_count(), 1);
+/// ```
+pub fn load_stl<R: Read>(mut source: R) -> Result<Mesh, std::io::Error> {
+    let mut contents = Vec::new();
+    source.read_to_end(&mut contents)?;
+
+    let triangles = if is_binary(&contents) {
+        parse_binary(&contents)?
+    } else if contents.trim_ascii_start().starts_with(b"solid") {
+        let text = std::str::from_utf8(&contents)
+            .map_err(|_| malformed("ASCII STL file contains invalid UTF-8."))?;
+
+        parse_ascii(text)?
+    } else {
+        return Err(malformed("File is neither an ASCII nor a binary STL file."));
+    };
+
+    // STL files don't share vertices between faces, so each triangle gets its
+    // own three vertices.
+    let faces = (0..triangles.len())
+        .map(|i| [3 * i, 3 * i + 1, 3 * i + 2])
+        .collect();
+    let vertices = triangles.into_iter().flatten().collect();
+
+    Ok(Mesh::new(vertices, faces))
+}
+
+/// Determine if the contents of a file are a binary STL file.
+///
+/// Binary files are allowed to start with "solid" just like ASCII files, so
+/// the only reliable check is whether the triangle count in the header
+/// matches the size of the file.
+fn is_binary(contents: &[u8]) -> bool {
+    if contents.len() < BINARY_HEADER_LENGTH + 4 {
+        return false;
+    }
+
+    let count = read_u32(contents, BINARY_HEADER_LENGTH) as usize;
+
+    contents.len() == BINARY_HEADER_LENGTH + 4 + count * BINARY_TRIANGLE_LENGTH
+}
+
+fn parse_binary(contents: &[u8]) -> Result<Vec<[Tuple; 3]>, std::io::Error> {
+    let count = read_u32(contents, BINARY_HEADER_LENGTH) as usize;
+    let mut triangles = Vec::with_capacity(count);
+
+    for i in 0..count {
+        let record = BINARY_HEADER_LENGTH + 4 + i * BINARY_TRIANGLE_LENGTH;
+
+        // Skip the 12 byte normal at the start of the record.
+        let vertex = |n: usize| {
+            let offset = record + 12 + n * 12;
+
+            Tuple::new_point(
+                read_f32(contents, offset),
+                read_f32(contents, offset + 4),
+                read_f32(contents, offset + 8),
+            )
+        };
+
+        triangles.push([vertex(0), vertex(1), vertex(2)]);
+    }
+
+    Ok(triangles)
+}
+
+fn parse_ascii(text: &str) -> Result<Vec<[Tuple; 3]>, std::io::Error> {
+    let mut tokens = text.split_whitespace().peekable();
+    expect(tokens.next(), "solid")?;
+
+    // The solid's name is optional and may contain spaces, so skip anything up
+    // until the first facet.
+    while let Some(token) = tokens.peek() {
+        if *token == "facet" || *token == "endsolid" {
+            break;
+        }
+
+        tokens.next();
+    }
+
+    let mut triangles = Vec::new();
+    loop {
+        match tokens.next() {
+            Some("facet") => (),
+            Some("endsolid") => break,
+            Some(token) => {
+                return Err(malformed(format!(
+                    "Expected 'facet' or 'endsolid' but found '{}'.",
+                    token
+                )))
+            }
+            None => return Err(malformed("Unexpected end of file; expected 'endsolid'.")),
+        }
+
+        expect(tokens.next(), "normal")?;
+        for _ in 0..3 {
+            parse_number(tokens.next())?;
+        }
+
+        expect(tokens.next(), "outer")?;
+        expect(tokens.next(), "loop")?;
+
+        let mut vertices = [Tuple::new_point(0, 0, 0); 3];
+        for vertex in vertices.iter_mut() {
+            expect(tokens.next(), "vertex")?;
+            *vertex = Tuple::new_point(
+                parse_number(tokens.next())?,
+                parse_number(tokens.next())?,
+                parse_number(tokens.next())?,
+            );
+        }
+
+        expect(tokens.next(), "endloop")?;
+        expect(tokens.next(), "endfacet")?;
+
+        triangles.push(vertices);
+    }
+
+    Ok(triangles)
+}
+
+fn expect(token: Option<&str>, expected: &str) -> Result<(), std::io::Error> {
+    match token {
+        Some(token) if token == expected => Ok(()),
+        Some(token) => Err(malformed(format!(
+            "Expected '{}' but found '{}'.",
+            expected, token
+        ))),
+        None => Err(malformed(format!(
+            "Unexpected end of file; expected '{}'.",
+            expected
+        ))),
+    }
+}
+
+fn parse_number(token: Option<&str>) -> Result<f64, std::io::Error> {
+    let token = token.ok_or_else(|| malformed("Unexpected end of file; expected a number."))?;
+
+    token
+        .parse()
+        .map_err(|_| malformed(format!("Expected a number but found '{}'.", token)))
+}
+
+// The binary readers are only called with offsets that have already been
+// checked against the length of the file.
+
+fn read_u32(contents: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(contents[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_f32(contents: &[u8], offset: usize) -> f32 {
+    f32::from_le_bytes(contents[offset..offset + 4].try_into().unwrap())
+}
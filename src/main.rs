@@ -5,16 +5,21 @@ use raytracer::{
     canvas::renderers::render_as_ppm,
     linear::{Matrix, Tuple},
     objects::{Plane, Shape, Sphere},
-    Color, Material, World,
+    patterns::Checker,
+    Color, Material, Preset, World, DEFAULT_LIGHT,
 };
 
 fn main() {
     let floor_material = Material::default()
-        .with_color(Color::new(1, 0.9, 0.9))
+        .with_pattern(Checker::new(
+            Color::new(1, 0.9, 0.9),
+            Color::new(0.5, 0.45, 0.45),
+        ))
         .with_specular(0.0);
 
     let floor = {
         let mut floor = Plane::default();
+        floor.set_name("floor");
         floor.set_material(floor_material);
 
         floor
@@ -22,12 +27,12 @@ fn main() {
 
     let middle = {
         let mut middle = Sphere::default();
+        middle.set_name("middle_sphere");
         middle.set_transform(Matrix::translation(-0.5, 1, 0.5));
         middle.set_material(
-            Material::default()
-                .with_color(Color::new(0.1, 1, 0.5))
-                .with_diffuse(0.7)
-                .with_specular(0.3),
+            Preset::GlossyPlastic
+                .material()
+                .with_color(Color::new(0.1, 1, 0.5)),
         );
 
         middle
@@ -35,12 +40,12 @@ fn main() {
 
     let right = {
         let mut right = Sphere::default();
+        right.set_name("right_sphere");
         right.set_transform(&Matrix::translation(1.5, 0.5, -0.5) * &Matrix::scaling(0.5, 0.5, 0.5));
         right.set_material(
-            Material::default()
-                .with_color(Color::new(0.5, 1, 0.1))
-                .with_diffuse(0.7)
-                .with_specular(0.3),
+            Preset::MattePlastic
+                .material()
+                .with_color(Color::new(0.5, 1, 0.1)),
         );
 
         right
@@ -48,23 +53,21 @@ fn main() {
 
     let left = {
         let mut left = Sphere::default();
+        left.set_name("left_sphere");
         left.set_transform(
             &Matrix::translation(-1.5, 0.33, -0.75) * &Matrix::scaling(0.33, 0.33, 0.33),
         );
-        left.set_material(
-            Material::default()
-                .with_color(Color::new(1, 0.8, 0.1))
-                .with_diffuse(0.7)
-                .with_specular(0.3),
-        );
+        left.set_material(Preset::Gold.material());
 
         left
     };
 
-    let world = World {
-        objects: vec![&floor, &middle, &left, &right],
-        ..Default::default()
-    };
+    let mut world = World::new();
+    world.add_light(&*DEFAULT_LIGHT);
+    world.add_object(&floor);
+    world.add_object(&middle);
+    world.add_object(&left);
+    world.add_object(&right);
 
     let camera = Camera::new(1000, 500, FRAC_PI_3).with_transform(view_transform(
         &Tuple::new_point(0.0, 1.5, -5.0),
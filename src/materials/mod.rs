@@ -0,0 +1,1075 @@
+mod presets;
+mod thin_film;
+
+use std::{f64::consts::PI, sync::Arc};
+
+use float_cmp::approx_eq;
+
+use crate::{
+    lights::Light,
+    linear::Tuple,
+    objects::{Shape, TangentFrame},
+    patterns::{NormalMap, Pattern},
+    Color,
+};
+
+pub use presets::{Preset, UnknownPreset};
+pub use thin_film::ThinFilm;
+
+/// The ways of computing how much light a transparent surface reflects rather
+/// than refracts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fresnel {
+    /// Schlick's approximation, which is cheap and accurate enough for most
+    /// materials.
+    Schlick,
+
+    /// The exact Fresnel equations for unpolarized light.
+    Exact,
+}
+
+/// The reflectance of dielectric surfaces like plastic or stone when viewed
+/// head-on.
+const DIELECTRIC_REFLECTANCE: f64 = 0.04;
+
+/// The smallest roughness used when shading. A perfectly smooth surface would
+/// reflect a point light as an infinitely small, infinitely bright highlight.
+const MIN_ROUGHNESS: f64 = 0.045;
+
+/// Points where an opacity mask is dimmer than this are cut out of the
+/// surface.
+const OPACITY_CUTOFF: f64 = 0.5;
+
+/// The models used to calculate how a surface reflects direct light.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShadingModel {
+    /// The classic Phong model, using the material's `ambient`, `diffuse`,
+    /// `specular`, and `shininess` values.
+    Phong,
+
+    /// An energy conserving Cook-Torrance model using a GGX microfacet
+    /// distribution, Smith masking, and Schlick's approximation of Fresnel
+    /// reflectance. The surface is described by its `metallic` and
+    /// `roughness` values, matching the metallic-roughness workflow used by
+    /// most other tools.
+    MetallicRoughness,
+
+    /// A non-photorealistic cel shading model for illustrations. Diffuse
+    /// light is quantized into flat bands, and the highlight has a hard edge
+    /// whose size is controlled by the material's `shininess`. Pair it with an
+    /// [`Outline`][crate::canvas::Outline] to draw ink lines around objects.
+    Toon {
+        /// The number of lit bands of diffuse light, not counting the
+        /// unlit side of the object.
+        bands: u32,
+    },
+}
+
+/// The models used to calculate the highlights of materials using the
+/// [`ShadingModel::Phong`] shading model.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpecularModel {
+    /// The classic Phong highlight, based on the angle between the reflected
+    /// light and the eye.
+    Phong,
+
+    /// The Blinn-Phong highlight, based on the angle between the normal and
+    /// the vector halfway between the light and the eye. Highlights stay
+    /// rounded at grazing angles, where Phong highlights are clipped. An
+    /// exponent around four times the Phong shininess gives a highlight of a
+    /// similar size.
+    BlinnPhong,
+
+    /// Ward's anisotropic model, whose highlights are stretched along the
+    /// surface's tangent or bitangent, like those on brushed metal or hair.
+    /// The material's `shininess` isn't used.
+    Ward {
+        /// The roughness along the surface's tangent. Typical values are
+        /// between 0.05 for a sharp highlight and 0.5 for a broad one.
+        roughness_x: f64,
+
+        /// The roughness along the surface's bitangent.
+        roughness_y: f64,
+    },
+}
+
+/// The models used to calculate the diffuse reflections of materials using
+/// the [`ShadingModel::Phong`] shading model.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffuseModel {
+    /// Lambertian reflection, which is equally bright from every direction.
+    Lambert,
+
+    /// The Oren-Nayar model for rough surfaces like clay, plaster, or the
+    /// moon. Rough surfaces look flatter than Lambertian ones, with less
+    /// darkening towards their edges.
+    OrenNayar {
+        /// The standard deviation of the angle of the surface's microfacets,
+        /// in radians. A roughness of 0 is the same as Lambertian reflection.
+        roughness: f64,
+    },
+}
+
+/// A material describes how a surface looks using ambient, diffuse, and
+/// specular reflections.
+#[derive(Clone, Debug)]
+pub struct Material {
+    color: Color,
+    pattern: Option<Arc<dyn Pattern>>,
+    normal_map: Option<Arc<dyn NormalMap>>,
+    opacity_mask: Option<Arc<dyn Pattern>>,
+    ambient: f64,
+    diffuse: f64,
+    specular: f64,
+    shininess: f64,
+    reflective: f64,
+    transparency: f64,
+    refractive_index: f64,
+    fresnel: Fresnel,
+    thin_film: Option<ThinFilm>,
+    absorption: Color,
+    shading_model: ShadingModel,
+    specular_model: SpecularModel,
+    diffuse_model: DiffuseModel,
+    metallic: f64,
+    roughness: f64,
+    emissive: Color,
+    shadow_catcher: bool,
+}
+
+impl Material {
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    pub fn with_color(&self, color: Color) -> Self {
+        Material {
+            color,
+            ..self.clone()
+        }
+    }
+
+    pub fn pattern(&self) -> Option<&dyn Pattern> {
+        self.pattern.as_deref()
+    }
+
+    /// Create a copy of the material that is colored using a pattern rather
+    /// than a single color.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to color the material with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{patterns::Stripe, Color, Material};
+    /// let m = Material::default()
+    ///     .with_pattern(Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0)));
+    ///
+    /// assert!(m.pattern().is_some());
+    /// ```
+    pub fn with_pattern<P: Pattern + 'static>(&self, pattern: P) -> Self {
+        Self {
+            pattern: Some(Arc::new(pattern)),
+            ..self.clone()
+        }
+    }
+
+    pub fn normal_map(&self) -> Option<&dyn NormalMap> {
+        self.normal_map.as_deref()
+    }
+
+    /// Create a copy of the material whose surface is shaded using a normal
+    /// map.
+    ///
+    /// # Arguments
+    ///
+    /// * `normal_map` - The normal map used to vary the shading normal.
+    pub fn with_normal_map<M: NormalMap + 'static>(&self, normal_map: M) -> Self {
+        Self {
+            normal_map: Some(Arc::new(normal_map)),
+            ..self.clone()
+        }
+    }
+
+    pub fn opacity_mask(&self) -> Option<&dyn Pattern> {
+        self.opacity_mask.as_deref()
+    }
+
+    /// Create a copy of the material whose surface is cut away wherever an
+    /// opacity mask is dark.
+    ///
+    /// The surface is kept where the average of the mask's red, green, and
+    /// blue components is at least 0.5, and is cut away everywhere else. Rays
+    /// pass straight through the cut away parts, as do shadows, so simple
+    /// shapes can stand in for detailed ones like leaves or fences.
+    ///
+    /// # Arguments
+    ///
+    /// * `opacity_mask` - The pattern giving the opacity of the surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, objects::Plane, patterns::Stripe, Color, Material};
+    /// let plane = Plane::default();
+    /// let m = Material::default()
+    ///     .with_opacity_mask(Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0)));
+    ///
+    /// assert!(m.is_opaque_at(&plane, &Tuple::new_point(0.5, 0.0, 0.0)));
+    /// assert!(!m.is_opaque_at(&plane, &Tuple::new_point(1.5, 0.0, 0.0)));
+    /// ```
+    pub fn with_opacity_mask<P: Pattern + 'static>(&self, opacity_mask: P) -> Self {
+        Self {
+            opacity_mask: Some(Arc::new(opacity_mask)),
+            ..self.clone()
+        }
+    }
+
+    /// Determine if the surface of an object using the material exists at a
+    /// point, or if it has been cut away by the material's opacity mask.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object the material belongs to.
+    /// * `world_point` - The point on the object's surface, in world space.
+    pub fn is_opaque_at(&self, object: &dyn Shape, world_point: &Tuple) -> bool {
+        match &self.opacity_mask {
+            Some(mask) => {
+                let c = mask.pattern_at_shape(object, world_point);
+
+                (c.red() + c.green() + c.blue()) / 3.0 >= OPACITY_CUTOFF
+            }
+            None => true,
+        }
+    }
+
+    pub fn ambient(&self) -> f64 {
+        self.ambient
+    }
+
+    pub fn with_ambient(&self, ambient: f64) -> Self {
+        Self {
+            ambient,
+            ..self.clone()
+        }
+    }
+
+    pub fn diffuse(&self) -> f64 {
+        self.diffuse
+    }
+
+    pub fn with_diffuse(&self, diffuse: f64) -> Self {
+        Self {
+            diffuse,
+            ..self.clone()
+        }
+    }
+
+    pub fn specular(&self) -> f64 {
+        self.specular
+    }
+
+    pub fn with_specular(&self, specular: f64) -> Self {
+        Self {
+            specular,
+            ..self.clone()
+        }
+    }
+
+    pub fn shininess(&self) -> f64 {
+        self.shininess
+    }
+
+    pub fn with_shininess(&self, shininess: f64) -> Self {
+        Self {
+            shininess,
+            ..self.clone()
+        }
+    }
+
+    pub fn reflective(&self) -> f64 {
+        self.reflective
+    }
+
+    /// Create a copy of the material with a different reflectivity.
+    ///
+    /// # Arguments
+    ///
+    /// * `reflective` - How much of the surrounding scene the surface
+    ///   reflects, from 0 for a matte surface to 1 for a perfect mirror.
+    pub fn with_reflective(&self, reflective: f64) -> Self {
+        Self {
+            reflective,
+            ..self.clone()
+        }
+    }
+
+    pub fn transparency(&self) -> f64 {
+        self.transparency
+    }
+
+    /// Create a copy of the material with a different transparency.
+    ///
+    /// # Arguments
+    ///
+    /// * `transparency` - How much light passes through the surface, from 0
+    ///   for an opaque surface to 1 for a perfectly clear one.
+    pub fn with_transparency(&self, transparency: f64) -> Self {
+        Self {
+            transparency,
+            ..self.clone()
+        }
+    }
+
+    pub fn refractive_index(&self) -> f64 {
+        self.refractive_index
+    }
+
+    /// Create a copy of the material with a different refractive index.
+    ///
+    /// # Arguments
+    ///
+    /// * `refractive_index` - How much light bends when entering the
+    ///   material. Common values are 1 for a vacuum, 1.333 for water, and 1.5
+    ///   for glass.
+    pub fn with_refractive_index(&self, refractive_index: f64) -> Self {
+        Self {
+            refractive_index,
+            ..self.clone()
+        }
+    }
+
+    pub fn fresnel(&self) -> Fresnel {
+        self.fresnel
+    }
+
+    /// Create a copy of the material that uses a different model for the
+    /// reflectance of transparent surfaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `fresnel` - The model to use.
+    pub fn with_fresnel(&self, fresnel: Fresnel) -> Self {
+        Self {
+            fresnel,
+            ..self.clone()
+        }
+    }
+
+    pub fn thin_film(&self) -> Option<ThinFilm> {
+        self.thin_film
+    }
+
+    /// Create a copy of the material that is coated with a thin film, giving
+    /// it an iridescent sheen like a soap bubble or an oil slick.
+    ///
+    /// The film replaces the material's [`Fresnel`] model on materials that
+    /// are both reflective and transparent. On opaque reflective materials,
+    /// it tints the reflections, like the oil on a puddle.
+    ///
+    /// # Arguments
+    ///
+    /// * `thin_film` - The film coating the material.
+    pub fn with_thin_film(&self, thin_film: ThinFilm) -> Self {
+        Self {
+            thin_film: Some(thin_film),
+            ..self.clone()
+        }
+    }
+
+    pub fn absorption(&self) -> Color {
+        self.absorption
+    }
+
+    /// Create a copy of the material that absorbs light passing through it.
+    ///
+    /// Following the Beer-Lambert law, the fraction of each color channel
+    /// that makes it through the material falls off exponentially with the
+    /// distance travelled, so thick parts of an object look darker than thin
+    /// parts. This only affects transparent materials.
+    ///
+    /// # Arguments
+    ///
+    /// * `absorption` - How much of each color channel is absorbed per unit
+    ///   of distance. Tinted glass absorbs the colors it isn't tinted with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Color, Material};
+    /// // Green glass absorbs red and blue light.
+    /// let green_glass = Material::default()
+    ///     .with_transparency(1.0)
+    ///     .with_refractive_index(1.5)
+    ///     .with_absorption(Color::new(0.8, 0.05, 0.8));
+    /// ```
+    pub fn with_absorption(&self, absorption: Color) -> Self {
+        Self {
+            absorption,
+            ..self.clone()
+        }
+    }
+
+    /// Find the fraction of light that passes through a distance of the
+    /// material without being absorbed.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance the light travels through the material.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Color, Material};
+    /// let m = Material::default().with_absorption(Color::new(0.5, 0.0, 1.0));
+    ///
+    /// assert_eq!(
+    ///     m.transmittance(2.0),
+    ///     Color::new((-1.0_f64).exp(), 1.0, (-2.0_f64).exp())
+    /// );
+    /// ```
+    pub fn transmittance(&self, distance: f64) -> Color {
+        let channel = |absorption: f64| {
+            // Avoid multiplying zero by an infinite distance.
+            if absorption == 0.0 {
+                1.0
+            } else {
+                (-absorption * distance).exp()
+            }
+        };
+
+        Color::new(
+            channel(self.absorption.red()),
+            channel(self.absorption.green()),
+            channel(self.absorption.blue()),
+        )
+    }
+
+    pub fn shading_model(&self) -> ShadingModel {
+        self.shading_model
+    }
+
+    /// Create a copy of the material that is lit using a different shading
+    /// model.
+    ///
+    /// # Arguments
+    ///
+    /// * `shading_model` - The model to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Material, ShadingModel};
+    /// let gold = Material::default()
+    ///     .with_shading_model(ShadingModel::MetallicRoughness)
+    ///     .with_metallic(1.0)
+    ///     .with_roughness(0.3);
+    ///
+    /// assert_eq!(gold.shading_model(), ShadingModel::MetallicRoughness);
+    /// ```
+    pub fn with_shading_model(&self, shading_model: ShadingModel) -> Self {
+        Self {
+            shading_model,
+            ..self.clone()
+        }
+    }
+
+    pub fn specular_model(&self) -> SpecularModel {
+        self.specular_model
+    }
+
+    /// Create a copy of the material with a different model for its
+    /// highlights.
+    ///
+    /// This is only used by the [`ShadingModel::Phong`] model.
+    ///
+    /// # Arguments
+    ///
+    /// * `specular_model` - The model to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Material, SpecularModel};
+    /// let brushed = Material::default().with_specular_model(SpecularModel::Ward {
+    ///     roughness_x: 0.05,
+    ///     roughness_y: 0.3,
+    /// });
+    /// ```
+    pub fn with_specular_model(&self, specular_model: SpecularModel) -> Self {
+        Self {
+            specular_model,
+            ..self.clone()
+        }
+    }
+
+    pub fn diffuse_model(&self) -> DiffuseModel {
+        self.diffuse_model
+    }
+
+    /// Create a copy of the material with a different model for its diffuse
+    /// reflections.
+    ///
+    /// This is only used by the [`ShadingModel::Phong`] model.
+    ///
+    /// # Arguments
+    ///
+    /// * `diffuse_model` - The model to use.
+    pub fn with_diffuse_model(&self, diffuse_model: DiffuseModel) -> Self {
+        Self {
+            diffuse_model,
+            ..self.clone()
+        }
+    }
+
+    pub fn metallic(&self) -> f64 {
+        self.metallic
+    }
+
+    /// Create a copy of the material with a different metalness.
+    ///
+    /// This is only used by the [`ShadingModel::MetallicRoughness`] model.
+    ///
+    /// # Arguments
+    ///
+    /// * `metallic` - From 0 for a dielectric surface, whose highlights are
+    ///   white, to 1 for a metal, whose highlights are tinted by its color
+    ///   and which has no diffuse reflection.
+    pub fn with_metallic(&self, metallic: f64) -> Self {
+        Self {
+            metallic,
+            ..self.clone()
+        }
+    }
+
+    pub fn roughness(&self) -> f64 {
+        self.roughness
+    }
+
+    /// Create a copy of the material with a different roughness.
+    ///
+    /// This is only used by the [`ShadingModel::MetallicRoughness`] model.
+    ///
+    /// # Arguments
+    ///
+    /// * `roughness` - From 0 for a polished surface with small, sharp
+    ///   highlights to 1 for a rough surface with broad, dim highlights.
+    pub fn with_roughness(&self, roughness: f64) -> Self {
+        Self {
+            roughness,
+            ..self.clone()
+        }
+    }
+
+    pub fn emissive(&self) -> Color {
+        self.emissive
+    }
+
+    /// Create a copy of the material that emits light.
+    ///
+    /// Emitted light is added to the surface's color no matter how it's lit,
+    /// so the surface glows even in shadow. It doesn't illuminate other
+    /// objects, so pair it with a light source to make a lamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `emissive` - The color of the light the surface emits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Color, Material};
+    /// let neon = Material::default().with_emissive(Color::new(1.0, 0.2, 0.6));
+    ///
+    /// assert!(neon.is_emissive());
+    /// assert!(!Material::default().is_emissive());
+    /// ```
+    pub fn with_emissive(&self, emissive: Color) -> Self {
+        Self {
+            emissive,
+            ..self.clone()
+        }
+    }
+
+    /// Determine if the material emits any light.
+    pub fn is_emissive(&self) -> bool {
+        self.emissive != Color::new(0, 0, 0)
+    }
+
+    pub fn is_shadow_catcher(&self) -> bool {
+        self.shadow_catcher
+    }
+
+    /// Create a copy of the material that is, or isn't, a shadow catcher.
+    ///
+    /// Shadow catchers are invisible, and don't cast shadows, but record how
+    /// much they are shadowed in the alpha channel of a render. This lets
+    /// shadows be composited over a photograph, such as a floor under an
+    /// object that is placed in a photo of a room.
+    ///
+    /// # Arguments
+    ///
+    /// * `shadow_catcher` - Whether the material is a shadow catcher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{objects::{Plane, Shape}, Material};
+    /// let mut floor = Plane::default();
+    /// floor.set_material(Material::default().with_shadow_catcher(true));
+    ///
+    /// assert!(floor.material().is_shadow_catcher());
+    /// ```
+    pub fn with_shadow_catcher(&self, shadow_catcher: bool) -> Self {
+        Self {
+            shadow_catcher,
+            ..self.clone()
+        }
+    }
+
+    /// Calculate the color of the material based on where the material is
+    /// observed from and what lighting sources are present.
+    ///
+    /// This is the sum of the material's [ambient][Self::ambient_light]
+    /// and [direct][Self::direct_light] lighting from a single light.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object the material belongs to. This is used to find
+    ///   the color of the material's pattern, if it has one.
+    /// * `light` - The light source.
+    /// * `position` - The material's position.
+    /// * `eye_v` - The vector pointing from the illuminated point towards the
+    ///   "eye" or camera.
+    /// * `normal_v` - The normal vector of the surface being lit.
+    /// * `in_shadow` - A boolean indicating if the point does not have direct
+    ///   line of sight to the light source.
+    pub fn light(
+        &self,
+        object: &dyn Shape,
+        light: &dyn Light,
+        position: &Tuple,
+        eye_v: &Tuple,
+        normal_v: &Tuple,
+        in_shadow: bool,
+    ) -> Color {
+        let ambient = self.ambient_light(object, position, light.intensity());
+
+        // If the point is shadowed, the ambient reflection is the only part
+        // that matters since the diffuse and specular reflections depend on a
+        // direct light source.
+        if in_shadow {
+            return ambient;
+        }
+
+        ambient + self.direct_light(object, light, position, eye_v, normal_v)
+    }
+
+    /// Calculate the color of the material lit only by ambient light.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object the material belongs to.
+    /// * `position` - The material's position.
+    /// * `intensity` - The intensity of the ambient light.
+    pub fn ambient_light(&self, object: &dyn Shape, position: &Tuple, intensity: Color) -> Color {
+        self.color_at(object, position) * intensity * self.ambient
+    }
+
+    /// Calculate the diffuse and specular reflections of a single light
+    /// source, ignoring ambient light and shadows.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object the material belongs to.
+    /// * `light` - The light source.
+    /// * `position` - The material's position.
+    /// * `eye_v` - The vector pointing from the illuminated point towards the
+    ///   "eye" or camera.
+    /// * `normal_v` - The normal vector of the surface being lit.
+    pub fn direct_light(
+        &self,
+        object: &dyn Shape,
+        light: &dyn Light,
+        position: &Tuple,
+        eye_v: &Tuple,
+        normal_v: &Tuple,
+    ) -> Color {
+        let color = self.color_at(object, position);
+
+        // Combine surface color with the color of the light reaching it.
+        let intensity = light.intensity_at(position);
+        let effective_color = color * intensity;
+
+        // Get a vector from the point being lit towards the light source.
+        let light_v = light.direction_from(position);
+
+        match self.shading_model {
+            ShadingModel::Phong => self.phong(
+                object,
+                intensity,
+                effective_color,
+                position,
+                &light_v,
+                eye_v,
+                normal_v,
+            ),
+            ShadingModel::MetallicRoughness => {
+                self.metallic_roughness(intensity, color, &light_v, eye_v, normal_v)
+            }
+            ShadingModel::Toon { bands } => {
+                self.toon(intensity, effective_color, bands, &light_v, eye_v, normal_v)
+            }
+        }
+    }
+
+    /// Find the color of the material's surface at a point, before it's lit.
+    fn color_at(&self, object: &dyn Shape, position: &Tuple) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.pattern_at_shape(object, position),
+            None => self.color,
+        }
+    }
+
+    /// Calculate the diffuse and specular reflections of a light using the
+    /// Phong model, or whichever diffuse and specular models the material
+    /// uses in its place.
+    #[allow(clippy::too_many_arguments)]
+    fn phong(
+        &self,
+        object: &dyn Shape,
+        intensity: Color,
+        effective_color: Color,
+        position: &Tuple,
+        light_v: &Tuple,
+        eye_v: &Tuple,
+        normal_v: &Tuple,
+    ) -> Color {
+        let diffuse: Color;
+        let specular: Color;
+
+        // A negative value for the dot product indicates the light is on the
+        // other side of the surface, so we would get no diffuse or specular
+        // reflections.
+        let light_dot_normal = light_v.dot(*normal_v);
+        if light_dot_normal < 0.0 {
+            diffuse = Color::new(0, 0, 0);
+            specular = Color::new(0, 0, 0);
+        } else {
+            // If the light is on the same side of the surface as the observer,
+            // we always get a diffuse reflection. For Lambertian surfaces it
+            // only depends on the light position and normal vector.
+            let roughness_factor = match self.diffuse_model {
+                DiffuseModel::Lambert => 1.0,
+                DiffuseModel::OrenNayar { roughness } => {
+                    oren_nayar(roughness, light_v, eye_v, normal_v)
+                }
+            };
+            diffuse = effective_color * self.diffuse * light_dot_normal * roughness_factor;
+
+            let factor = match self.specular_model {
+                SpecularModel::Phong => {
+                    // Compute the dot product of the vector that the reflected
+                    // light takes and the vector towards the observer. If the
+                    // value is positive, it indicates that the observer will
+                    // see some specular reflection.
+                    let reflect_v = (-*light_v).reflected_over(normal_v);
+                    let reflect_dot_eye = reflect_v.dot(*eye_v);
+                    if reflect_dot_eye <= 0.0 {
+                        0.0
+                    } else {
+                        reflect_dot_eye.powf(self.shininess)
+                    }
+                }
+                SpecularModel::BlinnPhong => {
+                    let half_v = (*light_v + *eye_v).normalized();
+                    let normal_dot_half = normal_v.dot(half_v);
+                    if normal_dot_half <= 0.0 || normal_v.dot(*eye_v) <= 0.0 {
+                        0.0
+                    } else {
+                        normal_dot_half.powf(self.shininess)
+                    }
+                }
+                SpecularModel::Ward {
+                    roughness_x,
+                    roughness_y,
+                } => {
+                    // Align the frame with the normal being shaded, which may
+                    // have been tilted by a normal map.
+                    let frame =
+                        TangentFrame::new(object.tangent_frame_at(position).tangent(), *normal_v);
+
+                    ward(roughness_x, roughness_y, light_v, eye_v, &frame)
+                }
+            };
+            specular = intensity * self.specular * factor;
+        }
+
+        diffuse + specular
+    }
+
+    /// Calculate the diffuse and specular reflections of a light using cel
+    /// shading.
+    fn toon(
+        &self,
+        intensity: Color,
+        effective_color: Color,
+        bands: u32,
+        light_v: &Tuple,
+        eye_v: &Tuple,
+        normal_v: &Tuple,
+    ) -> Color {
+        let light_dot_normal = light_v.dot(*normal_v);
+        if light_dot_normal < 0.0 || bands == 0 {
+            return Color::new(0, 0, 0);
+        }
+
+        // Round the diffuse term up to the nearest band so that every lit
+        // part of the surface is visibly brighter than the unlit side.
+        let bands = bands as f64;
+        let band = ((light_dot_normal * bands).floor() + 1.0).min(bands) / bands;
+        let diffuse = effective_color * self.diffuse * band;
+
+        // The highlight is either fully on or fully off, with the edge where
+        // the Phong highlight would fall to half of its peak.
+        let reflect_v = (-*light_v).reflected_over(normal_v);
+        let reflect_dot_eye = reflect_v.dot(*eye_v);
+        let specular = if reflect_dot_eye > 0.0 && reflect_dot_eye.powf(self.shininess) >= 0.5 {
+            intensity * self.specular
+        } else {
+            Color::new(0, 0, 0)
+        };
+
+        diffuse + specular
+    }
+
+    /// Calculate the diffuse and specular reflections of a light using the
+    /// Cook-Torrance model.
+    ///
+    /// The light's intensity is treated as the light arriving at a surface
+    /// facing it, so a white, rough dielectric lit head-on reflects roughly
+    /// the light's intensity like it would using the Phong model.
+    fn metallic_roughness(
+        &self,
+        intensity: Color,
+        base_color: Color,
+        light_v: &Tuple,
+        eye_v: &Tuple,
+        normal_v: &Tuple,
+    ) -> Color {
+        let n_dot_l = normal_v.dot(*light_v);
+        let n_dot_v = normal_v.dot(*eye_v);
+        if n_dot_l <= 0.0 || n_dot_v <= 0.0 {
+            return Color::new(0, 0, 0);
+        }
+
+        let metallic = self.metallic.clamp(0.0, 1.0);
+        let roughness = self.roughness.clamp(MIN_ROUGHNESS, 1.0);
+        let half_v = (*light_v + *eye_v).normalized();
+        let n_dot_h = normal_v.dot(half_v).max(0.0);
+        let v_dot_h = eye_v.dot(half_v).max(0.0);
+
+        // Metals reflect light tinted by their color, while everything else
+        // reflects a small amount of untinted light.
+        let dielectric = Color::new(
+            DIELECTRIC_REFLECTANCE,
+            DIELECTRIC_REFLECTANCE,
+            DIELECTRIC_REFLECTANCE,
+        );
+        let f0 = dielectric * (1.0 - metallic) + base_color * metallic;
+        let fresnel = f0 + (Color::new(1, 1, 1) - f0) * (1.0 - v_dot_h).powi(5);
+
+        let alpha = roughness * roughness;
+        let distribution = ggx_distribution(n_dot_h, alpha);
+        let geometry = smith_masking(n_dot_l, roughness) * smith_masking(n_dot_v, roughness);
+        let specular = fresnel * (distribution * geometry / (4.0 * n_dot_l * n_dot_v));
+
+        // Light that is reflected at the surface can't also be diffused, and
+        // metals absorb any light that enters them.
+        let diffuse_weight = (Color::new(1, 1, 1) - fresnel) * (1.0 - metallic);
+        let diffuse = diffuse_weight * base_color * (1.0 / PI);
+
+        // The Lambertian term is normalized by π, so the light is scaled back
+        // up to keep its intensity comparable to the Phong model.
+        (diffuse + specular) * intensity * (PI * n_dot_l)
+    }
+}
+
+/// Find how much brighter or darker a rough surface's diffuse reflection is
+/// compared to a Lambertian surface, using the qualitative Oren-Nayar model.
+fn oren_nayar(roughness: f64, light_v: &Tuple, eye_v: &Tuple, normal_v: &Tuple) -> f64 {
+    let sigma_2 = roughness * roughness;
+    let a = 1.0 - 0.5 * sigma_2 / (sigma_2 + 0.33);
+    let b = 0.45 * sigma_2 / (sigma_2 + 0.09);
+
+    let cos_i = light_v.dot(*normal_v).clamp(0.0, 1.0);
+    let cos_r = eye_v.dot(*normal_v).clamp(0.0, 1.0);
+    let (theta_i, theta_r) = (cos_i.acos(), cos_r.acos());
+    let alpha = theta_i.max(theta_r);
+    let beta = theta_i.min(theta_r);
+
+    // The cosine of the azimuthal angle between the light and eye, found by
+    // projecting both onto the surface.
+    let light_along_surface = *light_v - *normal_v * cos_i;
+    let eye_along_surface = *eye_v - *normal_v * cos_r;
+    let lengths = light_along_surface.magnitude() * eye_along_surface.magnitude();
+    let cos_phi = if lengths < 1e-9 {
+        0.0
+    } else {
+        (light_along_surface.dot(eye_along_surface) / lengths).max(0.0)
+    };
+
+    a + b * cos_phi * alpha.sin() * beta.tan()
+}
+
+/// Calculate the intensity of a highlight using Ward's anisotropic model.
+///
+/// Like the metallic-roughness model, the result is scaled by π so that the
+/// light's intensity is treated as the light arriving at the surface.
+fn ward(
+    roughness_x: f64,
+    roughness_y: f64,
+    light_v: &Tuple,
+    eye_v: &Tuple,
+    frame: &TangentFrame,
+) -> f64 {
+    let n_dot_l = frame.normal().dot(*light_v);
+    let n_dot_v = frame.normal().dot(*eye_v);
+    if n_dot_l <= 0.0 || n_dot_v <= 0.0 {
+        return 0.0;
+    }
+
+    let alpha_x = roughness_x.max(MIN_ROUGHNESS);
+    let alpha_y = roughness_y.max(MIN_ROUGHNESS);
+    let half_v = (*light_v + *eye_v).normalized();
+    let h_dot_n = frame.normal().dot(half_v);
+    let h_dot_x = frame.tangent().dot(half_v) / alpha_x;
+    let h_dot_y = frame.bitangent().dot(half_v) / alpha_y;
+
+    let exponent = -(h_dot_x * h_dot_x + h_dot_y * h_dot_y) / (h_dot_n * h_dot_n);
+    let brdf = exponent.exp() / (4.0 * PI * alpha_x * alpha_y * (n_dot_l * n_dot_v).sqrt());
+
+    brdf * n_dot_l * PI
+}
+
+/// The GGX (Trowbridge-Reitz) normal distribution function, giving the
+/// density of microfacets oriented along the half vector.
+fn ggx_distribution(n_dot_h: f64, alpha: f64) -> f64 {
+    let alpha_2 = alpha * alpha;
+    let denominator = n_dot_h * n_dot_h * (alpha_2 - 1.0) + 1.0;
+
+    alpha_2 / (PI * denominator * denominator)
+}
+
+/// Schlick's approximation of the Smith masking function for a single
+/// direction, giving the fraction of microfacets that aren't hidden by other
+/// microfacets.
+fn smith_masking(n_dot_x: f64, roughness: f64) -> f64 {
+    let k = (roughness + 1.0).powi(2) / 8.0;
+
+    n_dot_x / (n_dot_x * (1.0 - k) + k)
+}
+
+impl Default for Material {
+    /// Construct the default material.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Color, DiffuseModel, Fresnel, Material, ShadingModel, SpecularModel};
+    /// let m = Material::default();
+    ///
+    /// assert_eq!(m.color(), Color::new(1, 1, 1));
+    /// assert_eq!(m.ambient(), 0.1);
+    /// assert_eq!(m.diffuse(), 0.9);
+    /// assert_eq!(m.specular(), 0.9);
+    /// assert_eq!(m.shininess(), 200.0);
+    /// assert_eq!(m.reflective(), 0.0);
+    /// assert_eq!(m.transparency(), 0.0);
+    /// assert_eq!(m.refractive_index(), 1.0);
+    /// assert_eq!(m.fresnel(), Fresnel::Schlick);
+    /// assert!(m.thin_film().is_none());
+    /// assert_eq!(m.absorption(), Color::new(0, 0, 0));
+    /// assert_eq!(m.shading_model(), ShadingModel::Phong);
+    /// assert_eq!(m.specular_model(), SpecularModel::Phong);
+    /// assert_eq!(m.diffuse_model(), DiffuseModel::Lambert);
+    /// assert_eq!(m.metallic(), 0.0);
+    /// assert_eq!(m.roughness(), 0.5);
+    /// assert_eq!(m.emissive(), Color::new(0, 0, 0));
+    /// assert!(!m.is_shadow_catcher());
+    /// assert!(m.normal_map().is_none());
+    /// assert!(m.opacity_mask().is_none());
+    /// ```
+    fn default() -> Self {
+        Self {
+            color: Color::new(1, 1, 1),
+            pattern: None,
+            normal_map: None,
+            opacity_mask: None,
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.9,
+            shininess: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            fresnel: Fresnel::Schlick,
+            thin_film: None,
+            absorption: Color::new(0, 0, 0),
+            shading_model: ShadingModel::Phong,
+            specular_model: SpecularModel::Phong,
+            diffuse_model: DiffuseModel::Lambert,
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive: Color::new(0, 0, 0),
+            shadow_catcher: false,
+        }
+    }
+}
+
+impl PartialEq for Material {
+    fn eq(&self, other: &Self) -> bool {
+        // Patterns can't be compared, so materials only share a pattern if
+        // they were copied from the same material. The same goes for normal
+        // maps and opacity masks.
+        let same_pattern = match (&self.pattern, &other.pattern) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        let same_normal_map = match (&self.normal_map, &other.normal_map) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        let same_opacity_mask = match (&self.opacity_mask, &other.opacity_mask) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+
+        self.color == other.color
+            && same_pattern
+            && same_normal_map
+            && same_opacity_mask
+            && approx_eq!(f64, self.ambient, other.ambient)
+            && approx_eq!(f64, self.diffuse, other.diffuse)
+            && approx_eq!(f64, self.specular, other.specular)
+            && approx_eq!(f64, self.shininess, other.shininess)
+            && approx_eq!(f64, self.reflective, other.reflective)
+            && approx_eq!(f64, self.transparency, other.transparency)
+            && approx_eq!(f64, self.refractive_index, other.refractive_index)
+            && self.fresnel == other.fresnel
+            && self.thin_film == other.thin_film
+            && self.absorption == other.absorption
+            && self.shading_model == other.shading_model
+            && self.specular_model == other.specular_model
+            && self.diffuse_model == other.diffuse_model
+            && approx_eq!(f64, self.metallic, other.metallic)
+            && approx_eq!(f64, self.roughness, other.roughness)
+            && self.emissive == other.emissive
+            && self.shadow_catcher == other.shadow_catcher
+    }
+}
@@ -0,0 +1,195 @@
+use std::{fmt, str::FromStr};
+
+use crate::Color;
+
+use super::{Fresnel, Material, ShadingModel};
+
+/// Commonly used materials with physically plausible parameters.
+///
+/// Opaque presets use the [`ShadingModel::MetallicRoughness`] model, while
+/// the transparent ones use the refractive index of the real material. Any
+/// preset can be adjusted further using the material's builder methods, such
+/// as giving a plastic a different color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// A rough, white plastic with a faint, broad highlight.
+    MattePlastic,
+
+    /// A smooth, white plastic with a sharp highlight and faint reflections.
+    GlossyPlastic,
+
+    /// Aluminium with a rough finish, giving broad, tinted highlights and
+    /// blurry-looking reflections.
+    BrushedMetal,
+
+    /// Polished gold.
+    Gold,
+
+    /// Clear window glass.
+    Glass,
+
+    /// Clear water.
+    Water,
+
+    /// A diamond, which bends light far more than glass.
+    Diamond,
+
+    /// Dark, rough rubber with almost no highlight.
+    Rubber,
+}
+
+impl Preset {
+    /// Every available preset.
+    pub const ALL: [Preset; 8] = [
+        Preset::MattePlastic,
+        Preset::GlossyPlastic,
+        Preset::BrushedMetal,
+        Preset::Gold,
+        Preset::Glass,
+        Preset::Water,
+        Preset::Diamond,
+        Preset::Rubber,
+    ];
+
+    /// Get the name the preset is referred to by.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::Preset;
+    /// assert_eq!(Preset::GlossyPlastic.name(), "glossy_plastic");
+    /// assert_eq!("glossy_plastic".parse(), Ok(Preset::GlossyPlastic));
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MattePlastic => "matte_plastic",
+            Self::GlossyPlastic => "glossy_plastic",
+            Self::BrushedMetal => "brushed_metal",
+            Self::Gold => "gold",
+            Self::Glass => "glass",
+            Self::Water => "water",
+            Self::Diamond => "diamond",
+            Self::Rubber => "rubber",
+        }
+    }
+
+    /// Build the preset's material.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Color, Preset};
+    /// let red_plastic = Preset::GlossyPlastic.material().with_color(Color::new(0.8, 0.1, 0.1));
+    ///
+    /// assert_eq!(Preset::Glass.material().refractive_index(), 1.5);
+    /// ```
+    pub fn material(&self) -> Material {
+        let physically_based =
+            Material::default().with_shading_model(ShadingModel::MetallicRoughness);
+
+        // Transparent materials get their color from what is behind them, so
+        // only their highlights and reflections are shaded directly.
+        let clear = Material::default()
+            .with_color(Color::new(0, 0, 0))
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(1.0)
+            .with_shininess(300.0)
+            .with_reflective(1.0)
+            .with_transparency(1.0);
+
+        match self {
+            Self::MattePlastic => physically_based
+                .with_color(Color::new(0.8, 0.8, 0.8))
+                .with_roughness(0.8),
+            Self::GlossyPlastic => physically_based
+                .with_color(Color::new(0.8, 0.8, 0.8))
+                .with_roughness(0.2)
+                .with_reflective(0.05),
+            Self::BrushedMetal => physically_based
+                .with_color(Color::new(0.91, 0.92, 0.92))
+                .with_metallic(1.0)
+                .with_roughness(0.5)
+                .with_reflective(0.2),
+            Self::Gold => physically_based
+                .with_color(Color::new(1.0, 0.78, 0.34))
+                .with_metallic(1.0)
+                .with_roughness(0.2)
+                .with_reflective(0.6),
+            Self::Glass => clear.with_refractive_index(1.5),
+            Self::Water => clear.with_refractive_index(1.333),
+            // The Fresnel equations differ noticeably from Schlick's
+            // approximation for materials as dense as diamond.
+            Self::Diamond => clear
+                .with_refractive_index(2.417)
+                .with_fresnel(Fresnel::Exact),
+            Self::Rubber => physically_based
+                .with_color(Color::new(0.05, 0.05, 0.05))
+                .with_roughness(0.9),
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The error returned when parsing the name of a preset that doesn't exist.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownPreset(String);
+
+impl fmt::Display for UnknownPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown material preset: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownPreset {}
+
+impl FromStr for Preset {
+    type Err = UnknownPreset;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|preset| preset.name() == s)
+            .copied()
+            .ok_or_else(|| UnknownPreset(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for preset in Preset::ALL.iter() {
+            assert_eq!(preset.name().parse(), Ok(*preset));
+        }
+    }
+
+    #[test]
+    fn from_str_unknown() {
+        assert_eq!(
+            "unobtainium".parse::<Preset>(),
+            Err(UnknownPreset("unobtainium".to_owned()))
+        );
+    }
+
+    #[test]
+    fn transparent_presets_have_real_refractive_indices() {
+        assert_eq!(Preset::Water.material().refractive_index(), 1.333);
+        assert_eq!(Preset::Diamond.material().refractive_index(), 2.417);
+        assert_eq!(Preset::Diamond.material().transparency(), 1.0);
+    }
+
+    #[test]
+    fn metals_are_metallic() {
+        assert_eq!(Preset::Gold.material().metallic(), 1.0);
+        assert_eq!(Preset::BrushedMetal.material().metallic(), 1.0);
+        assert_eq!(Preset::GlossyPlastic.material().metallic(), 0.0);
+    }
+}
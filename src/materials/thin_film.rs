@@ -0,0 +1,184 @@
+use std::f64::consts::PI;
+
+use crate::Color;
+
+/// The wavelengths, in nanometres, used for the red, green, and blue
+/// components of light.
+const WAVELENGTHS: [f64; 3] = [650.0, 510.0, 475.0];
+
+/// A thin, transparent coating on a surface, like the wall of a soap bubble or
+/// the anti-reflective coating on a lens.
+///
+/// Light reflected off the top of the film interferes with light reflected off
+/// the bottom of it. Depending on the wavelength of the light and the angle it
+/// is viewed from, the reflections either reinforce or cancel each other, so
+/// the reflected color shifts as the surface is viewed from different
+/// directions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThinFilm {
+    thickness: f64,
+    refractive_index: f64,
+}
+
+impl ThinFilm {
+    /// Create a thin film.
+    ///
+    /// # Arguments
+    ///
+    /// * `thickness` - The thickness of the film, in nanometres. Films
+    ///   between 100 and 1000 nanometres thick give the most vivid colors.
+    /// * `refractive_index` - The refractive index of the film, such as 1.33
+    ///   for soapy water.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Material, ThinFilm};
+    /// let bubble = Material::default()
+    ///     .with_reflective(1.0)
+    ///     .with_transparency(1.0)
+    ///     .with_thin_film(ThinFilm::new(400.0, 1.33));
+    /// ```
+    pub fn new(thickness: f64, refractive_index: f64) -> Self {
+        Self {
+            thickness,
+            refractive_index,
+        }
+    }
+
+    pub fn thickness(&self) -> f64 {
+        self.thickness
+    }
+
+    pub fn refractive_index(&self) -> f64 {
+        self.refractive_index
+    }
+
+    /// Compute the fraction of the red, green, and blue components of light
+    /// that are reflected by a surface coated with the film.
+    ///
+    /// # Arguments
+    ///
+    /// * `n1` - The refractive index of the material light arrives from.
+    /// * `n2` - The refractive index of the coated material.
+    /// * `cos_i` - The cosine of the angle between the incoming light and the
+    ///   surface's normal.
+    pub fn reflectance(&self, n1: f64, n2: f64, cos_i: f64) -> Color {
+        let nf = self.refractive_index;
+        let sin2_i = 1.0 - cos_i * cos_i;
+
+        // Snell's law gives the angle of the light in the film and in the
+        // coated material. Past the critical angle of either boundary, all of
+        // the light is reflected.
+        let sin2_f = (n1 / nf).powi(2) * sin2_i;
+        let sin2_t = (n1 / n2).powi(2) * sin2_i;
+        if sin2_f > 1.0 || sin2_t > 1.0 {
+            return Color::new(1, 1, 1);
+        }
+
+        let cos_f = (1.0 - sin2_f).sqrt();
+        let cos_t = (1.0 - sin2_t).sqrt();
+
+        // Light polarized perpendicular and parallel to the plane of
+        // incidence is reflected differently, so find the reflectance of each
+        // and average them.
+        let perpendicular = |wavelength| {
+            airy(
+                perpendicular_amplitude(n1, cos_i, nf, cos_f),
+                perpendicular_amplitude(nf, cos_f, n2, cos_t),
+                self.phase(cos_f, wavelength),
+            )
+        };
+        let parallel = |wavelength| {
+            airy(
+                parallel_amplitude(n1, cos_i, nf, cos_f),
+                parallel_amplitude(nf, cos_f, n2, cos_t),
+                self.phase(cos_f, wavelength),
+            )
+        };
+        let [red, green, blue] = WAVELENGTHS.map(|w| (perpendicular(w) + parallel(w)) / 2.0);
+
+        Color::new(red, green, blue)
+    }
+
+    /// The phase difference between light reflected off the top and the bottom
+    /// of the film, caused by the extra distance travelled through it.
+    fn phase(&self, cos_f: f64, wavelength: f64) -> f64 {
+        4.0 * PI * self.refractive_index * self.thickness * cos_f / wavelength
+    }
+}
+
+/// The fraction of the amplitude of light polarized perpendicular to the plane
+/// of incidence that is reflected at a boundary.
+fn perpendicular_amplitude(n1: f64, cos_1: f64, n2: f64, cos_2: f64) -> f64 {
+    (n1 * cos_1 - n2 * cos_2) / (n1 * cos_1 + n2 * cos_2)
+}
+
+/// The fraction of the amplitude of light polarized parallel to the plane of
+/// incidence that is reflected at a boundary.
+fn parallel_amplitude(n1: f64, cos_1: f64, n2: f64, cos_2: f64) -> f64 {
+    (n1 * cos_2 - n2 * cos_1) / (n1 * cos_2 + n2 * cos_1)
+}
+
+/// The Airy formula for the fraction of light reflected by a film, summing
+/// every reflection bouncing back and forth inside it.
+///
+/// # Arguments
+///
+/// * `top` - The amplitude reflected at the top of the film.
+/// * `bottom` - The amplitude reflected at the bottom of the film.
+/// * `phase` - The phase difference between the two reflections.
+fn airy(top: f64, bottom: f64, phase: f64) -> f64 {
+    let interference = 2.0 * top * bottom * phase.cos();
+
+    (top * top + bottom * bottom + interference)
+        / (1.0 + top * top * bottom * bottom + interference)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reflectance_no_thickness_matches_uncoated_surface() {
+        let film = ThinFilm::new(0.0, 1.33);
+
+        // Uncoated glass reflects 4% of light head-on.
+        assert_eq!(
+            film.reflectance(1.0, 1.5, 1.0),
+            Color::new(0.04, 0.04, 0.04)
+        );
+    }
+
+    #[test]
+    fn reflectance_quarter_wave_coating_cancels_reflection() {
+        // A film a quarter of a wavelength thick, with an index between the
+        // air and the glass, is an anti-reflective coating.
+        let index = 1.5_f64.sqrt();
+        let film = ThinFilm::new(WAVELENGTHS[1] / (4.0 * index), index);
+
+        let r = film.reflectance(1.0, 1.5, 1.0);
+
+        // Other wavelengths are only partly cancelled.
+        assert!(r.green().abs() < 1e-9);
+        assert!(r.red() > r.green() && r.red() < 0.04);
+        assert!(r.blue() > r.green() && r.blue() < 0.04);
+    }
+
+    #[test]
+    fn reflectance_shifts_with_view_angle() {
+        let film = ThinFilm::new(400.0, 1.33);
+
+        let head_on = film.reflectance(1.0, 1.0, 1.0);
+        let oblique = film.reflectance(1.0, 1.0, 0.5);
+
+        assert_ne!(head_on, oblique);
+    }
+
+    #[test]
+    fn reflectance_total_internal_reflection() {
+        let film = ThinFilm::new(400.0, 1.33);
+
+        assert_eq!(film.reflectance(1.5, 1.0, 0.1), Color::new(1, 1, 1));
+    }
+}
@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use crate::linear::Tuple;
+
+use super::{scale_point, Noise};
+
+/// The ways that octaves of noise can be combined.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FractalKind {
+    /// Fractional Brownian motion: a plain sum of the octaves. The result has
+    /// the same range as the underlying noise.
+    Fbm,
+
+    /// A sum of the absolute value of each octave, giving billowy, cloud-like
+    /// shapes. The result is within `[0, 1]`.
+    Turbulence,
+
+    /// A ridged multifractal, where sharp ridges form along the zero crossings
+    /// of the noise and detail accumulates along them. The result is within
+    /// `[0, 1]`.
+    Ridged,
+}
+
+/// Noise built by summing several octaves of another noise source at
+/// increasing frequencies and decreasing amplitudes.
+#[derive(Clone, Debug)]
+pub struct Fractal {
+    noise: Arc<dyn Noise>,
+    kind: FractalKind,
+    octaves: u32,
+    lacunarity: f64,
+    gain: f64,
+}
+
+impl Fractal {
+    /// Create a fractal noise source.
+    ///
+    /// The fractal defaults to 5 octaves, with each octave having twice the
+    /// frequency and half the amplitude of the previous one.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise to sample for each octave. Gradient noise, such
+    ///   as [`Perlin`][super::Perlin] or [`Simplex`][super::Simplex], works
+    ///   best.
+    /// * `kind` - How the octaves are combined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, noise::{Fractal, FractalKind, Noise, Perlin}};
+    /// let turbulence = Fractal::new(Perlin::new(1), FractalKind::Turbulence).with_octaves(3);
+    ///
+    /// let n = turbulence.noise_at(&Tuple::new_point(0.5, 0.25, 0.75));
+    ///
+    /// assert!(n >= 0.0 && n <= 1.0);
+    /// ```
+    pub fn new<N: Noise + 'static>(noise: N, kind: FractalKind) -> Self {
+        Self {
+            noise: Arc::new(noise),
+            kind,
+            octaves: 5,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    /// Create a fractional Brownian motion noise source.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise to sample for each octave.
+    pub fn fbm<N: Noise + 'static>(noise: N) -> Self {
+        Self::new(noise, FractalKind::Fbm)
+    }
+
+    /// Create a turbulence noise source.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise to sample for each octave.
+    pub fn turbulence<N: Noise + 'static>(noise: N) -> Self {
+        Self::new(noise, FractalKind::Turbulence)
+    }
+
+    /// Create a ridged multifractal noise source.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise to sample for each octave.
+    pub fn ridged<N: Noise + 'static>(noise: N) -> Self {
+        Self::new(noise, FractalKind::Ridged)
+    }
+
+    pub fn kind(&self) -> FractalKind {
+        self.kind
+    }
+
+    pub fn octaves(&self) -> u32 {
+        self.octaves
+    }
+
+    /// Create a copy of the fractal with a different number of octaves.
+    ///
+    /// # Arguments
+    ///
+    /// * `octaves` - The number of layers of noise to sum. More octaves add
+    ///   finer detail at the cost of more samples.
+    pub fn with_octaves(&self, octaves: u32) -> Self {
+        Self {
+            octaves,
+            ..self.clone()
+        }
+    }
+
+    pub fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    /// Create a copy of the fractal with a different lacunarity.
+    ///
+    /// # Arguments
+    ///
+    /// * `lacunarity` - The factor the frequency is multiplied by for each
+    ///   octave.
+    pub fn with_lacunarity(&self, lacunarity: f64) -> Self {
+        Self {
+            lacunarity,
+            ..self.clone()
+        }
+    }
+
+    pub fn gain(&self) -> f64 {
+        self.gain
+    }
+
+    /// Create a copy of the fractal with a different gain.
+    ///
+    /// # Arguments
+    ///
+    /// * `gain` - The factor the amplitude is multiplied by for each octave.
+    pub fn with_gain(&self, gain: f64) -> Self {
+        Self {
+            gain,
+            ..self.clone()
+        }
+    }
+}
+
+impl Noise for Fractal {
+    fn noise_at(&self, point: &Tuple) -> f64 {
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+        let mut total_amplitude = 0.0;
+        let mut sum = 0.0;
+
+        // Ridged noise feeds each octave back into the next, so that detail
+        // only builds up along the ridges.
+        let mut weight = 1.0;
+
+        for _ in 0..self.octaves {
+            let sample = self.noise.noise_at(&scale_point(point, frequency));
+
+            let value = match self.kind {
+                FractalKind::Fbm => sample,
+                FractalKind::Turbulence => sample.abs(),
+                FractalKind::Ridged => {
+                    let ridge = (1.0 - sample.abs()).powi(2) * weight;
+                    weight = (ridge * 2.0).clamp(0.0, 1.0);
+
+                    ridge
+                }
+            };
+
+            sum += value * amplitude;
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        if total_amplitude == 0.0 {
+            0.0
+        } else {
+            // Normalize so the result stays in the same range no matter how
+            // many octaves there are.
+            sum / total_amplitude
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::noise::{Perlin, Simplex};
+
+    fn sample_points() -> impl Iterator<Item = Tuple> {
+        (0..500).map(|i| {
+            let t = i as f64 * 0.137;
+
+            Tuple::new_point(t, t * 0.3 + 0.2, -t * 0.6 + 0.4)
+        })
+    }
+
+    #[test]
+    fn noise_at_single_octave_matches_source() {
+        let fbm = Fractal::fbm(Perlin::new(2)).with_octaves(1);
+        let perlin = Perlin::new(2);
+
+        for point in sample_points() {
+            assert_eq!(fbm.noise_at(&point), perlin.noise_at(&point));
+        }
+    }
+
+    #[test]
+    fn noise_at_fbm_bounded() {
+        let fbm = Fractal::fbm(Simplex::new(2));
+
+        assert!(sample_points().all(|p| fbm.noise_at(&p).abs() <= 1.0));
+    }
+
+    #[test]
+    fn noise_at_turbulence_bounded() {
+        let turbulence = Fractal::turbulence(Perlin::new(2));
+
+        assert!(sample_points().all(|p| {
+            let n = turbulence.noise_at(&p);
+
+            (0.0..=1.0).contains(&n)
+        }));
+    }
+
+    #[test]
+    fn noise_at_ridged_bounded() {
+        let ridged = Fractal::ridged(Perlin::new(2));
+
+        assert!(sample_points().all(|p| {
+            let n = ridged.noise_at(&p);
+
+            (0.0..=1.0).contains(&n)
+        }));
+    }
+
+    #[test]
+    fn noise_at_no_octaves() {
+        let fbm = Fractal::fbm(Perlin::default()).with_octaves(0);
+
+        assert_eq!(fbm.noise_at(&Tuple::new_point(0.5, 0.5, 0.5)), 0.0);
+    }
+
+    #[test]
+    fn noise_at_is_deterministic() {
+        let a = Fractal::ridged(Simplex::new(6)).with_gain(0.6);
+        let b = Fractal::ridged(Simplex::new(6)).with_gain(0.6);
+
+        for point in sample_points() {
+            assert_eq!(a.noise_at(&point), b.noise_at(&point));
+        }
+    }
+}
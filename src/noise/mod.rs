@@ -0,0 +1,94 @@
+//! Coherent noise used to add natural looking variation to materials and
+//! surfaces.
+//!
+//! Every noise source is seeded, and the same seed always produces the same
+//! values, so renders are reproducible across runs.
+//!
+//! Noise is used to color materials through a
+//! [`NoisePattern`][crate::patterns::NoisePattern], and to make surfaces look
+//! bumpy through a [`BumpMap`][crate::patterns::BumpMap]. Bump maps only tilt
+//! the normal used for shading, so shadows and refraction still follow the
+//! actual surface of the shape.
+
+mod fractal;
+mod perlin;
+mod simplex;
+mod worley;
+
+use crate::linear::Tuple;
+
+pub use fractal::{Fractal, FractalKind};
+pub use perlin::Perlin;
+pub use simplex::Simplex;
+pub use worley::Worley;
+
+/// A source of noise that assigns a value to every point in space.
+pub trait Noise: std::fmt::Debug + Send + Sync {
+    /// Sample the noise at a point.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to sample the noise at.
+    fn noise_at(&self, point: &Tuple) -> f64;
+}
+
+/// A shuffled table of the numbers 0 to 255 used by the gradient noise
+/// generators to hash lattice coordinates.
+#[derive(Clone, Debug)]
+struct Permutation {
+    // The table is repeated twice so that lookups of the form `p[p[x] + y]`
+    // never need to wrap.
+    table: [u8; 512],
+}
+
+impl Permutation {
+    fn new(seed: u64) -> Self {
+        let mut shuffled = [0u8; 256];
+        for (i, entry) in shuffled.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+
+        // Fisher-Yates shuffle driven by a splitmix64 generator so the result
+        // only depends on the seed.
+        let mut state = seed;
+        for i in (1..shuffled.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            shuffled.swap(i, j);
+        }
+
+        let mut table = [0u8; 512];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = shuffled[i % 256];
+        }
+
+        Self { table }
+    }
+
+    fn get(&self, index: usize) -> usize {
+        self.table[index] as usize
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+    z ^ (z >> 31)
+}
+
+/// Scale the position of a point relative to the origin.
+///
+/// Multiplying a point by a scalar would also scale its w component, which
+/// would no longer be a point.
+fn scale_point(point: &Tuple, factor: f64) -> Tuple {
+    Tuple::new_point(point.x() * factor, point.y() * factor, point.z() * factor)
+}
+
+/// Find the index of the lattice cell containing a coordinate, wrapped to the
+/// size of a permutation table.
+fn lattice(coordinate: f64) -> usize {
+    (coordinate.floor() as i64).rem_euclid(256) as usize
+}
@@ -0,0 +1,176 @@
+use crate::linear::Tuple;
+
+use super::{lattice, Noise, Permutation};
+
+/// Ken Perlin's "improved" gradient noise.
+///
+/// The noise is smooth, repeats every 256 units, and is zero at every integer
+/// lattice point. Values fall roughly within `[-1, 1]`.
+#[derive(Clone, Debug)]
+pub struct Perlin {
+    permutation: Permutation,
+}
+
+impl Perlin {
+    /// Create a Perlin noise generator with a specific seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed used to shuffle the generator's permutation table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, noise::{Noise, Perlin}};
+    /// let a = Perlin::new(7);
+    /// let b = Perlin::new(7);
+    /// let point = Tuple::new_point(0.3, 1.2, -4.5);
+    ///
+    /// assert_eq!(a.noise_at(&point), b.noise_at(&point));
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Self {
+            permutation: Permutation::new(seed),
+        }
+    }
+}
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Noise for Perlin {
+    fn noise_at(&self, point: &Tuple) -> f64 {
+        let p = |i: usize| self.permutation.get(i);
+        let (x, y, z) = (point.x(), point.y(), point.z());
+
+        // Find the unit cube containing the point, and the point's position
+        // within that cube.
+        let (xi, yi, zi) = (lattice(x), lattice(y), lattice(z));
+        let (x, y, z) = (x - x.floor(), y - y.floor(), z - z.floor());
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        // Hash the coordinates of the cube's eight corners.
+        let a = p(xi) + yi;
+        let aa = p(a) + zi;
+        let ab = p(a + 1) + zi;
+        let b = p(xi + 1) + yi;
+        let ba = p(b) + zi;
+        let bb = p(b + 1) + zi;
+
+        // Blend the contributions from each corner.
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(p(aa), x, y, z), grad(p(ba), x - 1.0, y, z)),
+                lerp(
+                    u,
+                    grad(p(ab), x, y - 1.0, z),
+                    grad(p(bb), x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(p(aa + 1), x, y, z - 1.0),
+                    grad(p(ba + 1), x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(p(ab + 1), x, y - 1.0, z - 1.0),
+                    grad(p(bb + 1), x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+    }
+}
+
+/// Ease a coordinate within a cell so the noise has a continuous second
+/// derivative across cell boundaries.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Compute the dot product of the offset from a corner with one of twelve
+/// gradient directions chosen by the corner's hash.
+fn grad(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn noise_at_zero_at_lattice_points() {
+        let perlin = Perlin::default();
+
+        assert_eq!(perlin.noise_at(&Tuple::new_point(0, 0, 0)), 0.0);
+        assert_eq!(perlin.noise_at(&Tuple::new_point(3, -7, 12)), 0.0);
+    }
+
+    #[test]
+    fn noise_at_is_deterministic() {
+        let a = Perlin::new(42);
+        let b = Perlin::new(42);
+        let point = Tuple::new_point(0.3, 1.7, -2.2);
+
+        assert_eq!(a.noise_at(&point), b.noise_at(&point));
+    }
+
+    #[test]
+    fn noise_at_depends_on_seed() {
+        let a = Perlin::new(1);
+        let b = Perlin::new(2);
+
+        let differs = (0..10).any(|i| {
+            let point = Tuple::new_point(i as f64 * 0.37 + 0.1, 0.5, 0.5);
+
+            a.noise_at(&point) != b.noise_at(&point)
+        });
+
+        assert!(differs);
+    }
+
+    #[test]
+    fn noise_at_is_bounded_and_varies() {
+        let perlin = Perlin::default();
+        let samples: Vec<f64> = (0..1000)
+            .map(|i| {
+                let t = i as f64 * 0.173;
+
+                perlin.noise_at(&Tuple::new_point(t, t * 0.5 + 0.3, -t * 0.7 + 0.1))
+            })
+            .collect();
+
+        assert!(samples.iter().all(|n| n.abs() <= 1.0));
+        assert!(samples.iter().any(|n| n.abs() > 0.1));
+    }
+
+    #[test]
+    fn noise_at_is_continuous() {
+        let perlin = Perlin::new(3);
+        let a = perlin.noise_at(&Tuple::new_point(1.5, 2.5, 3.5));
+        let b = perlin.noise_at(&Tuple::new_point(1.5001, 2.5, 3.5));
+
+        assert!((a - b).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::linear::Tuple;
+
+use super::{lattice, Noise, Permutation};
+
+/// The gradients used by simplex noise: the midpoints of the edges of a cube.
+const GRADIENTS: [[f64; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+/// The factor used to skew space into a grid of simplices.
+const SKEW: f64 = 1.0 / 3.0;
+
+/// The factor used to unskew a simplex grid back into regular space.
+const UNSKEW: f64 = 1.0 / 6.0;
+
+/// Gradient noise computed over a grid of tetrahedra rather than cubes.
+///
+/// Simplex noise has fewer directional artifacts than Perlin noise and is
+/// cheaper to compute. Values fall within `[-1, 1]`.
+#[derive(Clone, Debug)]
+pub struct Simplex {
+    permutation: Permutation,
+}
+
+impl Simplex {
+    /// Create a simplex noise generator with a specific seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed used to shuffle the generator's permutation table.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            permutation: Permutation::new(seed),
+        }
+    }
+}
+
+impl Default for Simplex {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Noise for Simplex {
+    fn noise_at(&self, point: &Tuple) -> f64 {
+        let p = |i: usize| self.permutation.get(i);
+        let (x, y, z) = (point.x(), point.y(), point.z());
+
+        // Skew the input space to find which simplex cell the point is in.
+        let s = (x + y + z) * SKEW;
+        let (i, j, k) = ((x + s).floor(), (y + s).floor(), (z + s).floor());
+
+        // Unskew the cell's origin back into regular space to find the offset
+        // of the point from the first corner.
+        let t = (i + j + k) * UNSKEW;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+        let z0 = z - (k - t);
+
+        // Each cell is split into six tetrahedra. Work out which one contains
+        // the point by ranking the size of the offsets.
+        let (first, second) = if x0 >= y0 {
+            if y0 >= z0 {
+                ([1, 0, 0], [1, 1, 0])
+            } else if x0 >= z0 {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if y0 < z0 {
+            ([0, 0, 1], [0, 1, 1])
+        } else if x0 < z0 {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let corners = [[0, 0, 0], first, second, [1, 1, 1]];
+        let (ii, jj, kk) = (lattice(i), lattice(j), lattice(k));
+
+        corners
+            .iter()
+            .enumerate()
+            .map(|(n, corner)| {
+                let offset = n as f64 * UNSKEW;
+                let dx = x0 - corner[0] as f64 + offset;
+                let dy = y0 - corner[1] as f64 + offset;
+                let dz = z0 - corner[2] as f64 + offset;
+
+                let falloff = 0.6 - dx * dx - dy * dy - dz * dz;
+                if falloff < 0.0 {
+                    return 0.0;
+                }
+
+                let hash = p(ii + corner[0] + p(jj + corner[1] + p(kk + corner[2])));
+                let g = GRADIENTS[hash % 12];
+
+                falloff.powi(4) * (g[0] * dx + g[1] * dy + g[2] * dz)
+            })
+            .sum::<f64>()
+            // Scale the result to fit in [-1, 1].
+            * 32.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn noise_at_is_deterministic() {
+        let a = Simplex::new(9);
+        let b = Simplex::new(9);
+        let point = Tuple::new_point(4.2, -1.3, 0.8);
+
+        assert_eq!(a.noise_at(&point), b.noise_at(&point));
+    }
+
+    #[test]
+    fn noise_at_is_bounded_and_varies() {
+        let simplex = Simplex::default();
+        let samples: Vec<f64> = (0..1000)
+            .map(|i| {
+                let t = i as f64 * 0.173;
+
+                simplex.noise_at(&Tuple::new_point(t, t * 0.5 + 0.3, -t * 0.7 + 0.1))
+            })
+            .collect();
+
+        assert!(samples.iter().all(|n| n.abs() <= 1.0));
+        assert!(samples.iter().any(|n| n.abs() > 0.1));
+    }
+
+    #[test]
+    fn noise_at_is_continuous() {
+        let simplex = Simplex::new(5);
+        let a = simplex.noise_at(&Tuple::new_point(1.5, 2.5, 3.5));
+        let b = simplex.noise_at(&Tuple::new_point(1.5, 2.5001, 3.5));
+
+        assert!((a - b).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::linear::Tuple;
+
+use super::{splitmix64, Noise};
+
+/// Cellular noise based on the distance to randomly scattered feature points.
+///
+/// Space is divided into unit cubes that each contain a single feature point.
+/// The noise value is the distance from the sampled point to the closest
+/// feature point, which produces a pattern of cells resembling stone, scales,
+/// or cracked mud.
+#[derive(Clone, Debug)]
+pub struct Worley {
+    seed: u64,
+}
+
+impl Worley {
+    /// Create a Worley noise generator with a specific seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed used to place the feature points.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Find the distances from a point to the closest and second closest
+    /// feature points.
+    ///
+    /// The difference between the two distances is zero along the borders
+    /// between cells, which is useful for drawing the edges of the cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to measure from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, noise::Worley};
+    /// let worley = Worley::new(3);
+    ///
+    /// let (f1, f2) = worley.distances(&Tuple::new_point(0.5, 1.5, -2.5));
+    ///
+    /// assert!(f1 <= f2);
+    /// ```
+    pub fn distances(&self, point: &Tuple) -> (f64, f64) {
+        let cell = (
+            point.x().floor() as i64,
+            point.y().floor() as i64,
+            point.z().floor() as i64,
+        );
+
+        let mut closest = f64::INFINITY;
+        let mut second = f64::INFINITY;
+
+        // A feature point can only be closer than the one in the point's own
+        // cell if it's in one of the neighbouring cells.
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let feature = self.feature_point(cell.0 + dx, cell.1 + dy, cell.2 + dz);
+                    let distance = (feature - *point).magnitude();
+
+                    if distance < closest {
+                        second = closest;
+                        closest = distance;
+                    } else if distance < second {
+                        second = distance;
+                    }
+                }
+            }
+        }
+
+        (closest, second)
+    }
+
+    /// Find the feature point within a cell.
+    fn feature_point(&self, x: i64, y: i64, z: i64) -> Tuple {
+        let mut state = self.seed
+            ^ (x as u64).wrapping_mul(0x8da6_b343)
+            ^ (y as u64).wrapping_mul(0xd816_3841)
+            ^ (z as u64).wrapping_mul(0xcb1a_b31f);
+
+        let mut unit = || (splitmix64(&mut state) >> 11) as f64 / (1u64 << 53) as f64;
+
+        Tuple::new_point(x as f64 + unit(), y as f64 + unit(), z as f64 + unit())
+    }
+}
+
+impl Default for Worley {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Noise for Worley {
+    fn noise_at(&self, point: &Tuple) -> f64 {
+        self.distances(point).0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn noise_at_is_deterministic() {
+        let a = Worley::new(11);
+        let b = Worley::new(11);
+        let point = Tuple::new_point(-3.3, 2.1, 0.4);
+
+        assert_eq!(a.noise_at(&point), b.noise_at(&point));
+    }
+
+    #[test]
+    fn noise_at_zero_at_feature_point() {
+        let worley = Worley::new(4);
+        let feature = worley.feature_point(2, -1, 5);
+
+        assert_eq!(worley.noise_at(&feature), 0.0);
+    }
+
+    #[test]
+    fn feature_points_are_inside_their_cell() {
+        let worley = Worley::default();
+
+        for x in -3..3 {
+            let feature = worley.feature_point(x, 7, -2);
+
+            assert!(feature.x() >= x as f64 && feature.x() < x as f64 + 1.0);
+            assert!(feature.y() >= 7.0 && feature.y() < 8.0);
+            assert!(feature.z() >= -2.0 && feature.z() < -1.0);
+        }
+    }
+
+    #[test]
+    fn distances_are_ordered_and_bounded() {
+        let worley = Worley::new(8);
+
+        for i in 0..200 {
+            let t = i as f64 * 0.31;
+            let (f1, f2) = worley.distances(&Tuple::new_point(t, -t * 0.4, t * 0.9));
+
+            assert!(f1 <= f2);
+            // The closest feature point can't be further than the far corner
+            // of a neighbouring cell.
+            assert!(f1 < 2.0 * 3.0_f64.sqrt());
+        }
+    }
+}
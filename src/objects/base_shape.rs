@@ -4,11 +4,11 @@ use crate::{
     Material, Ray,
 };
 
-use super::{ObjectID, Shape};
+use super::Shape;
 
 #[derive(Clone, Debug)]
 pub struct BaseShape {
-    id: ObjectID,
+    name: Option<String>,
     material: Material,
     transform: Matrix,
 }
@@ -16,7 +16,7 @@ pub struct BaseShape {
 impl Default for BaseShape {
     fn default() -> Self {
         Self {
-            id: ObjectID::default(),
+            name: None,
             material: Material::default(),
             transform: Matrix::identity_4(),
         }
@@ -32,7 +32,7 @@ impl Shape for BaseShape {
         unimplemented!("BaseShape should never be used directly. It should be delegated to by a concrete shape.")
     }
 
-    fn intersect_local(&self, _ray: &Ray) -> Intersections {
+    fn intersect_local(&self, _ray: &Ray) -> Intersections<'_> {
         unimplemented!("BaseShape should never be used directly. It should be delegated to by a concrete shape.")
     }
 
@@ -48,8 +48,12 @@ impl Shape for BaseShape {
         self.material = material;
     }
 
-    fn object_id(&self) -> usize {
-        self.id.id()
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_owned());
     }
 
     fn transform(&self) -> &Matrix {
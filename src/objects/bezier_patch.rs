@@ -0,0 +1,786 @@
+use crate::{
+    intersections::{Intersection, Intersections},
+    linear::Tuple,
+    Ray,
+};
+
+use super::{
+    bounds::Bounds, tangent_frame::perpendicular_to, triangle::Triangle, BaseShape, Shape,
+};
+
+/// The number of subdivisions along each parametric direction that a patch is
+/// tessellated into by default.
+const DEFAULT_RESOLUTION: usize = 8;
+
+/// The maximum number of Newton iterations used to refine an intersection.
+const MAX_NEWTON_ITERATIONS: usize = 10;
+
+/// The number of times the control hull is split in half along each
+/// parametric direction when searching for intersections with Newton
+/// iteration.
+const MAX_SUBDIVISION_DEPTH: usize = 5;
+
+/// How rays are intersected with a [`BezierPatch`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PatchIntersection {
+    /// Intersect the flat triangles that the patch is tessellated into. This is
+    /// the fastest option, but the intersections are only as accurate as the
+    /// tessellation.
+    Tessellated,
+
+    /// Intersect the true surface of the patch. The control hull is split
+    /// into smaller pieces to find the parts of the patch that the ray passes
+    /// through, and the intersection with each part is solved for with Newton
+    /// iteration. This is slower, but unlike the tessellation it finds hits
+    /// where the curved surface bulges past its flat triangles, such as near
+    /// the patch's silhouette.
+    Newton,
+}
+
+/// One triangle of a tessellated patch along with the parametric coordinates
+/// of its corners.
+#[derive(Clone, Debug)]
+struct PatchTriangle {
+    triangle: Triangle,
+    uvs: [(f64, f64); 3],
+}
+
+impl PatchTriangle {
+    /// Convert barycentric coordinates within the triangle into parametric
+    /// coordinates on the patch.
+    fn uv_at(&self, a: f64, b: f64) -> (f64, f64) {
+        let [(u1, v1), (u2, v2), (u3, v3)] = self.uvs;
+        let c = 1.0 - a - b;
+
+        (u1 * c + u2 * a + u3 * b, v1 * c + v2 * a + v3 * b)
+    }
+}
+
+/// A piece of a patch covering part of its parametric domain, along with the
+/// control points describing just that piece.
+struct SubPatch {
+    control_points: [Tuple; 16],
+    u_range: (f64, f64),
+    v_range: (f64, f64),
+}
+
+impl SubPatch {
+    /// Split the piece in half along `u`.
+    fn split_u(&self) -> (Self, Self) {
+        let (low, high) = self.split(|i, j| 4 * i + j);
+        let middle = (self.u_range.0 + self.u_range.1) / 2.0;
+
+        (
+            Self {
+                control_points: low,
+                u_range: (self.u_range.0, middle),
+                v_range: self.v_range,
+            },
+            Self {
+                control_points: high,
+                u_range: (middle, self.u_range.1),
+                v_range: self.v_range,
+            },
+        )
+    }
+
+    /// Split the piece in half along `v`.
+    fn split_v(&self) -> (Self, Self) {
+        let (low, high) = self.split(|i, j| 4 * j + i);
+        let middle = (self.v_range.0 + self.v_range.1) / 2.0;
+
+        (
+            Self {
+                control_points: low,
+                u_range: self.u_range,
+                v_range: (self.v_range.0, middle),
+            },
+            Self {
+                control_points: high,
+                u_range: self.u_range,
+                v_range: (middle, self.v_range.1),
+            },
+        )
+    }
+
+    /// Split each curve of control points in half with de Casteljau's
+    /// algorithm.
+    ///
+    /// `index(i, j)` gives the index of the `i`th control point along the
+    /// `j`th curve being split.
+    fn split<F: Fn(usize, usize) -> usize>(&self, index: F) -> ([Tuple; 16], [Tuple; 16]) {
+        let midpoint = |a: Tuple, b: Tuple| a + (b - a) * 0.5;
+        let (mut low, mut high) = (self.control_points, self.control_points);
+
+        for j in 0..4 {
+            let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|i| self.control_points[index(i, j)]);
+            let (a, b, c) = (midpoint(p0, p1), midpoint(p1, p2), midpoint(p2, p3));
+            let (d, e) = (midpoint(a, b), midpoint(b, c));
+            let middle = midpoint(d, e);
+
+            for (i, point) in [p0, a, d, middle].iter().enumerate() {
+                low[index(i, j)] = *point;
+            }
+            for (i, point) in [middle, e, c, p3].iter().enumerate() {
+                high[index(i, j)] = *point;
+            }
+        }
+
+        (low, high)
+    }
+}
+
+/// A bicubic Bézier patch defined by a 4x4 grid of control points.
+///
+/// The patch is parameterized by `u` and `v`, both in the range `[0, 1]`. The
+/// control point at index `4 * i + j` is the point in the `i`th row along `u`
+/// and the `j`th column along `v`.
+#[derive(Clone, Debug)]
+pub struct BezierPatch {
+    base: BaseShape,
+    control_points: [Tuple; 16],
+    resolution: usize,
+    intersection_method: PatchIntersection,
+
+    // Computed from the control points and resolution.
+    triangles: Vec<PatchTriangle>,
+    bounds: Bounds,
+}
+
+impl BezierPatch {
+    /// Create a new patch from its control points.
+    ///
+    /// # Arguments
+    ///
+    /// * `control_points` - The 16 control points of the patch in row-major
+    ///   order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, objects::BezierPatch};
+    /// // A flat 2x2 square in the xz plane.
+    /// let mut points = [Tuple::new_point(0, 0, 0); 16];
+    /// for i in 0..4 {
+    ///     for j in 0..4 {
+    ///         let z = -1.0 + 2.0 * i as f64 / 3.0;
+    ///         let x = -1.0 + 2.0 * j as f64 / 3.0;
+    ///         points[4 * i + j] = Tuple::new_point(x, 0.0, z);
+    ///     }
+    /// }
+    ///
+    /// let patch = BezierPatch::new(points);
+    ///
+    /// assert_eq!(patch.point_at(0.5, 0.5), Tuple::new_point(0, 0, 0));
+    /// assert_eq!(patch.normal_at_uv(0.5, 0.5), Tuple::new_vector(0, 1, 0));
+    /// ```
+    pub fn new(control_points: [Tuple; 16]) -> Self {
+        // A Bézier patch is contained in the convex hull of its control points,
+        // so a ray that misses the box containing them can't hit the patch.
+        let bounds = Bounds::from_points(control_points.iter());
+
+        let mut patch = Self {
+            base: BaseShape::default(),
+            control_points,
+            resolution: DEFAULT_RESOLUTION,
+            intersection_method: PatchIntersection::Tessellated,
+            triangles: Vec::new(),
+            bounds,
+        };
+        patch.tessellate();
+
+        patch
+    }
+
+    pub fn control_points(&self) -> &[Tuple; 16] {
+        &self.control_points
+    }
+
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// Set the number of subdivisions along each parametric direction used
+    /// when tessellating the patch.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolution` - The number of subdivisions. A patch is tessellated into
+    ///   `2 * resolution * resolution` triangles.
+    pub fn set_resolution(&mut self, resolution: usize) {
+        assert!(resolution > 0, "Patch resolution must be positive.");
+
+        self.resolution = resolution;
+        self.tessellate();
+    }
+
+    pub fn intersection_method(&self) -> PatchIntersection {
+        self.intersection_method
+    }
+
+    pub fn set_intersection_method(&mut self, method: PatchIntersection) {
+        self.intersection_method = method;
+    }
+
+    /// Evaluate the patch at a pair of parametric coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The parametric coordinate along the rows of control points.
+    /// * `v` - The parametric coordinate along the columns of control points.
+    pub fn point_at(&self, u: f64, v: f64) -> Tuple {
+        let p = self.evaluate(&bernstein(u), &bernstein(v));
+
+        Tuple::new_point(p.x(), p.y(), p.z())
+    }
+
+    /// Compute the analytic normal vector at a pair of parametric coordinates.
+    ///
+    /// The normal is the cross product of the partial derivatives of the patch.
+    /// Patches like the Utah teapot collapse an entire edge into a single
+    /// point, where the cross product vanishes. In that case, the normal is
+    /// taken from a point nudged towards the middle of the patch instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The parametric coordinate along the rows of control points.
+    /// * `v` - The parametric coordinate along the columns of control points.
+    pub fn normal_at_uv(&self, u: f64, v: f64) -> Tuple {
+        let (mut u, mut v) = (u, v);
+
+        for _ in 0..8 {
+            let (du, dv) = self.derivatives(u, v);
+            let normal = du.cross(dv);
+
+            if normal.magnitude() > 1e-12 {
+                return normal.normalized();
+            }
+
+            u += (0.5 - u) * 1e-3;
+            v += (0.5 - v) * 1e-3;
+        }
+
+        // Every control point in the neighbourhood is identical, so there is no
+        // meaningful normal. Pick something consistent rather than NaN.
+        Tuple::new_vector(0, 1, 0)
+    }
+
+    /// Find the direction that `u` increases in at a pair of parametric
+    /// coordinates.
+    ///
+    /// Where an edge collapses into a single point, there is no such
+    /// direction, so an arbitrary tangent perpendicular to `normal` is used.
+    fn tangent_at_uv(&self, u: f64, v: f64, normal: &Tuple) -> Tuple {
+        let (du, _) = self.derivatives(u, v);
+        if du.magnitude() < 1e-12 {
+            return perpendicular_to(&normal.normalized());
+        }
+
+        du.normalized()
+    }
+
+    /// Sum the control points weighted by the given basis function values.
+    ///
+    /// The result is returned as a vector to allow the same method to be used
+    /// for both points and derivatives.
+    fn evaluate(&self, u_weights: &[f64; 4], v_weights: &[f64; 4]) -> Tuple {
+        let mut sum = Tuple::new_vector(0, 0, 0);
+
+        for (i, u_weight) in u_weights.iter().enumerate() {
+            for (j, v_weight) in v_weights.iter().enumerate() {
+                let p = self.control_points[4 * i + j];
+                sum = sum + Tuple::new_vector(p.x(), p.y(), p.z()) * (u_weight * v_weight);
+            }
+        }
+
+        sum
+    }
+
+    /// Compute the partial derivatives of the patch with respect to `u` and
+    /// `v`.
+    fn derivatives(&self, u: f64, v: f64) -> (Tuple, Tuple) {
+        let du = self.evaluate(&bernstein_derivative(u), &bernstein(v));
+        let dv = self.evaluate(&bernstein(u), &bernstein_derivative(v));
+
+        (du, dv)
+    }
+
+    /// Rebuild the triangles used to intersect the patch.
+    fn tessellate(&mut self) {
+        let n = self.resolution;
+        let step = 1.0 / n as f64;
+
+        let mut grid = Vec::with_capacity((n + 1) * (n + 1));
+        for i in 0..=n {
+            for j in 0..=n {
+                let (u, v) = (i as f64 * step, j as f64 * step);
+                grid.push(((u, v), self.point_at(u, v)));
+            }
+        }
+
+        let corner = |i: usize, j: usize| grid[i * (n + 1) + j];
+
+        self.triangles = Vec::with_capacity(2 * n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let cells = [
+                    [corner(i, j), corner(i + 1, j), corner(i + 1, j + 1)],
+                    [corner(i, j), corner(i + 1, j + 1), corner(i, j + 1)],
+                ];
+
+                for [(uv1, p1), (uv2, p2), (uv3, p3)] in cells.iter() {
+                    let triangle = Triangle::new(*p1, *p2, *p3);
+
+                    // Patches that collapse an edge to a point produce
+                    // triangles with no area. They can never be hit, so they
+                    // are dropped.
+                    if !triangle.is_degenerate() {
+                        self.triangles.push(PatchTriangle {
+                            triangle,
+                            uvs: [*uv1, *uv2, *uv3],
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Find the intersections between a ray and a piece of the patch, and
+    /// add them to `hits` as `(t, u, v)`.
+    ///
+    /// A Bézier patch lies inside the convex hull of its control points, so
+    /// pieces whose control points the ray misses are skipped. The rest are
+    /// split until they are small enough for Newton iteration to converge
+    /// from the middle of the piece.
+    fn intersect_sub_patch(
+        &self,
+        ray: &Ray,
+        sub_patch: &SubPatch,
+        depth: usize,
+        hits: &mut Vec<(f64, f64, f64)>,
+    ) {
+        if !Bounds::from_points(sub_patch.control_points.iter()).is_hit_by(ray) {
+            return;
+        }
+
+        if depth == MAX_SUBDIVISION_DEPTH {
+            let u = (sub_patch.u_range.0 + sub_patch.u_range.1) / 2.0;
+            let v = (sub_patch.v_range.0 + sub_patch.v_range.1) / 2.0;
+
+            // Start from the point along the ray closest to the middle of the
+            // piece.
+            let to_middle = self.point_at(u, v) - ray.origin();
+            let t = to_middle.dot(ray.direction()) / ray.direction().dot(ray.direction());
+
+            if let Some(hit) = self.refine_intersection(ray, t, u, v) {
+                hits.push(hit);
+            }
+
+            return;
+        }
+
+        let (low, high) = sub_patch.split_u();
+        for half in [low, high].iter() {
+            let (low, high) = half.split_v();
+            for quarter in [low, high].iter() {
+                self.intersect_sub_patch(ray, quarter, depth + 1, hits);
+            }
+        }
+    }
+
+    /// Refine an approximate intersection onto the true surface of the patch.
+    ///
+    /// This solves `P(u, v) = origin + t * direction` for `t`, `u`, and `v`
+    /// with Newton's method, returning them as `(t, u, v)`. `None` is returned
+    /// if the iteration doesn't converge to a point on the patch.
+    fn refine_intersection(&self, ray: &Ray, t: f64, u: f64, v: f64) -> Option<(f64, f64, f64)> {
+        let (mut t, mut u, mut v) = (t, u, v);
+        let neg_direction = -ray.direction();
+
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            let error = self.point_at(u, v) - ray.position_at(t);
+            let (du, dv) = self.derivatives(u, v);
+
+            // Solve the 3x3 system [du dv -d] * delta = -error using Cramer's
+            // rule.
+            let det = du.dot(dv.cross(neg_direction));
+            if det.abs() < 1e-12 {
+                return None;
+            }
+
+            let rhs = -error;
+            let delta_u = rhs.dot(dv.cross(neg_direction)) / det;
+            let delta_v = du.dot(rhs.cross(neg_direction)) / det;
+            let delta_t = du.dot(dv.cross(rhs)) / det;
+
+            u += delta_u;
+            v += delta_v;
+            t += delta_t;
+
+            if delta_u.abs() + delta_v.abs() + delta_t.abs() < 1e-10 {
+                break;
+            }
+        }
+
+        let on_patch = (-1e-6..=1.0 + 1e-6).contains(&u) && (-1e-6..=1.0 + 1e-6).contains(&v);
+        let error = self.point_at(u, v) - ray.position_at(t);
+
+        if on_patch && error.magnitude() < 1e-6 {
+            Some((t, u.clamp(0.0, 1.0), v.clamp(0.0, 1.0)))
+        } else {
+            None
+        }
+    }
+
+    /// Find the parametric coordinates of a point on the patch.
+    ///
+    /// The closest tessellated triangle provides a starting guess which is
+    /// then refined with a few Gauss-Newton steps.
+    fn uv_at(&self, point: &Tuple) -> (f64, f64) {
+        let closest = self.triangles.iter().min_by(|a, b| {
+            a.triangle
+                .distance_squared(point)
+                .partial_cmp(&b.triangle.distance_squared(point))
+                .unwrap()
+        });
+
+        let (mut u, mut v) = match closest {
+            Some(patch_triangle) => {
+                let (a, b) = patch_triangle.triangle.barycentric(point);
+                patch_triangle.uv_at(a, b)
+            }
+            None => (0.5, 0.5),
+        };
+
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            let residual = self.point_at(u, v) - *point;
+            let (du, dv) = self.derivatives(u, v);
+
+            let a = du.dot(du);
+            let b = du.dot(dv);
+            let c = dv.dot(dv);
+            let det = a * c - b * b;
+            if det.abs() < 1e-12 {
+                break;
+            }
+
+            let ru = -du.dot(residual);
+            let rv = -dv.dot(residual);
+            let delta_u = (c * ru - b * rv) / det;
+            let delta_v = (a * rv - b * ru) / det;
+
+            u = (u + delta_u).clamp(0.0, 1.0);
+            v = (v + delta_v).clamp(0.0, 1.0);
+
+            if delta_u.abs() + delta_v.abs() < 1e-10 {
+                break;
+            }
+        }
+
+        (u, v)
+    }
+}
+
+impl Shape for BezierPatch {
+    fn base_shape(&self) -> &BaseShape {
+        &self.base
+    }
+
+    fn base_shape_mut(&mut self) -> &mut BaseShape {
+        &mut self.base
+    }
+
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_> {
+        if !self.bounds.is_hit_by(ray) {
+            return Intersections::default();
+        }
+
+        let mut hits = Vec::new();
+        match self.intersection_method {
+            PatchIntersection::Tessellated => {
+                for patch_triangle in self.triangles.iter() {
+                    if let Some((t, a, b)) = patch_triangle.triangle.intersect(ray) {
+                        let (u, v) = patch_triangle.uv_at(a, b);
+                        hits.push((t, u, v));
+                    }
+                }
+            }
+            PatchIntersection::Newton => {
+                let whole = SubPatch {
+                    control_points: self.control_points,
+                    u_range: (0.0, 1.0),
+                    v_range: (0.0, 1.0),
+                };
+
+                self.intersect_sub_patch(ray, &whole, 0, &mut hits);
+            }
+        }
+
+        // A ray passing through a shared edge hits both neighbouring
+        // triangles, and neighbouring pieces can converge to the same point,
+        // so drop any duplicates.
+        hits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        hits.dedup_by(|a, b| (a.0 - b.0).abs() < 1e-6);
+
+        // The patch is a single face, and its parametric coordinates are
+        // recorded so the normal doesn't have to be searched for.
+        Intersections::new(
+            hits.into_iter()
+                .map(|(t, u, v)| Intersection::with_face(t, self, 0, u, v))
+                .collect(),
+        )
+    }
+
+    fn normal_at_local(&self, point: &Tuple) -> Tuple {
+        let (u, v) = self.uv_at(point);
+
+        self.normal_at_uv(u, v)
+    }
+
+    fn normal_at_local_hit(&self, point: &Tuple, hit: &Intersection) -> Tuple {
+        match hit.uv() {
+            Some((u, v)) => self.normal_at_uv(u, v),
+            None => self.normal_at_local(point),
+        }
+    }
+
+    fn tangent_at_local(&self, point: &Tuple, normal: &Tuple) -> Tuple {
+        let (u, v) = self.uv_at(point);
+
+        self.tangent_at_uv(u, v, normal)
+    }
+
+    fn tangent_at_local_hit(&self, point: &Tuple, normal: &Tuple, hit: &Intersection) -> Tuple {
+        match hit.uv() {
+            Some((u, v)) => self.tangent_at_uv(u, v, normal),
+            None => self.tangent_at_local(point, normal),
+        }
+    }
+}
+
+/// Compute the cubic Bernstein basis functions at `t`.
+fn bernstein(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+
+    [s * s * s, 3.0 * t * s * s, 3.0 * t * t * s, t * t * t]
+}
+
+/// Compute the derivatives of the cubic Bernstein basis functions at `t`.
+fn bernstein_derivative(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+
+    [
+        -3.0 * s * s,
+        3.0 * s * s - 6.0 * t * s,
+        6.0 * t * s - 3.0 * t * t,
+        3.0 * t * t,
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Build a 2x2 patch in the xz plane. The middle four control points are
+    /// raised to `height` to form a dome.
+    fn dome(height: f64) -> BezierPatch {
+        let mut points = [Tuple::new_point(0, 0, 0); 16];
+        for i in 0..4 {
+            for j in 0..4 {
+                let z = -1.0 + 2.0 * i as f64 / 3.0;
+                let x = -1.0 + 2.0 * j as f64 / 3.0;
+                let y = if (1..=2).contains(&i) && (1..=2).contains(&j) {
+                    height
+                } else {
+                    0.0
+                };
+
+                points[4 * i + j] = Tuple::new_point(x, y, z);
+            }
+        }
+
+        BezierPatch::new(points)
+    }
+
+    #[test]
+    fn tessellation_triangle_count() {
+        let mut patch = dome(1.0);
+
+        assert_eq!(patch.triangles.len(), 2 * 8 * 8);
+
+        patch.set_resolution(3);
+
+        assert_eq!(patch.triangles.len(), 2 * 3 * 3);
+    }
+
+    #[test]
+    fn point_at_corners() {
+        let patch = dome(1.0);
+
+        assert_eq!(patch.point_at(0.0, 0.0), Tuple::new_point(-1, 0, -1));
+        assert_eq!(patch.point_at(0.0, 1.0), Tuple::new_point(1, 0, -1));
+        assert_eq!(patch.point_at(1.0, 0.0), Tuple::new_point(-1, 0, 1));
+        assert_eq!(patch.point_at(1.0, 1.0), Tuple::new_point(1, 0, 1));
+    }
+
+    #[test]
+    fn intersect_local_flat_patch() {
+        let patch = dome(0.0);
+        let r = Ray::new(Tuple::new_point(0.2, 1.0, 0.3), Tuple::new_vector(0, -1, 0));
+
+        let intersections = patch.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t(), 1.0);
+        assert_eq!(intersections[0].object(), &patch);
+    }
+
+    #[test]
+    fn intersect_local_miss() {
+        let patch = dome(1.0);
+        let r = Ray::new(Tuple::new_point(2, 1, 0), Tuple::new_vector(0, -1, 0));
+
+        let intersections = patch.intersect_local(&r);
+
+        assert!(
+            intersections.is_empty(),
+            "Found unexpected intersections: {:?}",
+            intersections
+        );
+    }
+
+    #[test]
+    fn intersect_local_tessellated_is_approximate() {
+        // With an odd resolution, the top of the dome falls in the middle of a
+        // triangle rather than on a vertex.
+        let mut patch = dome(1.0);
+        patch.set_resolution(3);
+        let r = Ray::new(Tuple::new_point(0, 5, 0), Tuple::new_vector(0, -1, 0));
+
+        let intersections = patch.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 1);
+        assert!((intersections[0].t() - 4.4375).abs() > 1e-3);
+    }
+
+    #[test]
+    fn intersect_local_newton_is_exact() {
+        let mut patch = dome(1.0);
+        patch.set_resolution(3);
+        patch.set_intersection_method(PatchIntersection::Newton);
+        let r = Ray::new(Tuple::new_point(0, 5, 0), Tuple::new_vector(0, -1, 0));
+
+        let intersections = patch.intersect_local(&r);
+
+        // The top of the dome is at y = (3/8 + 3/8)^2 = 0.5625.
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t(), 4.4375);
+    }
+
+    #[test]
+    fn intersect_local_newton_finds_hits_outside_tessellation() {
+        // With a single cell, the tessellation is a flat square under the
+        // dome, so a ray passing through the dome misses it entirely.
+        let mut patch = dome(1.0);
+        patch.set_resolution(1);
+        let r = Ray::new(Tuple::new_point(-5.0, 0.3, 0.0), Tuple::new_vector(1, 0, 0));
+
+        assert!(patch.intersect_local(&r).is_empty());
+
+        patch.set_intersection_method(PatchIntersection::Newton);
+        let intersections = patch.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 2);
+        for intersection in intersections.iter() {
+            let (u, v) = intersection.uv().unwrap();
+
+            assert_eq!(patch.point_at(u, v), r.position_at(intersection.t()));
+        }
+    }
+
+    #[test]
+    fn intersect_local_records_uv() {
+        let patch = dome(1.0);
+        let r = Ray::new(Tuple::new_point(0, 5, 0), Tuple::new_vector(0, -1, 0));
+
+        let intersections = patch.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].uv(), Some((0.5, 0.5)));
+    }
+
+    #[test]
+    fn normal_at_local_hit_uses_uv() {
+        let patch = dome(1.0);
+        let (u, v) = (0.3, 0.8);
+        let hit = Intersection::with_face(1.0, &patch, 0, u, v);
+
+        // The point is ignored in favour of the recorded coordinates.
+        let n = patch.normal_at_local_hit(&Tuple::new_point(0, 0, 0), &hit);
+
+        assert_eq!(n, patch.normal_at_uv(u, v));
+    }
+
+    #[test]
+    fn normal_at_local_top_of_dome() {
+        let patch = dome(1.0);
+
+        let n = patch.normal_at_local(&Tuple::new_point(0.0, 0.5625, 0.0));
+
+        assert_eq!(n, Tuple::new_vector(0, 1, 0));
+    }
+
+    #[test]
+    fn normal_at_local_matches_analytic_normal() {
+        let patch = dome(1.0);
+        let (u, v) = (0.3, 0.8);
+
+        let n = patch.normal_at_local(&patch.point_at(u, v));
+
+        assert_eq!(n, patch.normal_at_uv(u, v));
+    }
+
+    #[test]
+    fn normal_at_uv_collapsed_edge() {
+        // Collapse the whole u = 0 edge into a single point, like the patches
+        // at the top of the Utah teapot's lid.
+        let mut points = *dome(1.0).control_points();
+        for point in points.iter_mut().take(4) {
+            *point = Tuple::new_point(0, 0, -1);
+        }
+        let patch = BezierPatch::new(points);
+
+        let n = patch.normal_at_uv(0.0, 0.5);
+
+        assert!(!n.x().is_nan() && !n.y().is_nan() && !n.z().is_nan());
+        assert!((n.magnitude() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tangent_at_local_follows_u() {
+        // The u coordinate runs along the rows of control points, which step
+        // along the z-axis.
+        let patch = dome(1.0);
+        let point = Tuple::new_point(0.0, 0.5625, 0.0);
+        let normal = patch.normal_at_local(&point);
+
+        let tangent = patch.tangent_at_local(&point, &normal);
+
+        assert_eq!(tangent, Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn tangent_at_local_collapsed_edge() {
+        let mut points = *dome(1.0).control_points();
+        for point in points.iter_mut().take(4) {
+            *point = Tuple::new_point(0, 0, -1);
+        }
+        let patch = BezierPatch::new(points);
+        let point = Tuple::new_point(0, 0, -1);
+        let normal = patch.normal_at_local(&point);
+
+        let tangent = patch.tangent_at_local(&point, &normal);
+
+        assert!((tangent.magnitude() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,573 @@
+use crate::{
+    intersections::{Intersection, Intersections},
+    linear::Tuple,
+    Ray,
+};
+
+use super::{BaseShape, Shape};
+
+/// The threshold used for blobby shapes created with [`Blobby::default`].
+const DEFAULT_THRESHOLD: f64 = 0.5;
+
+/// The number of samples taken across the diameter of the smallest blob when
+/// searching for roots along a ray.
+const SAMPLES_PER_DIAMETER: f64 = 32.0;
+
+/// The maximum number of bisection steps used to refine a root.
+const MAX_BISECTION_STEPS: usize = 64;
+
+/// The shapes of curve that a blob's field can fall off with.
+///
+/// Every falloff is 1 at the centre of a blob and smoothly reaches 0 at its
+/// radius, so only nearby blobs affect each point. They differ in how quickly
+/// the field drops away, which changes how readily neighbouring blobs merge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Falloff {
+    /// The polynomial `(1 - r^2 / R^2)^3`. This is the default.
+    Cubic,
+
+    /// The "soft object" polynomial from Wyvill, McPheeters and Wyvill. It
+    /// stays higher further from the centre than the cubic falloff, so blobs
+    /// bulge more before they merge.
+    Wyvill,
+
+    /// Nishimura's piecewise quadratic metaball falloff, which drops quickly
+    /// near the centre and then tapers off.
+    Metaball,
+}
+
+impl Falloff {
+    /// Compute the falloff for a point, along with its derivative.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The squared distance from the blob's centre to the point,
+    ///   divided by the squared radius of the blob.
+    ///
+    /// # Returns
+    ///
+    /// The falloff, and its derivative with respect to `s`.
+    fn evaluate(&self, s: f64) -> (f64, f64) {
+        if s >= 1.0 {
+            return (0.0, 0.0);
+        }
+
+        match self {
+            Falloff::Cubic => ((1.0 - s).powi(3), -3.0 * (1.0 - s).powi(2)),
+            Falloff::Wyvill => (
+                1.0 - 4.0 / 9.0 * s.powi(3) + 17.0 / 9.0 * s * s - 22.0 / 9.0 * s,
+                -4.0 / 3.0 * s * s + 34.0 / 9.0 * s - 22.0 / 9.0,
+            ),
+            Falloff::Metaball => {
+                // The two pieces meet a third of the way out from the centre.
+                if s < 1.0 / 9.0 {
+                    (1.0 - 3.0 * s, -3.0)
+                } else {
+                    let r = s.sqrt();
+
+                    (1.5 * (1.0 - r).powi(2), -1.5 * (1.0 - r) / r)
+                }
+            }
+        }
+    }
+}
+
+/// A single weighted centre contributing to a [`Blobby`] shape's field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Blob {
+    center: Tuple,
+    radius: f64,
+    strength: f64,
+}
+
+impl Blob {
+    /// Create a new blob.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - The location of the blob's centre in object space.
+    /// * `radius` - The distance from the centre at which the blob's influence
+    ///   falls to zero.
+    /// * `strength` - The value of the blob's field at its centre. Negative
+    ///   strengths carve space out of neighbouring blobs.
+    ///
+    /// # Panics
+    ///
+    /// If `radius` is not positive.
+    pub fn new(center: Tuple, radius: f64, strength: f64) -> Self {
+        assert!(radius > 0.0, "Blob radius must be positive.");
+
+        Self {
+            center,
+            radius,
+            strength,
+        }
+    }
+
+    pub fn center(&self) -> Tuple {
+        self.center
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn strength(&self) -> f64 {
+        self.strength
+    }
+
+    /// Compute the blob's contribution to the field at a point.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The object space point to evaluate the field at.
+    /// * `falloff` - How the blob's field falls off with distance.
+    fn field_at(&self, point: &Tuple, falloff: Falloff) -> f64 {
+        let offset = *point - self.center;
+        let (value, _) = falloff.evaluate(offset.dot(offset) / (self.radius * self.radius));
+
+        self.strength * value
+    }
+
+    /// Compute the gradient of the blob's contribution to the field.
+    fn gradient_at(&self, point: &Tuple, falloff: Falloff) -> Tuple {
+        let offset = *point - self.center;
+        let radius_squared = self.radius * self.radius;
+        let (_, derivative) = falloff.evaluate(offset.dot(offset) / radius_squared);
+
+        offset * (self.strength * derivative * 2.0 / radius_squared)
+    }
+
+    /// Find the range of times where a ray is within the blob's radius of
+    /// influence.
+    fn influence_interval(&self, ray: &Ray) -> Option<(f64, f64)> {
+        let blob_to_ray = ray.origin() - self.center;
+
+        let a = ray.direction().dot(ray.direction());
+        let b = 2.0 * ray.direction().dot(blob_to_ray);
+        let c = blob_to_ray.dot(blob_to_ray) - self.radius * self.radius;
+
+        let discriminant = (b * b) - 4.0 * a * c;
+        if discriminant <= 0.0 {
+            return None;
+        }
+
+        Some((
+            (-b - discriminant.sqrt()) / (2.0 * a),
+            (-b + discriminant.sqrt()) / (2.0 * a),
+        ))
+    }
+}
+
+/// An implicit surface formed by a collection of blobs, also known as
+/// metaballs.
+///
+/// Each blob contributes a field that falls off with distance from its centre.
+/// The surface is wherever the total field equals the shape's threshold, so
+/// blobs that are close together merge into a single smooth form.
+#[derive(Clone, Debug)]
+pub struct Blobby {
+    base: BaseShape,
+    blobs: Vec<Blob>,
+    threshold: f64,
+    falloff: Falloff,
+}
+
+impl Blobby {
+    /// Create a new blobby shape with no blobs.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The field value at which the surface lies.
+    ///
+    /// # Panics
+    ///
+    /// If `threshold` is not positive. Space far away from every blob has a
+    /// field of zero, so a threshold of zero or less would put all of that
+    /// space inside the surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, objects::{Blob, Blobby}};
+    /// let mut blobby = Blobby::new(0.5);
+    /// blobby.add_blob(Blob::new(Tuple::new_point(-1, 0, 0), 2.0, 1.0));
+    /// blobby.add_blob(Blob::new(Tuple::new_point(1, 0, 0), 2.0, 1.0));
+    ///
+    /// assert_eq!(blobby.blobs().len(), 2);
+    /// assert_eq!(blobby.threshold(), 0.5);
+    /// ```
+    pub fn new(threshold: f64) -> Self {
+        assert_positive_threshold(threshold);
+
+        Self {
+            base: BaseShape::default(),
+            blobs: Vec::new(),
+            threshold,
+            falloff: Falloff::Cubic,
+        }
+    }
+
+    pub fn blobs(&self) -> &[Blob] {
+        &self.blobs
+    }
+
+    pub fn add_blob(&mut self, blob: Blob) {
+        self.blobs.push(blob);
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Set the field value at which the surface lies.
+    ///
+    /// # Panics
+    ///
+    /// If `threshold` is not positive.
+    pub fn set_threshold(&mut self, threshold: f64) {
+        assert_positive_threshold(threshold);
+
+        self.threshold = threshold;
+    }
+
+    pub fn falloff(&self) -> Falloff {
+        self.falloff
+    }
+
+    /// Set how the field of each blob falls off with distance from its
+    /// centre.
+    pub fn set_falloff(&mut self, falloff: Falloff) {
+        self.falloff = falloff;
+    }
+
+    /// Compute the total field at a point relative to the threshold.
+    ///
+    /// The result is positive inside the surface and negative outside.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The object space point to evaluate the field at.
+    pub fn field_at(&self, point: &Tuple) -> f64 {
+        self.blobs
+            .iter()
+            .map(|b| b.field_at(point, self.falloff))
+            .sum::<f64>()
+            - self.threshold
+    }
+
+    /// Find the intervals along a ray where at least one blob has influence.
+    ///
+    /// Outside of these intervals the field is zero, and since the threshold
+    /// is positive there can't be any surface there. Overlapping intervals are
+    /// merged so each stretch of the ray is only searched once.
+    fn influence_intervals(&self, ray: &Ray) -> Vec<(f64, f64)> {
+        let mut intervals: Vec<(f64, f64)> = self
+            .blobs
+            .iter()
+            .filter_map(|b| b.influence_interval(ray))
+            .collect();
+        intervals.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut merged: Vec<(f64, f64)> = Vec::with_capacity(intervals.len());
+        for (start, end) in intervals.into_iter() {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        merged
+    }
+
+    /// Refine a root of the field that is known to lie between two times
+    /// using bisection.
+    fn bisect(&self, ray: &Ray, mut low: f64, mut high: f64) -> f64 {
+        let low_inside = self.field_at(&ray.position_at(low)) > 0.0;
+
+        for _ in 0..MAX_BISECTION_STEPS {
+            let middle = (low + high) / 2.0;
+            let middle_inside = self.field_at(&ray.position_at(middle)) > 0.0;
+            if middle_inside == low_inside {
+                low = middle;
+            } else {
+                high = middle;
+            }
+
+            if high - low < 1e-10 {
+                break;
+            }
+        }
+
+        (low + high) / 2.0
+    }
+}
+
+fn assert_positive_threshold(threshold: f64) {
+    assert!(threshold > 0.0, "Blobby threshold must be positive.");
+}
+
+impl Default for Blobby {
+    fn default() -> Self {
+        Self::new(DEFAULT_THRESHOLD)
+    }
+}
+
+impl Shape for Blobby {
+    fn base_shape(&self) -> &BaseShape {
+        &self.base
+    }
+
+    fn base_shape_mut(&mut self) -> &mut BaseShape {
+        &mut self.base
+    }
+
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_> {
+        let smallest_radius = self
+            .blobs
+            .iter()
+            .map(|b| b.radius)
+            .fold(f64::INFINITY, f64::min);
+
+        // Sample the field with a spacing fine enough that the smallest blob
+        // can't be skipped over. The step is a distance along the ray, so it
+        // has to be converted to a step in time.
+        let step = 2.0 * smallest_radius / SAMPLES_PER_DIAMETER / ray.direction().magnitude();
+
+        let mut intersections = Vec::new();
+        for (start, end) in self.influence_intervals(ray).into_iter() {
+            let mut t = start;
+            let mut inside = self.field_at(&ray.position_at(t)) > 0.0;
+
+            while t < end {
+                let next_t = (t + step).min(end);
+                let next_inside = self.field_at(&ray.position_at(next_t)) > 0.0;
+
+                // A change in sign means the surface lies somewhere in this
+                // step.
+                if next_inside != inside {
+                    intersections.push(Intersection::new(self.bisect(ray, t, next_t), self));
+                }
+
+                t = next_t;
+                inside = next_inside;
+            }
+        }
+
+        Intersections::new(intersections)
+    }
+
+    fn normal_at_local(&self, point: &Tuple) -> Tuple {
+        let gradient = self
+            .blobs
+            .iter()
+            .fold(Tuple::new_vector(0, 0, 0), |sum, b| {
+                sum + b.gradient_at(point, self.falloff)
+            });
+
+        // The field increases towards the inside of the surface, so the normal
+        // points against the gradient.
+        if gradient.magnitude() > 1e-12 {
+            return (-gradient).normalized();
+        }
+
+        // The gradient vanishes at saddle points, such as halfway between two
+        // identical blobs, or where a negative blob cancels out the others.
+        // Point away from whichever blob contributes the most to the field
+        // instead, since that's the blob the surface is closest to wrapping
+        // around.
+        let strongest = self.blobs.iter().max_by(|a, b| {
+            a.field_at(point, self.falloff)
+                .partial_cmp(&b.field_at(point, self.falloff))
+                .unwrap()
+        });
+        if let Some(blob) = strongest {
+            let away = *point - blob.center();
+            if away.magnitude() > 1e-12 {
+                return away.normalized();
+            }
+        }
+
+        // The point is at the centre of the blob, so every direction is as
+        // good as any other. Pick something consistent rather than NaN.
+        Tuple::new_vector(0, 1, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn intersect_local_single_blob() {
+        // With a threshold of 1/8, the surface of a single blob is where
+        // (1 - r^2 / 4)^3 = 1/8, which is at r = sqrt(2).
+        let mut blobby = Blobby::new(0.125);
+        blobby.add_blob(Blob::new(Tuple::new_point(0, 0, 0), 2.0, 1.0));
+        let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+        let intersections = blobby.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 2);
+        assert!((intersections[0].t() - (5.0 - 2.0_f64.sqrt())).abs() < 1e-8);
+        assert!((intersections[1].t() - (5.0 + 2.0_f64.sqrt())).abs() < 1e-8);
+        assert_eq!(intersections[0].object(), &blobby);
+    }
+
+    #[test]
+    fn intersect_local_miss() {
+        let mut blobby = Blobby::new(0.125);
+        blobby.add_blob(Blob::new(Tuple::new_point(0, 0, 0), 2.0, 1.0));
+        let r = Ray::new(Tuple::new_point(0, 3, -5), Tuple::new_vector(0, 0, 1));
+
+        let intersections = blobby.intersect_local(&r);
+
+        assert!(
+            intersections.is_empty(),
+            "Found unexpected intersections: {:?}",
+            intersections
+        );
+    }
+
+    #[test]
+    fn intersect_local_no_blobs() {
+        let blobby = Blobby::default();
+        let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+        assert!(blobby.intersect_local(&r).is_empty());
+    }
+
+    #[test]
+    fn intersect_local_blobs_merge() {
+        // Each blob contributes (1 - 1/4)^3 = 0.421875 at the origin, which is
+        // below the threshold alone but above it when combined.
+        let ray = Ray::new(Tuple::new_point(0, -5, 0), Tuple::new_vector(0, 1, 0));
+
+        let mut single = Blobby::new(0.5);
+        single.add_blob(Blob::new(Tuple::new_point(1, 0, 0), 2.0, 1.0));
+
+        let mut merged = single.clone();
+        merged.add_blob(Blob::new(Tuple::new_point(-1, 0, 0), 2.0, 1.0));
+
+        assert!(single.intersect_local(&ray).is_empty());
+        assert_eq!(merged.intersect_local(&ray).len(), 2);
+    }
+
+    #[test]
+    fn intersect_local_negative_blob_carves_hole() {
+        let ray = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+        let mut blobby = Blobby::new(0.125);
+        blobby.add_blob(Blob::new(Tuple::new_point(0, 0, 0), 2.0, 1.0));
+        blobby.add_blob(Blob::new(Tuple::new_point(0, 0, 0), 0.5, -1.0));
+
+        // The ray enters the outer surface, leaves into the hole, re-enters,
+        // then exits the far side.
+        assert_eq!(blobby.intersect_local(&ray).len(), 4);
+    }
+
+    #[test]
+    fn normal_at_local_single_blob() {
+        let mut blobby = Blobby::new(0.125);
+        blobby.add_blob(Blob::new(Tuple::new_point(0, 0, 0), 2.0, 1.0));
+        let sqrt_2 = 2.0_f64.sqrt();
+
+        let n1 = blobby.normal_at_local(&Tuple::new_point(sqrt_2, 0.0, 0.0));
+        let n2 = blobby.normal_at_local(&Tuple::new_point(0.0, 0.0, -sqrt_2));
+
+        assert_eq!(n1, Tuple::new_vector(1, 0, 0));
+        assert_eq!(n2, Tuple::new_vector(0, 0, -1));
+    }
+
+    #[test]
+    fn normal_at_local_between_merged_blobs() {
+        let mut blobby = Blobby::new(0.5);
+        blobby.add_blob(Blob::new(Tuple::new_point(1, 0, 0), 2.0, 1.0));
+        blobby.add_blob(Blob::new(Tuple::new_point(-1, 0, 0), 2.0, 1.0));
+        let ray = Ray::new(Tuple::new_point(0, -5, 0), Tuple::new_vector(0, 1, 0));
+
+        let intersections = blobby.intersect_local(&ray);
+        let n = blobby.normal_at_local(&ray.position_at(intersections[0].t()));
+
+        // By symmetry, the normal at the bottom of the bridge between the two
+        // blobs points straight down.
+        assert_eq!(n, Tuple::new_vector(0, -1, 0));
+    }
+
+    #[test]
+    fn normal_at_local_zero_gradient() {
+        // Halfway between two identical blobs, their gradients cancel out.
+        let mut blobby = Blobby::new(0.5);
+        blobby.add_blob(Blob::new(Tuple::new_point(1, 0, 0), 2.0, 1.0));
+        blobby.add_blob(Blob::new(Tuple::new_point(-1, 0, 0), 2.0, 1.0));
+
+        let n = blobby.normal_at_local(&Tuple::new_point(0, 0, 0));
+
+        assert!(n == Tuple::new_vector(1, 0, 0) || n == Tuple::new_vector(-1, 0, 0));
+    }
+
+    #[test]
+    fn normal_at_local_zero_gradient_at_centre() {
+        let mut blobby = Blobby::new(0.5);
+        blobby.add_blob(Blob::new(Tuple::new_point(0, 0, 0), 2.0, 1.0));
+
+        let n = blobby.normal_at_local(&Tuple::new_point(0, 0, 0));
+
+        assert_eq!(n, Tuple::new_vector(0, 1, 0));
+    }
+
+    #[test]
+    fn falloff_reaches_zero_at_radius() {
+        for falloff in [Falloff::Cubic, Falloff::Wyvill, Falloff::Metaball] {
+            assert_eq!(falloff.evaluate(0.0).0, 1.0, "{:?}", falloff);
+            assert!(falloff.evaluate(1.0 - 1e-9).0.abs() < 1e-9, "{:?}", falloff);
+            assert_eq!(falloff.evaluate(1.5), (0.0, 0.0), "{:?}", falloff);
+        }
+    }
+
+    #[test]
+    fn falloff_derivative_matches_value() {
+        let h = 1e-6;
+
+        for falloff in [Falloff::Cubic, Falloff::Wyvill, Falloff::Metaball] {
+            for s in [0.05, 0.3, 0.7] {
+                let numeric = (falloff.evaluate(s + h).0 - falloff.evaluate(s - h).0) / (2.0 * h);
+
+                assert!(
+                    (falloff.evaluate(s).1 - numeric).abs() < 1e-6,
+                    "{:?} at {}",
+                    falloff,
+                    s
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn set_falloff_changes_surface() {
+        let mut blobby = Blobby::new(0.125);
+        blobby.add_blob(Blob::new(Tuple::new_point(0, 0, 0), 2.0, 1.0));
+        let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+        let cubic = blobby.intersect_local(&r)[0].t();
+
+        blobby.set_falloff(Falloff::Wyvill);
+        let wyvill = blobby.intersect_local(&r)[0].t();
+
+        // The soft object falloff stays higher further out, so the surface is
+        // hit earlier.
+        assert_eq!(blobby.falloff(), Falloff::Wyvill);
+        assert!(wyvill < cubic);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_non_positive_threshold() {
+        Blobby::new(0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_threshold_non_positive() {
+        Blobby::default().set_threshold(-0.5);
+    }
+}
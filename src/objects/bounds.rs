@@ -0,0 +1,155 @@
+use crate::{linear::Tuple, Ray};
+
+/// An axis-aligned box used to quickly reject rays that can't hit a shape.
+#[derive(Clone, Copy, Debug)]
+pub struct Bounds {
+    min: Tuple,
+    max: Tuple,
+}
+
+impl Bounds {
+    /// Find the box containing a set of points.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The points that must be inside the box.
+    pub fn from_points<'a, I: IntoIterator<Item = &'a Tuple>>(points: I) -> Self {
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+
+        for p in points.into_iter() {
+            for (axis, value) in [p.x(), p.y(), p.z()].iter().enumerate() {
+                min[axis] = min[axis].min(*value);
+                max[axis] = max[axis].max(*value);
+            }
+        }
+
+        Self {
+            min: Tuple::new_point(min[0], min[1], min[2]),
+            max: Tuple::new_point(max[0], max[1], max[2]),
+        }
+    }
+
+    pub fn min(&self) -> Tuple {
+        self.min
+    }
+
+    pub fn max(&self) -> Tuple {
+        self.max
+    }
+
+    /// Determine if a point is inside the box.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to check.
+    pub fn contains(&self, point: &Tuple) -> bool {
+        // Allow some slack so points computed on the boundary aren't rejected
+        // due to rounding.
+        point.x() >= self.min.x() - 1e-5
+            && point.x() <= self.max.x() + 1e-5
+            && point.y() >= self.min.y() - 1e-5
+            && point.y() <= self.max.y() + 1e-5
+            && point.z() >= self.min.z() - 1e-5
+            && point.z() <= self.max.z() + 1e-5
+    }
+
+    /// Determine if a ray passes through the box.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray to test against the box.
+    pub fn is_hit_by(&self, ray: &Ray) -> bool {
+        let axes = [
+            (
+                ray.origin().x(),
+                ray.direction().x(),
+                self.min.x(),
+                self.max.x(),
+            ),
+            (
+                ray.origin().y(),
+                ray.direction().y(),
+                self.min.y(),
+                self.max.y(),
+            ),
+            (
+                ray.origin().z(),
+                ray.direction().z(),
+                self.min.z(),
+                self.max.z(),
+            ),
+        ];
+
+        let mut t_min = f64::NEG_INFINITY;
+        let mut t_max = f64::INFINITY;
+
+        for (origin, direction, min, max) in axes.iter() {
+            // Pad the box slightly so flat shapes still have some volume.
+            let (min, max) = (min - 1e-5, max + 1e-5);
+
+            if direction.abs() < 1e-12 {
+                if *origin < min || *origin > max {
+                    return false;
+                }
+
+                continue;
+            }
+
+            let t1 = (min - origin) / direction;
+            let t2 = (max - origin) / direction;
+
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+
+        t_min <= t_max
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn unit_box() -> Bounds {
+        Bounds::from_points(&[Tuple::new_point(-1, -1, -1), Tuple::new_point(1, 1, 1)])
+    }
+
+    #[test]
+    fn is_hit_by_ray_through_box() {
+        let r = Ray::new(Tuple::new_point(5.0, 0.5, 0.0), Tuple::new_vector(-1, 0, 0));
+
+        assert!(unit_box().is_hit_by(&r));
+    }
+
+    #[test]
+    fn is_hit_by_ray_missing_box() {
+        let r = Ray::new(Tuple::new_point(-2, 0, 0), Tuple::new_vector(2, 4, 6));
+
+        assert!(!unit_box().is_hit_by(&r));
+    }
+
+    #[test]
+    fn is_hit_by_parallel_ray_outside_box() {
+        let r = Ray::new(Tuple::new_point(0, 2, -5), Tuple::new_vector(0, 0, 1));
+
+        assert!(!unit_box().is_hit_by(&r));
+    }
+
+    #[test]
+    fn contains_points() {
+        let b = unit_box();
+
+        assert!(b.contains(&Tuple::new_point(0, 0, 0)));
+        assert!(b.contains(&Tuple::new_point(1, -1, 1)));
+        assert!(!b.contains(&Tuple::new_point(0.0, 1.5, 0.0)));
+    }
+
+    #[test]
+    fn is_hit_by_flat_box() {
+        let flat = Bounds::from_points(&[Tuple::new_point(-1, 0, -1), Tuple::new_point(1, 0, 1)]);
+        let r = Ray::new(Tuple::new_point(0, 1, 0), Tuple::new_vector(0, -1, 0));
+
+        assert!(flat.is_hit_by(&r));
+    }
+}
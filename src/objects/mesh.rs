@@ -0,0 +1,421 @@
+use crate::{
+    intersections::{Intersection, Intersections},
+    linear::Tuple,
+    Ray,
+};
+
+use super::{bounds::Bounds, triangle::Triangle, BaseShape, Shape};
+
+/// Vertex normals shorter than this are treated as missing.
+const MIN_NORMAL_LENGTH: f64 = 1e-9;
+
+/// A single face of a mesh.
+#[derive(Clone, Debug)]
+struct MeshTriangle {
+    triangle: Triangle,
+
+    // The index of the face in the list the mesh was created with, which
+    // counts any degenerate faces that were left out.
+    face: usize,
+
+    // Per-vertex normals, if the mesh provides them, used to smoothly
+    // interpolate the normal across the face.
+    normals: Option<[Tuple; 3]>,
+}
+
+impl MeshTriangle {
+    /// Find the normal at a point on the face.
+    ///
+    /// # Arguments
+    ///
+    /// * `(u, v)` - The barycentric coordinates of the point within the face.
+    fn normal_at(&self, (u, v): (f64, f64)) -> Tuple {
+        match self.normals {
+            Some([n1, n2, n3]) => {
+                let normal = n1 * (1.0 - u - v) + n2 * u + n3 * v;
+
+                // Opposing vertex normals can cancel out, leaving nothing to
+                // normalize.
+                if normal.magnitude() < MIN_NORMAL_LENGTH {
+                    self.triangle.normal()
+                } else {
+                    normal.normalized()
+                }
+            }
+            None => self.triangle.normal(),
+        }
+    }
+}
+
+/// A collection of triangles that share a single transform and material.
+///
+/// Meshes are usually produced by loading a model from a file, such as with
+/// [`load_stl`][crate::loaders::load_stl] or
+/// [`load_ply`][crate::loaders::load_ply].
+#[derive(Clone, Debug)]
+pub struct Mesh {
+    base: BaseShape,
+    triangles: Vec<MeshTriangle>,
+    face_count: usize,
+    degenerate_faces: Vec<usize>,
+    bounds: Bounds,
+}
+
+impl Mesh {
+    /// Create a mesh from a list of vertices and the faces connecting them.
+    ///
+    /// The faces are shaded flat, using the normal implied by the winding of
+    /// each face's vertices.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertices` - The points making up the mesh.
+    /// * `faces` - The indices of the vertices making up each triangle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a face refers to a vertex that doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, objects::Mesh};
+    /// let vertices = vec![
+    ///     Tuple::new_point(0, 0, 0),
+    ///     Tuple::new_point(1, 0, 0),
+    ///     Tuple::new_point(0, 1, 0),
+    ///     Tuple::new_point(2, 0, 0),
+    /// ];
+    /// let faces = vec![[0, 1, 2], [0, 1, 3]];
+    ///
+    /// let mesh = Mesh::new(vertices, faces);
+    ///
+    /// assert_eq!(mesh.face_count(), 2);
+    /// assert_eq!(mesh.degenerate_faces(), &[1]);
+    /// ```
+    pub fn new(vertices: Vec<Tuple>, faces: Vec<[usize; 3]>) -> Self {
+        Self::build(&vertices, None, &faces)
+    }
+
+    /// Create a mesh with smooth shading from per-vertex normals.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertices` - The points making up the mesh.
+    /// * `normals` - The normal vector at each vertex.
+    /// * `faces` - The indices of the vertices making up each triangle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't a normal for every vertex, or if a face refers to
+    /// a vertex that doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// Some exporters write normals with no length for vertices they couldn't
+    /// find a normal for. Faces using those vertices fall back to their own
+    /// flat normal at that vertex.
+    pub fn with_normals(vertices: Vec<Tuple>, normals: Vec<Tuple>, faces: Vec<[usize; 3]>) -> Self {
+        assert_eq!(
+            vertices.len(),
+            normals.len(),
+            "A mesh needs exactly one normal per vertex."
+        );
+
+        Self::build(&vertices, Some(&normals), &faces)
+    }
+
+    fn build(vertices: &[Tuple], normals: Option<&[Tuple]>, faces: &[[usize; 3]]) -> Self {
+        let mut triangles = Vec::with_capacity(faces.len());
+        let mut degenerate_faces = Vec::new();
+
+        for (index, &[a, b, c]) in faces.iter().enumerate() {
+            let triangle = Triangle::new(vertices[a], vertices[b], vertices[c]);
+
+            // Faces with no area can never be hit and don't have a meaningful
+            // normal, so they are excluded from the mesh but recorded so the
+            // caller can report them.
+            if triangle.is_degenerate() {
+                degenerate_faces.push(index);
+                continue;
+            }
+
+            let face_normal = triangle.normal();
+            let vertex_normal = |n: Tuple| {
+                if n.magnitude() < MIN_NORMAL_LENGTH {
+                    face_normal
+                } else {
+                    n.normalized()
+                }
+            };
+
+            triangles.push(MeshTriangle {
+                triangle,
+                face: index,
+                normals: normals.map(|n| [n[a], n[b], n[c]].map(vertex_normal)),
+            });
+        }
+
+        Self {
+            base: BaseShape::default(),
+            triangles,
+            face_count: faces.len(),
+            degenerate_faces,
+            bounds: Bounds::from_points(vertices.iter()),
+        }
+    }
+
+    /// Get the number of faces the mesh was created with, including any
+    /// degenerate faces.
+    pub fn face_count(&self) -> usize {
+        self.face_count
+    }
+
+    /// Get the indices of the faces that have no area.
+    ///
+    /// These faces are ignored when rendering. They usually indicate a problem
+    /// with the tool that exported the mesh.
+    pub fn degenerate_faces(&self) -> &[usize] {
+        &self.degenerate_faces
+    }
+}
+
+impl Shape for Mesh {
+    fn base_shape(&self) -> &BaseShape {
+        &self.base
+    }
+
+    fn base_shape_mut(&mut self) -> &mut BaseShape {
+        &mut self.base
+    }
+
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_> {
+        if !self.bounds.is_hit_by(ray) {
+            return Intersections::default();
+        }
+
+        let intersections = self
+            .triangles
+            .iter()
+            .filter_map(|face| {
+                face.triangle
+                    .intersect(ray)
+                    .map(|(t, u, v)| Intersection::with_face(t, self, face.face, u, v))
+            })
+            .collect();
+
+        Intersections::new(intersections)
+    }
+
+    fn normal_at_local(&self, point: &Tuple) -> Tuple {
+        // The point alone doesn't tell us which face it is on, so find the
+        // face closest to it. Normals for hits are found more quickly and
+        // reliably by `normal_at_local_hit`, using the face that was hit.
+        let closest = self.triangles.iter().min_by(|a, b| {
+            a.triangle
+                .distance_squared(point)
+                .partial_cmp(&b.triangle.distance_squared(point))
+                .unwrap()
+        });
+
+        match closest {
+            Some(face) => face.normal_at(face.triangle.barycentric(point)),
+            None => Tuple::new_vector(0, 1, 0),
+        }
+    }
+
+    fn normal_at_local_hit(&self, point: &Tuple, hit: &Intersection) -> Tuple {
+        // Faces are numbered as the mesh was created, and stay in that order
+        // after degenerate faces are left out.
+        let face = hit.face().and_then(|face| {
+            self.triangles
+                .binary_search_by_key(&face, |triangle| triangle.face)
+                .ok()
+        });
+
+        match (face, hit.uv()) {
+            (Some(index), Some(uv)) => self.triangles[index].normal_at(uv),
+            _ => self.normal_at_local(point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Build a unit square in the xy plane made of two triangles.
+    fn square() -> (Vec<Tuple>, Vec<[usize; 3]>) {
+        let vertices = vec![
+            Tuple::new_point(0, 0, 0),
+            Tuple::new_point(1, 0, 0),
+            Tuple::new_point(1, 1, 0),
+            Tuple::new_point(0, 1, 0),
+        ];
+        let faces = vec![[0, 1, 2], [0, 2, 3]];
+
+        (vertices, faces)
+    }
+
+    #[test]
+    fn intersect_local_hits_each_face() {
+        let (vertices, faces) = square();
+        let mesh = Mesh::new(vertices, faces);
+
+        let r1 = Ray::new(
+            Tuple::new_point(0.75, 0.25, -1.0),
+            Tuple::new_vector(0, 0, 1),
+        );
+        let r2 = Ray::new(
+            Tuple::new_point(0.25, 0.75, -1.0),
+            Tuple::new_vector(0, 0, 1),
+        );
+
+        let i1 = mesh.intersect_local(&r1);
+        let i2 = mesh.intersect_local(&r2);
+
+        assert_eq!(i1.len(), 1);
+        assert_eq!(i1[0].t(), 1.0);
+        assert_eq!(i1[0].object(), &mesh);
+        assert_eq!(i2.len(), 1);
+        assert_eq!(i2[0].t(), 1.0);
+    }
+
+    #[test]
+    fn intersect_local_miss() {
+        let (vertices, faces) = square();
+        let mesh = Mesh::new(vertices, faces);
+        let r = Ray::new(Tuple::new_point(2, 2, -1), Tuple::new_vector(0, 0, 1));
+
+        assert!(mesh.intersect_local(&r).is_empty());
+    }
+
+    #[test]
+    fn normal_at_local_flat() {
+        let (vertices, faces) = square();
+        let mesh = Mesh::new(vertices, faces);
+
+        let n = mesh.normal_at_local(&Tuple::new_point(0.25, 0.75, 0.0));
+
+        assert_eq!(n, Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn normal_at_local_interpolates_vertex_normals() {
+        let (vertices, faces) = square();
+        let normals = vec![
+            Tuple::new_vector(-1, 0, 1).normalized(),
+            Tuple::new_vector(1, 0, 1).normalized(),
+            Tuple::new_vector(1, 0, 1).normalized(),
+            Tuple::new_vector(-1, 0, 1).normalized(),
+        ];
+        let mesh = Mesh::with_normals(vertices, normals, faces);
+
+        let left = mesh.normal_at_local(&Tuple::new_point(0.0, 0.5, 0.0));
+        let middle = mesh.normal_at_local(&Tuple::new_point(0.5, 0.5, 0.0));
+
+        assert_eq!(left, Tuple::new_vector(-1, 0, 1).normalized());
+        assert_eq!(middle, Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn intersect_local_records_face() {
+        let (vertices, faces) = square();
+        let mesh = Mesh::new(vertices, faces);
+        let r = Ray::new(
+            Tuple::new_point(0.25, 0.75, -1.0),
+            Tuple::new_vector(0, 0, 1),
+        );
+
+        let intersections = mesh.intersect_local(&r);
+
+        assert_eq!(intersections[0].face(), Some(1));
+        assert_eq!(intersections[0].uv(), Some((0.25, 0.5)));
+    }
+
+    #[test]
+    fn intersect_local_records_face_after_degenerate_face() {
+        let (vertices, faces) = square();
+        let faces = vec![faces[0], [0, 1, 1], faces[1]];
+        let mesh = Mesh::new(vertices, faces);
+        let r = Ray::new(
+            Tuple::new_point(0.25, 0.75, -1.0),
+            Tuple::new_vector(0, 0, 1),
+        );
+
+        let intersections = mesh.intersect_local(&r);
+
+        // The face is numbered as it was given, counting the degenerate face
+        // before it.
+        assert_eq!(intersections[0].face(), Some(2));
+    }
+
+    #[test]
+    fn normal_at_local_hit_after_degenerate_face() {
+        let vertices = vec![
+            Tuple::new_point(0, 0, 0),
+            Tuple::new_point(1, 0, 0),
+            Tuple::new_point(0, 1, 0),
+        ];
+        let faces = vec![[0, 1, 2], [0, 1, 1], [0, 2, 1]];
+        let mesh = Mesh::new(vertices, faces);
+        let point = Tuple::new_point(0.25, 0.25, 0.0);
+
+        let front =
+            mesh.normal_at_local_hit(&point, &Intersection::with_face(1.0, &mesh, 0, 0.25, 0.25));
+        let back =
+            mesh.normal_at_local_hit(&point, &Intersection::with_face(1.0, &mesh, 2, 0.25, 0.25));
+
+        assert_eq!(front, Tuple::new_vector(0, 0, 1));
+        assert_eq!(back, Tuple::new_vector(0, 0, -1));
+    }
+
+    #[test]
+    fn normal_at_local_hit_uses_face() {
+        // Two faces on top of each other, facing opposite ways, so the point
+        // alone can't tell them apart.
+        let vertices = vec![
+            Tuple::new_point(0, 0, 0),
+            Tuple::new_point(1, 0, 0),
+            Tuple::new_point(0, 1, 0),
+        ];
+        let faces = vec![[0, 1, 2], [0, 2, 1]];
+        let mesh = Mesh::new(vertices, faces);
+        let point = Tuple::new_point(0.25, 0.25, 0.0);
+
+        let front =
+            mesh.normal_at_local_hit(&point, &Intersection::with_face(1.0, &mesh, 0, 0.25, 0.25));
+        let back =
+            mesh.normal_at_local_hit(&point, &Intersection::with_face(1.0, &mesh, 1, 0.25, 0.25));
+
+        assert_eq!(front, Tuple::new_vector(0, 0, 1));
+        assert_eq!(back, Tuple::new_vector(0, 0, -1));
+    }
+
+    #[test]
+    fn zero_length_vertex_normals_use_face_normal() {
+        let (vertices, faces) = square();
+        let normals = vec![
+            Tuple::new_vector(0, 0, 0),
+            Tuple::new_vector(0, 0, 1),
+            Tuple::new_vector(0, 0, 1),
+            Tuple::new_vector(0, 0, 1),
+        ];
+        let mesh = Mesh::with_normals(vertices, normals, faces);
+
+        let n = mesh.normal_at_local(&Tuple::new_point(0.1, 0.05, 0.0));
+
+        assert_eq!(n, Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn degenerate_faces_are_flagged() {
+        let (vertices, mut faces) = square();
+        faces.push([0, 1, 1]);
+        let mesh = Mesh::new(vertices, faces);
+
+        assert_eq!(mesh.face_count(), 3);
+        assert_eq!(mesh.degenerate_faces(), &[2]);
+        assert_eq!(mesh.triangles.len(), 2);
+    }
+}
@@ -1,8 +1,14 @@
 mod base_shape;
-mod object_id;
+mod bezier_patch;
+mod blobby;
+mod bounds;
+mod mesh;
 mod plane;
+mod quadric;
 mod shape;
 mod sphere;
+mod tangent_frame;
+mod triangle;
 
 // Pull in the test shape only if running tests. It exercises the ability to
 // delegate operations to the base shape.
@@ -11,8 +17,12 @@ mod test_shape;
 
 // Allow other shapes to utilize the base shape, but don't expose it publicly.
 use base_shape::BaseShape;
-use object_id::ObjectID;
 
+pub use bezier_patch::{BezierPatch, PatchIntersection};
+pub use blobby::{Blob, Blobby, Falloff};
+pub use mesh::Mesh;
 pub use plane::Plane;
+pub use quadric::Quadric;
 pub use shape::Shape;
 pub use sphere::Sphere;
+pub use tangent_frame::TangentFrame;
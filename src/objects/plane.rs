@@ -21,7 +21,7 @@ impl Shape for Plane {
         &mut self.base
     }
 
-    fn intersect_local(&self, ray: &Ray) -> Intersections {
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_> {
         // If the ray has no y-component, we know it will never hit the plane.
         // It's either parallel to the plane, or it's coplanar in which case we
         // treat the infinite number of intersections as having no
@@ -41,6 +41,11 @@ impl Shape for Plane {
         // along the y-axis.
         Tuple::new_vector(0, 1, 0)
     }
+
+    fn tangent_at_local(&self, _point: &Tuple, _normal: &Tuple) -> Tuple {
+        // The planar texture mapping's u coordinate increases along the x-axis.
+        Tuple::new_vector(1, 0, 0)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,426 @@
+use crate::{
+    intersections::{Intersection, Intersections},
+    linear::Tuple,
+    Ray,
+};
+
+use super::{bounds::Bounds, BaseShape, Shape};
+
+/// A surface described by a general second degree equation:
+///
+/// `Ax² + By² + Cz² + Dxy + Exz + Fyz + Gx + Hy + Iz + J = 0`
+///
+/// Depending on the coefficients, this describes ellipsoids, hyperboloids,
+/// paraboloids, cylinders, cones, and planes. Many of these extend infinitely,
+/// so a quadric can optionally be clipped to a box in object space.
+#[derive(Clone, Debug)]
+pub struct Quadric {
+    base: BaseShape,
+    coefficients: [f64; 10],
+    clip: Option<Bounds>,
+}
+
+impl Quadric {
+    /// Create a new quadric from its coefficients.
+    ///
+    /// # Arguments
+    ///
+    /// * `coefficients` - The coefficients `A` through `J`, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::objects::Quadric;
+    /// // x² + y² + z² - 1 = 0 is the unit sphere.
+    /// let sphere = Quadric::new([1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0]);
+    /// ```
+    pub fn new(coefficients: [f64; 10]) -> Self {
+        Self {
+            base: BaseShape::default(),
+            coefficients,
+            clip: None,
+        }
+    }
+
+    /// Create an ellipsoid centred at the origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `rx`, `ry`, `rz` - The radius of the ellipsoid along each axis.
+    pub fn ellipsoid(rx: f64, ry: f64, rz: f64) -> Self {
+        Self::new([
+            1.0 / (rx * rx),
+            1.0 / (ry * ry),
+            1.0 / (rz * rz),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -1.0,
+        ])
+    }
+
+    /// Create an infinite elliptic cylinder around the y-axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `rx`, `rz` - The radius of the cylinder along the x- and z-axis.
+    pub fn elliptic_cylinder(rx: f64, rz: f64) -> Self {
+        Self::new([
+            1.0 / (rx * rx),
+            0.0,
+            1.0 / (rz * rz),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -1.0,
+        ])
+    }
+
+    /// Create an elliptic paraboloid opening upwards along the y-axis, with its
+    /// vertex at the origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `rx`, `rz` - The radius of the paraboloid along the x- and z-axis at
+    ///   a height of one.
+    pub fn paraboloid(rx: f64, rz: f64) -> Self {
+        Self::new([
+            1.0 / (rx * rx),
+            0.0,
+            1.0 / (rz * rz),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -1.0,
+            0.0,
+            0.0,
+        ])
+    }
+
+    /// Create a hyperboloid of one sheet around the y-axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `rx`, `rz` - The radius of the waist of the hyperboloid along the x-
+    ///   and z-axis.
+    /// * `ry` - Controls how quickly the hyperboloid widens along the y-axis.
+    pub fn hyperboloid_one_sheet(rx: f64, ry: f64, rz: f64) -> Self {
+        Self::new([
+            1.0 / (rx * rx),
+            -1.0 / (ry * ry),
+            1.0 / (rz * rz),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -1.0,
+        ])
+    }
+
+    /// Create a hyperboloid of two sheets around the y-axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `rx`, `rz` - Control how quickly the sheets widen along the x- and
+    ///   z-axis.
+    /// * `ry` - The distance from the origin to the vertex of each sheet.
+    pub fn hyperboloid_two_sheets(rx: f64, ry: f64, rz: f64) -> Self {
+        Self::new([
+            -1.0 / (rx * rx),
+            1.0 / (ry * ry),
+            -1.0 / (rz * rz),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -1.0,
+        ])
+    }
+
+    pub fn coefficients(&self) -> &[f64; 10] {
+        &self.coefficients
+    }
+
+    /// Clip the quadric to an axis-aligned box in object space.
+    ///
+    /// Intersections outside of the box are ignored. Since the surface isn't
+    /// capped, clipped quadrics are open at the edges of the box.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The corner of the box with the smallest coordinates.
+    /// * `max` - The corner of the box with the largest coordinates.
+    pub fn set_clip_bounds(&mut self, min: Tuple, max: Tuple) {
+        self.clip = Some(Bounds::from_points(&[min, max]));
+    }
+
+    /// Get the corners of the box the quadric is clipped to, if any.
+    pub fn clip_bounds(&self) -> Option<(Tuple, Tuple)> {
+        self.clip.map(|b| (b.min(), b.max()))
+    }
+
+    /// Evaluate the quadric's equation at a point.
+    ///
+    /// The result is zero on the surface, and has opposite signs on either
+    /// side of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The object space point to evaluate the equation at.
+    pub fn value_at(&self, point: &Tuple) -> f64 {
+        let [a, b, c, d, e, f, g, h, i, j] = self.coefficients;
+        let (x, y, z) = (point.x(), point.y(), point.z());
+
+        a * x * x
+            + b * y * y
+            + c * z * z
+            + d * x * y
+            + e * x * z
+            + f * y * z
+            + g * x
+            + h * y
+            + i * z
+            + j
+    }
+
+    fn is_visible(&self, point: &Tuple) -> bool {
+        match &self.clip {
+            Some(bounds) => bounds.contains(point),
+            None => true,
+        }
+    }
+}
+
+impl Shape for Quadric {
+    fn base_shape(&self) -> &BaseShape {
+        &self.base
+    }
+
+    fn base_shape_mut(&mut self) -> &mut BaseShape {
+        &mut self.base
+    }
+
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_> {
+        if let Some(bounds) = &self.clip {
+            if !bounds.is_hit_by(ray) {
+                return Intersections::default();
+            }
+        }
+
+        let [ca, cb, cc, cd, ce, cf, cg, ch, ci, _] = self.coefficients;
+        let o = ray.origin();
+        let d = ray.direction();
+
+        // Substituting the ray's equation into the quadric's gives a quadratic
+        // in t. The constant term is the quadric evaluated at the origin.
+        let a = ca * d.x() * d.x()
+            + cb * d.y() * d.y()
+            + cc * d.z() * d.z()
+            + cd * d.x() * d.y()
+            + ce * d.x() * d.z()
+            + cf * d.y() * d.z();
+        let b = 2.0 * (ca * o.x() * d.x() + cb * o.y() * d.y() + cc * o.z() * d.z())
+            + cd * (o.x() * d.y() + o.y() * d.x())
+            + ce * (o.x() * d.z() + o.z() * d.x())
+            + cf * (o.y() * d.z() + o.z() * d.y())
+            + cg * d.x()
+            + ch * d.y()
+            + ci * d.z();
+        let c = self.value_at(&o);
+
+        let times = if a.abs() < 1e-12 {
+            // The quadratic term vanishes when the ray is parallel to an axis
+            // the surface is open along (like the axis of a paraboloid),
+            // leaving a single linear solution.
+            if b.abs() < 1e-12 {
+                vec![]
+            } else {
+                vec![-c / b]
+            }
+        } else {
+            let discriminant = (b * b) - 4.0 * a * c;
+            if discriminant < 0.0 {
+                vec![]
+            } else {
+                vec![
+                    (-b - discriminant.sqrt()) / (2.0 * a),
+                    (-b + discriminant.sqrt()) / (2.0 * a),
+                ]
+            }
+        };
+
+        let intersections = times
+            .into_iter()
+            .filter(|t| self.is_visible(&ray.position_at(*t)))
+            .map(|t| Intersection::new(t, self))
+            .collect();
+
+        Intersections::new(intersections)
+    }
+
+    fn normal_at_local(&self, point: &Tuple) -> Tuple {
+        let [a, b, c, d, e, f, g, h, i, _] = self.coefficients;
+        let (x, y, z) = (point.x(), point.y(), point.z());
+
+        // The normal is the gradient of the quadric's equation.
+        Tuple::new_vector(
+            2.0 * a * x + d * y + e * z + g,
+            2.0 * b * y + d * x + f * z + h,
+            2.0 * c * z + e * x + f * y + i,
+        )
+        .normalized()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn intersect_local_unit_sphere() {
+        let q = Quadric::ellipsoid(1.0, 1.0, 1.0);
+        let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+        let intersections = q.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t(), 4.0);
+        assert_eq!(intersections[1].t(), 6.0);
+        assert_eq!(intersections[0].object(), &q);
+    }
+
+    #[test]
+    fn intersect_local_ellipsoid() {
+        let q = Quadric::ellipsoid(3.0, 1.0, 2.0);
+        let along_x = Ray::new(Tuple::new_point(-5, 0, 0), Tuple::new_vector(1, 0, 0));
+        let along_z = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+        let ix = q.intersect_local(&along_x);
+        let iz = q.intersect_local(&along_z);
+
+        assert!(float_cmp::approx_eq!(f64, ix[0].t(), 2.0, epsilon = 1e-9));
+        assert!(float_cmp::approx_eq!(f64, ix[1].t(), 8.0, epsilon = 1e-9));
+        assert!(float_cmp::approx_eq!(f64, iz[0].t(), 3.0, epsilon = 1e-9));
+        assert!(float_cmp::approx_eq!(f64, iz[1].t(), 7.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn intersect_local_miss() {
+        let q = Quadric::ellipsoid(1.0, 1.0, 1.0);
+        let r = Ray::new(Tuple::new_point(0, 2, -5), Tuple::new_vector(0, 0, 1));
+
+        assert!(q.intersect_local(&r).is_empty());
+    }
+
+    #[test]
+    fn intersect_local_paraboloid_along_axis() {
+        // A ray along the paraboloid's axis only hits it once, at its vertex.
+        let q = Quadric::paraboloid(1.0, 1.0);
+        let r = Ray::new(Tuple::new_point(0, -5, 0), Tuple::new_vector(0, 1, 0));
+
+        let intersections = q.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t(), 5.0);
+    }
+
+    #[test]
+    fn intersect_local_hyperboloid_waist() {
+        let q = Quadric::hyperboloid_one_sheet(1.0, 1.0, 1.0);
+        let through_waist = Ray::new(Tuple::new_point(-5, 0, 0), Tuple::new_vector(1, 0, 0));
+        let along_axis = Ray::new(Tuple::new_point(0, -5, 0), Tuple::new_vector(0, 1, 0));
+
+        let i1 = q.intersect_local(&through_waist);
+        let i2 = q.intersect_local(&along_axis);
+
+        assert_eq!(i1.len(), 2);
+        assert_eq!(i1[0].t(), 4.0);
+        assert!(i2.is_empty());
+    }
+
+    #[test]
+    fn intersect_local_hyperboloid_two_sheets() {
+        let q = Quadric::hyperboloid_two_sheets(1.0, 1.0, 1.0);
+        let r = Ray::new(Tuple::new_point(0, -5, 0), Tuple::new_vector(0, 1, 0));
+
+        let intersections = q.intersect_local(&r);
+
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t(), 4.0);
+        assert_eq!(intersections[1].t(), 6.0);
+    }
+
+    #[test]
+    fn intersect_local_clipped_cylinder() {
+        let mut q = Quadric::elliptic_cylinder(1.0, 1.0);
+        q.set_clip_bounds(Tuple::new_point(-2, 0, -2), Tuple::new_point(2, 1, 2));
+
+        let inside_bounds = Ray::new(Tuple::new_point(0.0, 0.5, -5.0), Tuple::new_vector(0, 0, 1));
+        let outside_bounds = Ray::new(Tuple::new_point(0, 2, -5), Tuple::new_vector(0, 0, 1));
+
+        // A diagonal ray that leaves the cylinder below the clipped region
+        // passes through the bounds without hitting the open surface.
+        let diagonal = Ray::new(
+            Tuple::new_point(0.0, -1.5, 0.0),
+            Tuple::new_vector(0, 1, 1).normalized(),
+        );
+
+        assert_eq!(q.intersect_local(&inside_bounds).len(), 2);
+        assert!(q.intersect_local(&outside_bounds).is_empty());
+        assert!(q.intersect_local(&diagonal).is_empty());
+    }
+
+    #[test]
+    fn clip_bounds() {
+        let mut q = Quadric::elliptic_cylinder(1.0, 1.0);
+
+        assert!(q.clip_bounds().is_none());
+
+        q.set_clip_bounds(Tuple::new_point(-2, 0, -2), Tuple::new_point(2, 1, 2));
+
+        assert_eq!(
+            q.clip_bounds(),
+            Some((Tuple::new_point(-2, 0, -2), Tuple::new_point(2, 1, 2)))
+        );
+    }
+
+    #[test]
+    fn normal_at_local_ellipsoid() {
+        let q = Quadric::ellipsoid(3.0, 1.0, 2.0);
+
+        let n1 = q.normal_at_local(&Tuple::new_point(3, 0, 0));
+        let n2 = q.normal_at_local(&Tuple::new_point(0, -1, 0));
+
+        assert_eq!(n1, Tuple::new_vector(1, 0, 0));
+        assert_eq!(n2, Tuple::new_vector(0, -1, 0));
+    }
+
+    #[test]
+    fn normal_at_local_cylinder_ignores_height() {
+        let q = Quadric::elliptic_cylinder(1.0, 1.0);
+
+        let n = q.normal_at_local(&Tuple::new_point(0, 10, 1));
+
+        assert_eq!(n, Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn normal_at_local_paraboloid_vertex() {
+        let q = Quadric::paraboloid(1.0, 1.0);
+
+        let n = q.normal_at_local(&Tuple::new_point(0, 0, 0));
+
+        assert_eq!(n, Tuple::new_vector(0, -1, 0));
+    }
+}
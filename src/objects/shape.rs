@@ -1,10 +1,10 @@
 use crate::{
-    intersections::Intersections,
+    intersections::{Intersection, Intersections},
     linear::{Matrix, Tuple},
     Material, Ray,
 };
 
-use super::BaseShape;
+use super::{tangent_frame::perpendicular_to, BaseShape, TangentFrame};
 
 pub trait Shape: std::fmt::Debug {
     /// Retrieve the object's base shape.
@@ -27,7 +27,7 @@ pub trait Shape: std::fmt::Debug {
     ///
     /// * `ray` - The ray to intersect with the object. It is treated as being
     ///   in object space (as opposed to world space).
-    fn intersect_local(&self, ray: &Ray) -> Intersections;
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_>;
 
     /// Find the normal vector at an object space location on the object's
     /// surface.
@@ -39,6 +39,57 @@ pub trait Shape: std::fmt::Debug {
     ///   surface to get a good result.
     fn normal_at_local(&self, point: &Tuple) -> Tuple;
 
+    /// Find the normal vector where an intersection hit the object's surface,
+    /// in object space.
+    ///
+    /// Shapes that record extra information in their intersections, such as
+    /// which face of a mesh was hit, can use it here to find the normal more
+    /// quickly or accurately. By default, the intersection is ignored and
+    /// [`normal_at_local`][Self::normal_at_local] is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point where the object was hit, in object space.
+    /// * `hit` - The intersection that hit the object.
+    fn normal_at_local_hit(&self, point: &Tuple, _hit: &Intersection) -> Tuple {
+        self.normal_at_local(point)
+    }
+
+    /// Find a tangent vector at an object space location on the object's
+    /// surface.
+    ///
+    /// Shapes with a natural texture mapping should return the direction that
+    /// the u coordinate of the mapping increases in. By default, the tangent
+    /// follows the lines of latitude around the y-axis, which turns smoothly
+    /// with the normal so that bumps and anisotropic highlights don't show
+    /// seams. This suits shapes without a texture mapping of their own, such
+    /// as quadrics, meshes, and blobby shapes.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point on the object's surface, in object space.
+    /// * `normal` - The object space normal vector at the point.
+    fn tangent_at_local(&self, _point: &Tuple, normal: &Tuple) -> Tuple {
+        perpendicular_to(&normal.normalized())
+    }
+
+    /// Find a tangent vector where an intersection hit the object's surface,
+    /// in object space.
+    ///
+    /// Like [`normal_at_local_hit`][Self::normal_at_local_hit], this lets
+    /// shapes use what they recorded in the intersection. By default, the
+    /// intersection is ignored and [`tangent_at_local`][Self::tangent_at_local]
+    /// is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point where the object was hit, in object space.
+    /// * `normal` - The object space normal vector at the point.
+    /// * `hit` - The intersection that hit the object.
+    fn tangent_at_local_hit(&self, point: &Tuple, normal: &Tuple, _hit: &Intersection) -> Tuple {
+        self.tangent_at_local(point, normal)
+    }
+
     ////////////////////////////////////////////////////////////////////////////
     // The following methods have default implementations that rely on the    //
     // above methods.                                                         //
@@ -57,9 +108,14 @@ pub trait Shape: std::fmt::Debug {
         self.base_shape_mut().set_material(material);
     }
 
-    /// Retrieve the object's unique ID.
-    fn object_id(&self) -> usize {
-        self.base_shape().object_id()
+    /// Retrieve the user-assigned name of the shape, if any.
+    fn name(&self) -> Option<&str> {
+        self.base_shape().name()
+    }
+
+    /// Give the shape a name that it can be looked up by.
+    fn set_name(&mut self, name: &str) {
+        self.base_shape_mut().set_name(name);
     }
 
     /// Retrieve the shape's transform.
@@ -81,7 +137,7 @@ pub trait Shape: std::fmt::Debug {
     /// # Arguments
     ///
     /// * `ray` - The ray to intersect with the object.
-    fn intersect(&self, ray: &Ray) -> Intersections {
+    fn intersect(&self, ray: &Ray) -> Intersections<'_> {
         let local_ray = ray.transformed(&self.transform().inverted());
 
         self.intersect_local(&local_ray)
@@ -103,22 +159,127 @@ pub trait Shape: std::fmt::Debug {
 
         let local_point = &inverted_transform * *point;
         let local_normal = self.normal_at_local(&local_point);
-        let world_normal = &inverted_transform.transposed() * local_normal;
 
-        // Since the proper calculations involve taking the submatrix containing
-        // only the x, y, and z components of the transform, we have to zero out
-        // w here. The simplest way of doing that is by constructing a new
-        // vector.
-        Tuple::new_vector(world_normal.x(), world_normal.y(), world_normal.z()).normalized()
+        to_world_normal(&inverted_transform, local_normal)
+    }
+
+    /// Find the normal vector where an intersection hit the object's surface.
+    ///
+    /// This is the same as [`normal_at`][Self::normal_at], except that the
+    /// object space normal is found using
+    /// [`normal_at_local_hit`][Self::normal_at_local_hit].
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point where the object was hit, in world space.
+    /// * `hit` - The intersection that hit the object.
+    fn normal_at_hit(&self, point: &Tuple, hit: &Intersection) -> Tuple {
+        let inverted_transform = self.transform().inverted();
+
+        let local_point = &inverted_transform * *point;
+        let local_normal = self.normal_at_local_hit(&local_point, hit);
+
+        to_world_normal(&inverted_transform, local_normal)
+    }
+
+    /// Find the tangent frame at a point on the object's surface.
+    ///
+    /// The tangent is found in object space using
+    /// [`tangent_at_local`][Self::tangent_at_local] and converted into world
+    /// space. The frame's normal is the same as [`normal_at`][Self::normal_at].
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point on the object's surface to find the frame at,
+    ///   in world space.
+    fn tangent_frame_at(&self, point: &Tuple) -> TangentFrame {
+        let inverted_transform = self.transform().inverted();
+
+        let local_point = &inverted_transform * *point;
+        let local_normal = self.normal_at_local(&local_point);
+        let local_tangent = self.tangent_at_local(&local_point, &local_normal);
+
+        to_world_frame(
+            self.transform(),
+            &inverted_transform,
+            local_tangent,
+            local_normal,
+        )
     }
+
+    /// Find the tangent frame where an intersection hit the object's surface.
+    ///
+    /// This is the same as [`tangent_frame_at`][Self::tangent_frame_at],
+    /// except that the frame is found using
+    /// [`normal_at_local_hit`][Self::normal_at_local_hit] and
+    /// [`tangent_at_local_hit`][Self::tangent_at_local_hit], so its normal
+    /// matches [`normal_at_hit`][Self::normal_at_hit].
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point where the object was hit, in world space.
+    /// * `hit` - The intersection that hit the object.
+    fn tangent_frame_at_hit(&self, point: &Tuple, hit: &Intersection) -> TangentFrame {
+        let inverted_transform = self.transform().inverted();
+
+        let local_point = &inverted_transform * *point;
+        let local_normal = self.normal_at_local_hit(&local_point, hit);
+        let local_tangent = self.tangent_at_local_hit(&local_point, &local_normal, hit);
+
+        to_world_frame(
+            self.transform(),
+            &inverted_transform,
+            local_tangent,
+            local_normal,
+        )
+    }
+}
+
+/// Convert a normal vector from object space into world space.
+///
+/// # Arguments
+///
+/// * `inverted_transform` - The inverse of the object's transform.
+/// * `local_normal` - The normal vector in object space.
+fn to_world_normal(inverted_transform: &Matrix, local_normal: Tuple) -> Tuple {
+    let world_normal = &inverted_transform.transposed() * local_normal;
+
+    // Since the proper calculations involve taking the submatrix containing
+    // only the x, y, and z components of the transform, we have to zero out
+    // w here. The simplest way of doing that is by constructing a new
+    // vector.
+    Tuple::new_vector(world_normal.x(), world_normal.y(), world_normal.z()).normalized()
+}
+
+/// Convert a tangent frame from object space into world space.
+///
+/// # Arguments
+///
+/// * `transform` - The object's transform.
+/// * `inverted_transform` - The inverse of the object's transform.
+/// * `local_tangent` - The tangent vector in object space.
+/// * `local_normal` - The normal vector in object space.
+fn to_world_frame(
+    transform: &Matrix,
+    inverted_transform: &Matrix,
+    local_tangent: Tuple,
+    local_normal: Tuple,
+) -> TangentFrame {
+    // Unlike normals, tangents lie along the surface, so they are transformed
+    // the same way as the surface itself.
+    let world_tangent = transform * local_tangent;
+
+    TangentFrame::new(
+        world_tangent,
+        to_world_normal(inverted_transform, local_normal),
+    )
 }
 
 impl PartialEq for &dyn Shape {
     fn eq(&self, other: &Self) -> bool {
-        // We only use shape equality in test cases where we already have a
-        // reference to the object we expect to see, so we can do this ID
-        // comparison. This check will break down if we need to be able to
-        // construct expected objects from their material/transform.
-        self.object_id() == other.object_id()
+        // Two references are only the same shape if they point at the same
+        // object. Comparing addresses (and ignoring the vtable) means this
+        // works regardless of whether the shapes have been added to a world.
+        std::ptr::addr_eq(*self, *other)
     }
 }
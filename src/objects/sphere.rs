@@ -18,7 +18,7 @@ impl Shape for Sphere {
         &mut self.base
     }
 
-    fn intersect_local(&self, ray: &Ray) -> Intersections {
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_> {
         let sphere_to_ray = ray.origin() - Tuple::new_point(0, 0, 0);
 
         let a = ray.direction().dot(ray.direction());
@@ -45,6 +45,18 @@ impl Shape for Sphere {
     fn normal_at_local(&self, point: &Tuple) -> Tuple {
         *point - Tuple::new_point(0, 0, 0)
     }
+
+    fn tangent_at_local(&self, point: &Tuple, _normal: &Tuple) -> Tuple {
+        // Follow the lines of latitude in the direction that the spherical
+        // texture mapping's u coordinate increases. There is no such direction
+        // at the poles.
+        let tangent = Tuple::new_vector(-point.z(), 0.0, point.x());
+        if tangent.magnitude() < 1e-9 {
+            return Tuple::new_vector(1, 0, 0);
+        }
+
+        tangent.normalized()
+    }
 }
 
 #[cfg(test)]
@@ -143,7 +155,7 @@ mod test {
     #[test]
     fn normal_at_local_non_axial_point() {
         let s = Sphere::default();
-        let sqrt_3_over_3 = (3.0 as f64).sqrt() / 3.0;
+        let sqrt_3_over_3 = 3.0_f64.sqrt() / 3.0;
 
         let n = s.normal_at_local(&Tuple::new_point(
             sqrt_3_over_3,
@@ -160,7 +172,7 @@ mod test {
     #[test]
     fn normal_at_local_is_normalized() {
         let s = Sphere::default();
-        let sqrt_3_over_3 = (3.0 as f64).sqrt() / 3.0;
+        let sqrt_3_over_3 = 3.0_f64.sqrt() / 3.0;
 
         let n = s.normal_at_local(&Tuple::new_point(
             sqrt_3_over_3,
@@ -170,4 +182,25 @@ mod test {
 
         assert_eq!(n, n.normalized());
     }
+
+    #[test]
+    fn tangent_at_local_follows_texture_u() {
+        let s = Sphere::default();
+        let point = Tuple::new_point(0, 0, -1);
+
+        let t = s.tangent_at_local(&point, &s.normal_at_local(&point));
+
+        assert_eq!(t, Tuple::new_vector(1, 0, 0));
+    }
+
+    #[test]
+    fn tangent_at_local_at_pole() {
+        let s = Sphere::default();
+        let point = Tuple::new_point(0, 1, 0);
+
+        let t = s.tangent_at_local(&point, &s.normal_at_local(&point));
+
+        assert_eq!(t.dot(s.normal_at_local(&point)), 0.0);
+        assert_eq!(t.magnitude(), 1.0);
+    }
 }
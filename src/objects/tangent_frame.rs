@@ -0,0 +1,151 @@
+use crate::linear::Tuple;
+
+/// A set of perpendicular unit vectors describing the orientation of a
+/// surface at a point.
+///
+/// The tangent and bitangent lie along the surface, and point in the
+/// directions that texture coordinates increase in, so that normal maps
+/// authored in tangent space can be applied to the surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TangentFrame {
+    tangent: Tuple,
+    bitangent: Tuple,
+    normal: Tuple,
+}
+
+impl TangentFrame {
+    /// Create a tangent frame from a tangent and normal vector.
+    ///
+    /// The tangent doesn't have to be exactly perpendicular to the normal.
+    /// Any part of it that lies along the normal is removed. The bitangent is
+    /// the cross product of the tangent and normal.
+    ///
+    /// # Arguments
+    ///
+    /// * `tangent` - A vector along the surface.
+    /// * `normal` - The normal vector of the surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, objects::TangentFrame};
+    /// let frame = TangentFrame::new(Tuple::new_vector(1, 1, 0), Tuple::new_vector(0, 1, 0));
+    ///
+    /// assert_eq!(frame.tangent(), Tuple::new_vector(1, 0, 0));
+    /// assert_eq!(frame.bitangent(), Tuple::new_vector(0, 0, 1));
+    /// assert_eq!(frame.normal(), Tuple::new_vector(0, 1, 0));
+    /// ```
+    pub fn new(tangent: Tuple, normal: Tuple) -> Self {
+        let normal = normal.normalized();
+        let along_surface = tangent - normal * tangent.dot(normal);
+
+        // If the tangent lies along the normal, any direction along the
+        // surface will have to do.
+        let tangent = if along_surface.magnitude() < 1e-9 {
+            perpendicular_to(&normal)
+        } else {
+            along_surface.normalized()
+        };
+
+        Self {
+            tangent,
+            bitangent: tangent.cross(normal),
+            normal,
+        }
+    }
+
+    pub fn tangent(&self) -> Tuple {
+        self.tangent
+    }
+
+    pub fn bitangent(&self) -> Tuple {
+        self.bitangent
+    }
+
+    pub fn normal(&self) -> Tuple {
+        self.normal
+    }
+
+    /// Convert a vector from tangent space, where the tangent, bitangent, and
+    /// normal lie along the x-, y-, and z-axis, into the space of the frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `vector` - The vector in tangent space.
+    pub fn to_frame_space(&self, vector: &Tuple) -> Tuple {
+        self.tangent * vector.x() + self.bitangent * vector.y() + self.normal * vector.z()
+    }
+}
+
+/// Find a unit vector perpendicular to another vector.
+///
+/// The result follows the lines of latitude around the y-axis, like the
+/// tangents of a sphere. It changes smoothly as the vector turns, so tangent
+/// frames built from it don't have seams, except at the two points where the
+/// vector lies along the y-axis and there is no line of latitude to follow.
+///
+/// # Arguments
+///
+/// * `vector` - The unit vector to find a perpendicular vector for.
+pub(super) fn perpendicular_to(vector: &Tuple) -> Tuple {
+    let around_y = Tuple::new_vector(-vector.z(), 0.0, vector.x());
+    if around_y.magnitude() < 1e-9 {
+        return Tuple::new_vector(1, 0, 0);
+    }
+
+    around_y.normalized()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new_tangent_along_normal() {
+        let frame = TangentFrame::new(Tuple::new_vector(0, 2, 0), Tuple::new_vector(0, 1, 0));
+
+        assert!(frame.tangent().dot(frame.normal()).abs() < 1e-9);
+        assert!((frame.tangent().magnitude() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn perpendicular_to_x_axis() {
+        let v = perpendicular_to(&Tuple::new_vector(1, 0, 0));
+
+        assert_eq!(v, Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn perpendicular_to_y_axis() {
+        let v = perpendicular_to(&Tuple::new_vector(0, -1, 0));
+
+        assert_eq!(v, Tuple::new_vector(1, 0, 0));
+    }
+
+    #[test]
+    fn perpendicular_to_is_continuous() {
+        // Nudging the vector only nudges the result, wherever the vector
+        // points.
+        for i in 0..100 {
+            let angle = i as f64 * 0.0628;
+            let a = Tuple::new_vector(angle.cos(), 0.3, angle.sin()).normalized();
+            let b = Tuple::new_vector((angle + 1e-4).cos(), 0.3, (angle + 1e-4).sin()).normalized();
+
+            assert!((perpendicular_to(&a) - perpendicular_to(&b)).magnitude() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn to_frame_space() {
+        let frame = TangentFrame::new(Tuple::new_vector(1, 0, 0), Tuple::new_vector(0, 1, 0));
+
+        assert_eq!(
+            frame.to_frame_space(&Tuple::new_vector(0, 0, 1)),
+            Tuple::new_vector(0, 1, 0)
+        );
+        assert_eq!(
+            frame.to_frame_space(&Tuple::new_vector(0, 1, 0)),
+            Tuple::new_vector(0, 0, 1)
+        );
+    }
+}
@@ -21,7 +21,7 @@ impl Shape for TestShape {
         &mut self.base
     }
 
-    fn intersect_local(&self, ray: &Ray) -> Intersections {
+    fn intersect_local(&self, ray: &Ray) -> Intersections<'_> {
         self.last_intersected_ray.replace(Some(*ray));
 
         Intersections::default()
@@ -40,7 +40,7 @@ mod test {
 
     use super::*;
 
-    use crate::{linear::Matrix, Material};
+    use crate::{intersections::Intersection, linear::Matrix, Material};
 
     #[test]
     fn test_material() {
@@ -49,7 +49,7 @@ mod test {
         assert_eq!(s.material(), &Material::default());
 
         let override_material = Material::default().with_ambient(1.0);
-        s.set_material(override_material);
+        s.set_material(override_material.clone());
 
         assert_eq!(s.material(), &override_material);
     }
@@ -66,6 +66,17 @@ mod test {
         assert_eq!(s.transform(), &new_transform);
     }
 
+    #[test]
+    fn test_name() {
+        let mut s = TestShape::default();
+
+        assert_eq!(s.name(), None);
+
+        s.set_name("test_shape");
+
+        assert_eq!(s.name(), Some("test_shape"));
+    }
+
     #[test]
     fn intersect_scaled() {
         let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
@@ -102,6 +113,8 @@ mod test {
     }
 
     #[test]
+    // The rounded values come straight from the book's test case.
+    #[allow(clippy::approx_constant)]
     fn normal_at_translated() {
         // Normal vector should not be impacted by translation.
         let mut s = TestShape::default();
@@ -127,4 +140,42 @@ mod test {
 
         assert_eq!(n, Tuple::new_vector(0.0, 0.97014, -0.24254));
     }
+
+    #[test]
+    fn test_tangent_frame_at_default_is_orthonormal() {
+        let s = TestShape::default();
+
+        let frame = s.tangent_frame_at(&Tuple::new_point(1, 2, 3));
+
+        assert_eq!(frame.normal(), Tuple::new_vector(1, 2, 3).normalized());
+        assert!(frame.tangent().dot(frame.normal()).abs() < 1e-9);
+        assert!(frame.bitangent().dot(frame.normal()).abs() < 1e-9);
+        assert!(frame.tangent().dot(frame.bitangent()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tangent_frame_at_transformed() {
+        let mut s = TestShape::default();
+        s.set_transform(Matrix::rotation_y(PI / 2.0));
+
+        // In object space the point is on the z-axis, so the default tangent
+        // follows the line of latitude along the negative x-axis, which is
+        // rotated onto the z-axis.
+        let frame = s.tangent_frame_at(&Tuple::new_point(1, 0, 0));
+
+        assert_eq!(frame.normal(), Tuple::new_vector(1, 0, 0));
+        assert_eq!(frame.tangent(), Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn test_tangent_frame_at_hit_defaults_to_tangent_frame_at() {
+        let mut s = TestShape::default();
+        s.set_transform(Matrix::rotation_y(PI / 2.0));
+        let point = Tuple::new_point(1, 0, 0);
+
+        let frame = s.tangent_frame_at_hit(&point, &Intersection::new(1.0, &s));
+
+        assert_eq!(frame.normal(), s.tangent_frame_at(&point).normal());
+        assert_eq!(frame.tangent(), s.tangent_frame_at(&point).tangent());
+    }
 }
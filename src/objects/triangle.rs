@@ -0,0 +1,213 @@
+use crate::{linear::Tuple, Ray};
+
+/// A flat triangle used as a building block by shapes made up of many
+/// triangles.
+///
+/// This isn't a shape by itself. Shapes that tessellate their surface own a
+/// collection of these and delegate their intersection tests to them.
+#[derive(Clone, Debug)]
+pub struct Triangle {
+    p1: Tuple,
+    e1: Tuple,
+    e2: Tuple,
+}
+
+impl Triangle {
+    /// Create a new triangle from its three corners.
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple) -> Self {
+        Self {
+            p1,
+            e1: p2 - p1,
+            e2: p3 - p1,
+        }
+    }
+
+    /// Get the triangle's normal vector.
+    ///
+    /// The normal is `e1 x e2`, so it points towards an observer who sees the
+    /// corners in counter-clockwise order. This matches the winding used by
+    /// common mesh formats.
+    pub fn normal(&self) -> Tuple {
+        self.e1.cross(self.e2).normalized()
+    }
+
+    /// Determine if the triangle has no area.
+    pub fn is_degenerate(&self) -> bool {
+        self.e1.cross(self.e2).magnitude() < 1e-12
+    }
+
+    /// Find where a ray intersects the triangle using the Möller–Trumbore
+    /// algorithm.
+    ///
+    /// If the ray hits, the result contains the time of the intersection as
+    /// well as the barycentric `u` and `v` coordinates of the hit, which
+    /// describe how far along the first and second edges the hit is.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray to intersect with the triangle.
+    pub fn intersect(&self, ray: &Ray) -> Option<(f64, f64, f64)> {
+        let dir_cross_e2 = ray.direction().cross(self.e2);
+        let det = self.e1.dot(dir_cross_e2);
+
+        // A determinant of zero means the ray is parallel to the triangle.
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        let f = 1.0 / det;
+        let p1_to_origin = ray.origin() - self.p1;
+        let u = f * p1_to_origin.dot(dir_cross_e2);
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let origin_cross_e1 = p1_to_origin.cross(self.e1);
+        let v = f * ray.direction().dot(origin_cross_e1);
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = f * self.e2.dot(origin_cross_e1);
+
+        Some((t, u, v))
+    }
+
+    /// Find the barycentric coordinates of a point on the triangle's plane.
+    ///
+    /// The result is the `u` and `v` coordinates relative to the first and
+    /// second edges, matching the values returned from
+    /// [`intersect`][Self::intersect].
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to find the coordinates of.
+    pub fn barycentric(&self, point: &Tuple) -> (f64, f64) {
+        let to_point = *point - self.p1;
+
+        let d00 = self.e1.dot(self.e1);
+        let d01 = self.e1.dot(self.e2);
+        let d11 = self.e2.dot(self.e2);
+        let d20 = to_point.dot(self.e1);
+        let d21 = to_point.dot(self.e2);
+        let denominator = d00 * d11 - d01 * d01;
+
+        (
+            (d11 * d20 - d01 * d21) / denominator,
+            (d00 * d21 - d01 * d20) / denominator,
+        )
+    }
+
+    /// Find the squared distance from a point to the closest point on the
+    /// triangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to measure the distance from.
+    pub fn distance_squared(&self, point: &Tuple) -> f64 {
+        let (u, v) = self.barycentric(point);
+        let (u, v) = clamp_barycentric(u, v);
+        let closest = self.p1 + self.e1 * u + self.e2 * v;
+        let offset = *point - closest;
+
+        offset.dot(offset)
+    }
+}
+
+/// Clamp barycentric coordinates so they describe a point inside the triangle.
+///
+/// This is an approximation of the true closest point, but it is plenty
+/// accurate for choosing between neighbouring triangles.
+fn clamp_barycentric(u: f64, v: f64) -> (f64, f64) {
+    let u = u.max(0.0);
+    let v = v.max(0.0);
+    let sum = u + v;
+
+    if sum > 1.0 {
+        (u / sum, v / sum)
+    } else {
+        (u, v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn default_triangle() -> Triangle {
+        Triangle::new(
+            Tuple::new_point(0, 1, 0),
+            Tuple::new_point(-1, 0, 0),
+            Tuple::new_point(1, 0, 0),
+        )
+    }
+
+    #[test]
+    fn normal_follows_winding() {
+        let t = default_triangle();
+
+        assert_eq!(t.normal(), Tuple::new_vector(0, 0, 1));
+    }
+
+    #[test]
+    fn intersect_parallel_ray() {
+        let t = default_triangle();
+        let r = Ray::new(Tuple::new_point(0, -1, -2), Tuple::new_vector(0, 1, 0));
+
+        assert!(t.intersect(&r).is_none());
+    }
+
+    #[test]
+    fn intersect_misses_edges() {
+        let t = default_triangle();
+
+        let rays = [
+            Ray::new(Tuple::new_point(1, 1, -2), Tuple::new_vector(0, 0, 1)),
+            Ray::new(Tuple::new_point(-1, 1, -2), Tuple::new_vector(0, 0, 1)),
+            Ray::new(Tuple::new_point(0, -1, -2), Tuple::new_vector(0, 0, 1)),
+        ];
+
+        for r in rays.iter() {
+            assert!(t.intersect(r).is_none(), "Expected {:?} to miss.", r);
+        }
+    }
+
+    #[test]
+    fn intersect_hit() {
+        let t = default_triangle();
+        let r = Ray::new(Tuple::new_point(0.0, 0.5, -2.0), Tuple::new_vector(0, 0, 1));
+
+        let (time, u, v) = t.intersect(&r).expect("Expected a hit.");
+
+        assert_eq!(time, 2.0);
+        assert_eq!(u, 0.25);
+        assert_eq!(v, 0.25);
+    }
+
+    #[test]
+    fn barycentric_matches_intersection() {
+        let t = default_triangle();
+        let r = Ray::new(
+            Tuple::new_point(-0.2, 0.3, -2.0),
+            Tuple::new_vector(0, 0, 1),
+        );
+
+        let (time, u, v) = t.intersect(&r).expect("Expected a hit.");
+        let (bu, bv) = t.barycentric(&r.position_at(time));
+
+        assert!((u - bu).abs() < 1e-9);
+        assert!((v - bv).abs() < 1e-9);
+    }
+
+    #[test]
+    fn degenerate_triangle() {
+        let t = Triangle::new(
+            Tuple::new_point(0, 0, 0),
+            Tuple::new_point(1, 1, 1),
+            Tuple::new_point(2, 2, 2),
+        );
+
+        assert!(t.is_degenerate());
+        assert!(!default_triangle().is_degenerate());
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    Color,
+};
+
+use super::Pattern;
+
+/// A pattern that is a weighted average of two other patterns.
+#[derive(Clone, Debug)]
+pub struct Blend {
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    weight: f64,
+    transform: Matrix,
+}
+
+impl Blend {
+    /// Create a new pattern that blends two patterns equally.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first pattern to blend.
+    /// * `b` - The second pattern to blend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, patterns::{Blend, Pattern, Solid}, Color};
+    /// let pattern = Blend::new(Solid::new(Color::new(1, 0, 0)), Solid::new(Color::new(0, 0, 1)));
+    ///
+    /// assert_eq!(
+    ///     pattern.pattern_at(&Tuple::new_point(0, 0, 0)),
+    ///     Color::new(0.5, 0.0, 0.5)
+    /// );
+    /// ```
+    pub fn new<A, B>(a: A, b: B) -> Self
+    where
+        A: Pattern + 'static,
+        B: Pattern + 'static,
+    {
+        Self {
+            a: Arc::new(a),
+            b: Arc::new(b),
+            weight: 0.5,
+            transform: Matrix::identity_4(),
+        }
+    }
+
+    /// Create a copy of the blend with a different weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - How much of the second pattern to use, between 0 and 1. A
+    ///   weight of 0 gives only the first pattern, and a weight of 1 gives
+    ///   only the second.
+    pub fn with_weight(&self, weight: f64) -> Self {
+        Self {
+            weight,
+            ..self.clone()
+        }
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+impl Pattern for Blend {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        self.a.sample(point) * (1.0 - self.weight) + self.b.sample(point) * self.weight
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::patterns::{Solid, Stripe};
+
+    #[test]
+    fn pattern_at_weighted() {
+        let pattern = Blend::new(
+            Solid::new(Color::new(1, 1, 1)),
+            Solid::new(Color::new(0, 0, 0)),
+        )
+        .with_weight(0.25);
+
+        let c = pattern.pattern_at(&Tuple::new_point(0, 0, 0));
+
+        assert_eq!(c, Color::new(0.75, 0.75, 0.75));
+    }
+
+    #[test]
+    fn pattern_at_crossed_stripes() {
+        let white = Color::new(1, 1, 1);
+        let black = Color::new(0, 0, 0);
+        let mut vertical = Stripe::new(white, black);
+        vertical.set_transform(Matrix::rotation_y(std::f64::consts::FRAC_PI_2));
+        let pattern = Blend::new(Stripe::new(white, black), vertical);
+
+        // Where both stripes agree the color is unchanged, otherwise it's an
+        // even mix.
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0.5, 0.0, -0.5)), white);
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(1.5, 0.0, -0.5)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+    }
+}
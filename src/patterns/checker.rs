@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    Color,
+};
+
+use super::{Pattern, Solid};
+
+/// A three dimensional checkerboard of unit cubes that alternate between two
+/// colors.
+#[derive(Clone, Debug)]
+pub struct Checker {
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    transform: Matrix,
+}
+
+impl Checker {
+    /// Create a new checker pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The color of the cube touching the origin in the positive
+    ///   direction, and every other cube from there.
+    /// * `b` - The color of the remaining cubes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, patterns::{Checker, Pattern}, Color};
+    /// let white = Color::new(1, 1, 1);
+    /// let black = Color::new(0, 0, 0);
+    /// let pattern = Checker::new(white, black);
+    ///
+    /// assert_eq!(pattern.pattern_at(&Tuple::new_point(0.5, 0.5, 0.5)), white);
+    /// assert_eq!(pattern.pattern_at(&Tuple::new_point(1.5, 0.5, 0.5)), black);
+    /// ```
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Create a new checker pattern that alternates between two other patterns.
+    ///
+    /// The sub-patterns are sampled in this pattern's space, so their
+    /// transforms are relative to this pattern's transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The pattern used for the cube touching the origin in the
+    ///   positive direction, and every other cube from there.
+    /// * `b` - The pattern used for the remaining cubes.
+    pub fn from_patterns<A, B>(a: A, b: B) -> Self
+    where
+        A: Pattern + 'static,
+        B: Pattern + 'static,
+    {
+        Self {
+            a: Arc::new(a),
+            b: Arc::new(b),
+            transform: Matrix::identity_4(),
+        }
+    }
+}
+
+impl Pattern for Checker {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        let sum = point.x().floor() + point.y().floor() + point.z().floor();
+
+        if (sum as i64).rem_euclid(2) == 0 {
+            self.a.sample(point)
+        } else {
+            self.b.sample(point)
+        }
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::patterns::Stripe;
+
+    fn white() -> Color {
+        Color::new(1, 1, 1)
+    }
+
+    fn black() -> Color {
+        Color::new(0, 0, 0)
+    }
+
+    #[test]
+    fn pattern_at_repeats_in_x() {
+        let pattern = Checker::new(white(), black());
+
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0, 0, 0)), white());
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.99, 0.0, 0.0)),
+            white()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(1.01, 0.0, 0.0)),
+            black()
+        );
+    }
+
+    #[test]
+    fn pattern_at_repeats_in_y() {
+        let pattern = Checker::new(white(), black());
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.0, 0.99, 0.0)),
+            white()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.0, 1.01, 0.0)),
+            black()
+        );
+    }
+
+    #[test]
+    fn pattern_at_repeats_in_z() {
+        let pattern = Checker::new(white(), black());
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.0, 0.0, 0.99)),
+            white()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.0, 0.0, 1.01)),
+            black()
+        );
+    }
+
+    #[test]
+    fn pattern_at_negative_coordinates() {
+        let pattern = Checker::new(white(), black());
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(-0.5, 0.5, 0.5)),
+            black()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(-0.5, -0.5, 0.5)),
+            white()
+        );
+    }
+
+    #[test]
+    fn pattern_at_nested_stripes() {
+        let red = Color::new(1, 0, 0);
+        let mut stripes = Stripe::new(white(), black());
+        stripes.set_transform(Matrix::scaling(0.5, 0.5, 0.5));
+        let pattern = Checker::from_patterns(stripes, Solid::new(red));
+
+        // The first cube is striped every half unit.
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.25, 0.0, 0.0)),
+            white()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.75, 0.0, 0.0)),
+            black()
+        );
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(1.25, 0.0, 0.0)), red);
+    }
+}
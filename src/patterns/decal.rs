@@ -0,0 +1,319 @@
+use std::sync::Arc;
+
+use crate::{
+    camera::view_transform,
+    linear::{Matrix, Tuple},
+    objects::Shape,
+    Color,
+};
+
+use super::{pattern::to_pattern_space, Pattern, Texture};
+
+/// The ways a decal can project its texture.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Project the texture straight along the projector's view, so it stays
+    /// the same size no matter how far away the surface is. The longer side
+    /// of the texture spans from -1 to 1 across the projector's view.
+    Orthographic,
+
+    /// Project the texture outwards from a point like a slide projector, so
+    /// it grows larger on surfaces further away.
+    Perspective {
+        /// The angle, in radians, that the longer side of the texture spans.
+        field_of_view: f64,
+    },
+}
+
+/// A pattern that projects a texture onto whatever surfaces are in front of
+/// it, like a sticker or a slide projector.
+///
+/// The projector works like a [`Camera`][crate::camera::Camera]. It sits at
+/// the origin of the pattern's space and looks along the negative z-axis, so
+/// the pattern's transform is used to place and aim it. Like other patterns,
+/// the transform is relative to the object the decal is applied to, so the
+/// decal moves with the object. Surfaces facing away from the projector, and
+/// parts of the surface that the texture doesn't reach, show a background
+/// pattern instead.
+#[derive(Clone, Debug)]
+pub struct Decal {
+    texture: Texture,
+    projection: Projection,
+    background: Arc<dyn Pattern>,
+    transform: Matrix,
+    inverse_transform: Matrix,
+}
+
+impl Decal {
+    /// Create a new decal.
+    ///
+    /// # Arguments
+    ///
+    /// * `texture` - The texture to project.
+    /// * `projection` - How the texture is projected.
+    /// * `background` - The pattern shown wherever the texture isn't
+    ///   projected. It's sampled in the same space as the decal, rather than
+    ///   the projector's space.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// # use raytracer::{
+    /// #     linear::Tuple,
+    /// #     loaders::load_ppm,
+    /// #     patterns::{Decal, Projection, Solid, Texture},
+    /// #     Color, Material,
+    /// # };
+    /// let image = load_ppm(File::open("logo.ppm").unwrap()).unwrap();
+    /// let logo = Decal::new(
+    ///     Texture::new(image),
+    ///     Projection::Orthographic,
+    ///     Solid::new(Color::new(0.8, 0.1, 0.1)),
+    /// )
+    /// .aimed_at(
+    ///     &Tuple::new_point(0, 0, -5),
+    ///     &Tuple::new_point(0, 0, 0),
+    ///     &Tuple::new_vector(0, 1, 0),
+    /// );
+    ///
+    /// let m = Material::default().with_pattern(logo);
+    /// ```
+    pub fn new<P: Pattern + 'static>(
+        texture: Texture,
+        projection: Projection,
+        background: P,
+    ) -> Self {
+        Self {
+            texture,
+            projection,
+            background: Arc::new(background),
+            transform: Matrix::identity_4(),
+            inverse_transform: Matrix::identity_4(),
+        }
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    pub fn background(&self) -> &dyn Pattern {
+        self.background.as_ref()
+    }
+
+    /// Create a copy of the decal with the projector moved and aimed at a
+    /// point.
+    ///
+    /// This replaces the decal's transform, in the same way that
+    /// [`view_transform`] positions a camera.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Where the projector is located.
+    /// * `to` - The point the projector is aimed at.
+    /// * `up` - The direction that is up in the projected texture.
+    pub fn aimed_at(&self, from: &Tuple, to: &Tuple, up: &Tuple) -> Self {
+        let inverse_transform = view_transform(from, to, up);
+
+        Self {
+            transform: inverse_transform.inverted(),
+            inverse_transform,
+            ..self.clone()
+        }
+    }
+
+    /// Find where a point lands on the projected texture, if it lands on it at
+    /// all.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to project, in pattern space.
+    fn project(&self, point: &Tuple) -> Option<(f64, f64)> {
+        // Like a camera, the projector can't see behind itself.
+        if point.z() >= 0.0 {
+            return None;
+        }
+
+        let (x, y, half_view) = match self.projection {
+            Projection::Orthographic => (point.x(), point.y(), 1.0),
+            Projection::Perspective { field_of_view } => (
+                point.x() / -point.z(),
+                point.y() / -point.z(),
+                (field_of_view / 2.0).tan(),
+            ),
+        };
+
+        let image = self.texture.image();
+        let aspect = image.width() as f64 / image.height() as f64;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+
+        // The x-axis points to the projector's left, so u runs against it.
+        let u = 0.5 - x / (2.0 * half_width);
+        let v = 0.5 + y / (2.0 * half_height);
+
+        if (0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v) {
+            Some((u, v))
+        } else {
+            None
+        }
+    }
+
+    /// Sample the background behind a point in pattern space.
+    fn background_at(&self, point: &Tuple) -> Color {
+        self.background.sample(&(&self.transform * *point))
+    }
+}
+
+impl Pattern for Decal {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        match self.project(point) {
+            Some((u, v)) => self.texture.color_at_uv(u, v),
+            None => self.background_at(point),
+        }
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse_transform = transform.inverted();
+        self.transform = transform;
+    }
+
+    fn pattern_at_shape(&self, object: &dyn Shape, world_point: &Tuple) -> Color {
+        let (pattern_point, normal) = to_pattern_space(
+            object,
+            &self.transform,
+            &self.inverse_transform,
+            world_point,
+        );
+        let to_projector = match self.projection {
+            Projection::Orthographic => Tuple::new_vector(0, 0, 1),
+            Projection::Perspective { .. } => Tuple::new_point(0, 0, 0) - pattern_point,
+        };
+
+        // The texture only lands on the side of an object facing the
+        // projector, rather than passing through to the back of it.
+        let facing = normal.dot(to_projector);
+        if facing <= 0.0 {
+            return self.background_at(&pattern_point);
+        }
+
+        self.pattern_at(&pattern_point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+
+    use crate::{
+        canvas::Canvas,
+        objects::{Plane, Sphere},
+        patterns::{Solid, TextureFilter},
+    };
+
+    fn background() -> Color {
+        Color::new(0, 1, 0)
+    }
+
+    /// Build a decal whose texture is red on its left half and blue on its
+    /// right half.
+    fn halves(projection: Projection) -> Decal {
+        let mut image = Canvas::new(2, 1);
+        image.write_pixel(0, 0, Color::new(1, 0, 0));
+        image.write_pixel(1, 0, Color::new(0, 0, 1));
+
+        Decal::new(
+            Texture::new(image).with_filter(TextureFilter::Nearest),
+            projection,
+            Solid::new(background()),
+        )
+    }
+
+    #[test]
+    fn pattern_at_orthographic() {
+        let decal = halves(Projection::Orthographic);
+        let sample = |x, y, z| decal.pattern_at(&Tuple::new_point(x, y, z));
+
+        // The texture is twice as wide as it is tall, so it spans from -0.5
+        // to 0.5 vertically.
+        assert_eq!(sample(0.5, 0.0, -1.0), Color::new(1, 0, 0));
+        assert_eq!(sample(-0.5, 0.25, -10.0), Color::new(0, 0, 1));
+        assert_eq!(sample(0.0, 0.75, -1.0), background());
+        assert_eq!(sample(1.5, 0.0, -1.0), background());
+        assert_eq!(sample(0.5, 0.0, 1.0), background());
+    }
+
+    #[test]
+    fn pattern_at_perspective() {
+        let decal = halves(Projection::Perspective {
+            field_of_view: FRAC_PI_2,
+        });
+        let sample = |x, y, z| decal.pattern_at(&Tuple::new_point(x, y, z));
+
+        assert_eq!(sample(1.0, 0.0, -4.0), Color::new(1, 0, 0));
+        assert_eq!(sample(-3.0, 0.0, -4.0), Color::new(0, 0, 1));
+        assert_eq!(sample(2.0, 0.0, -1.0), background());
+    }
+
+    #[test]
+    fn pattern_at_shape_aimed_projector() {
+        let plane = Plane::default();
+        let decal = halves(Projection::Orthographic).aimed_at(
+            &Tuple::new_point(0, 5, 0),
+            &Tuple::new_point(0, 0, 0),
+            &Tuple::new_vector(0, 0, -1),
+        );
+
+        // Looking down with the negative z-axis up, the projector's left is
+        // along the positive x-axis.
+        let left = decal.pattern_at_shape(&plane, &Tuple::new_point(0.5, 0.0, 0.0));
+        let right = decal.pattern_at_shape(&plane, &Tuple::new_point(-0.5, 0.0, 0.0));
+
+        assert_eq!(left, Color::new(1, 0, 0));
+        assert_eq!(right, Color::new(0, 0, 1));
+    }
+
+    #[test]
+    fn pattern_at_shape_skips_surfaces_facing_away() {
+        let sphere = Sphere::default();
+        let decal = halves(Projection::Orthographic).aimed_at(
+            &Tuple::new_point(0, 5, 0),
+            &Tuple::new_point(0, 0, 0),
+            &Tuple::new_vector(0, 0, -1),
+        );
+
+        let top = decal.pattern_at_shape(&sphere, &Tuple::new_point(0.6, 0.8, 0.0));
+        let bottom = decal.pattern_at_shape(&sphere, &Tuple::new_point(0.6, -0.8, 0.0));
+
+        assert_eq!(top, Color::new(1, 0, 0));
+        assert_eq!(bottom, background());
+    }
+
+    #[test]
+    fn pattern_at_shape_moves_with_object() {
+        let mut plane = Plane::default();
+        plane.set_transform(Matrix::translation(10, 0, 0));
+        let decal = halves(Projection::Orthographic).aimed_at(
+            &Tuple::new_point(0, 5, 0),
+            &Tuple::new_point(0, 0, 0),
+            &Tuple::new_vector(0, 0, -1),
+        );
+
+        let c = decal.pattern_at_shape(&plane, &Tuple::new_point(10.5, 0.0, 0.0));
+
+        assert_eq!(c, Color::new(1, 0, 0));
+    }
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    Color,
+};
+
+use super::{Pattern, Solid};
+
+/// A pattern that linearly blends between two colors along the x-axis.
+///
+/// The blend repeats every unit, so the pattern jumps back to the first color
+/// at each integer x value.
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    transform: Matrix,
+}
+
+impl Gradient {
+    /// Create a new gradient pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The color at the start of the gradient.
+    /// * `b` - The color the gradient approaches at the end of each unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, patterns::{Gradient, Pattern}, Color};
+    /// let pattern = Gradient::new(Color::new(1, 1, 1), Color::new(0, 0, 0));
+    ///
+    /// assert_eq!(
+    ///     pattern.pattern_at(&Tuple::new_point(0.5, 0.0, 0.0)),
+    ///     Color::new(0.5, 0.5, 0.5)
+    /// );
+    /// ```
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Create a new gradient pattern that blends between two other patterns.
+    ///
+    /// The sub-patterns are sampled in this pattern's space, so their
+    /// transforms are relative to this pattern's transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The pattern sampled at the start of the gradient.
+    /// * `b` - The pattern the gradient approaches at the end of each unit.
+    pub fn from_patterns<A, B>(a: A, b: B) -> Self
+    where
+        A: Pattern + 'static,
+        B: Pattern + 'static,
+    {
+        Self {
+            a: Arc::new(a),
+            b: Arc::new(b),
+            transform: Matrix::identity_4(),
+        }
+    }
+}
+
+impl Pattern for Gradient {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        let fraction = point.x() - point.x().floor();
+
+        let a = self.a.sample(point);
+        let b = self.b.sample(point);
+
+        a + (b - a) * fraction
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pattern_at_interpolates() {
+        let pattern = Gradient::new(Color::new(1, 1, 1), Color::new(0, 0, 0));
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0, 0, 0)),
+            Color::new(1, 1, 1)
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.25, 0.0, 0.0)),
+            Color::new(0.75, 0.75, 0.75)
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.75, 0.0, 0.0)),
+            Color::new(0.25, 0.25, 0.25)
+        );
+    }
+
+    #[test]
+    fn pattern_at_repeats() {
+        let pattern = Gradient::new(Color::new(1, 1, 1), Color::new(0, 0, 0));
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(1.25, 0.0, 0.0)),
+            Color::new(0.75, 0.75, 0.75)
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(-0.25, 0.0, 0.0)),
+            Color::new(0.25, 0.25, 0.25)
+        );
+    }
+}
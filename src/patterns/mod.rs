@@ -0,0 +1,37 @@
+//! Patterns vary the color of a material across the surface of an object.
+//!
+//! Most patterns can be built from other patterns, so they can be nested (such
+//! as a checker pattern whose squares are striped), blended together, or
+//! perturbed with noise. Image textures can be applied using a UV mapping, or
+//! projected onto objects without one using triplanar mapping or decals.
+//!
+//! Normal maps use patterns and textures to vary the normal that a surface is
+//! shaded with instead of its color.
+
+mod blend;
+mod checker;
+mod decal;
+mod gradient;
+mod noise_pattern;
+mod normal_map;
+mod pattern;
+mod perturbed;
+mod ring;
+mod solid;
+mod stripe;
+mod texture;
+mod triplanar;
+
+pub use blend::Blend;
+pub use checker::Checker;
+pub use decal::{Decal, Projection};
+pub use gradient::Gradient;
+pub use noise_pattern::NoisePattern;
+pub use normal_map::{BumpMap, NormalMap, NormalTexture};
+pub use pattern::Pattern;
+pub use perturbed::Perturbed;
+pub use ring::Ring;
+pub use solid::Solid;
+pub use stripe::Stripe;
+pub use texture::{CubeFace, CubeMap, Texture, TextureFilter, TextureMap, UvMapping};
+pub use triplanar::Triplanar;
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    noise::Noise,
+    Color,
+};
+
+use super::{Pattern, Solid};
+
+/// A pattern that blends between two patterns based on the value of a noise
+/// source.
+///
+/// Combined with fractal noise, this gives the look of clouds, smoke, or
+/// stone.
+#[derive(Clone, Debug)]
+pub struct NoisePattern {
+    noise: Arc<dyn Noise>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    low: f64,
+    high: f64,
+    transform: Matrix,
+}
+
+impl NoisePattern {
+    /// Create a new noise pattern that blends between two colors.
+    ///
+    /// The noise is assumed to fall within `[-1, 1]`, which is the case for
+    /// gradient noise. Use [`with_range`][Self::with_range] for noise with a
+    /// different range.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise that controls the blend.
+    /// * `a` - The color where the noise is at its lowest.
+    /// * `b` - The color where the noise is at its highest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{noise::{Fractal, Perlin}, patterns::NoisePattern, Color, Material};
+    /// let clouds = NoisePattern::new(
+    ///     Fractal::fbm(Perlin::new(1)),
+    ///     Color::new(0.3, 0.5, 1.0),
+    ///     Color::new(1, 1, 1),
+    /// );
+    /// let m = Material::default().with_pattern(clouds);
+    /// ```
+    pub fn new<N: Noise + 'static>(noise: N, a: Color, b: Color) -> Self {
+        Self::from_patterns(noise, Solid::new(a), Solid::new(b))
+    }
+
+    /// Create a new noise pattern that blends between two other patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise that controls the blend.
+    /// * `a` - The pattern used where the noise is at its lowest.
+    /// * `b` - The pattern used where the noise is at its highest.
+    pub fn from_patterns<N, A, B>(noise: N, a: A, b: B) -> Self
+    where
+        N: Noise + 'static,
+        A: Pattern + 'static,
+        B: Pattern + 'static,
+    {
+        Self {
+            noise: Arc::new(noise),
+            a: Arc::new(a),
+            b: Arc::new(b),
+            low: -1.0,
+            high: 1.0,
+            transform: Matrix::identity_4(),
+        }
+    }
+
+    /// Create a copy of the pattern that expects noise within a different
+    /// range.
+    ///
+    /// Noise values at or below `low` give the first pattern, and values at or
+    /// above `high` give the second.
+    ///
+    /// # Arguments
+    ///
+    /// * `low` - The noise value mapped to the first pattern.
+    /// * `high` - The noise value mapped to the second pattern.
+    pub fn with_range(&self, low: f64, high: f64) -> Self {
+        Self {
+            low,
+            high,
+            ..self.clone()
+        }
+    }
+
+    pub fn range(&self) -> (f64, f64) {
+        (self.low, self.high)
+    }
+}
+
+impl Pattern for NoisePattern {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        let n = self.noise.noise_at(point);
+        let fraction = ((n - self.low) / (self.high - self.low)).clamp(0.0, 1.0);
+
+        let a = self.a.sample(point);
+        let b = self.b.sample(point);
+
+        a + (b - a) * fraction
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::noise::{Perlin, Worley};
+
+    /// Noise that is always the same value.
+    #[derive(Debug)]
+    struct Constant(f64);
+
+    impl Noise for Constant {
+        fn noise_at(&self, _point: &Tuple) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn pattern_at_maps_noise_to_blend() {
+        let white = Color::new(1, 1, 1);
+        let black = Color::new(0, 0, 0);
+        let point = Tuple::new_point(0, 0, 0);
+
+        let low = NoisePattern::new(Constant(-1.0), black, white);
+        let middle = NoisePattern::new(Constant(0.0), black, white);
+        let high = NoisePattern::new(Constant(2.0), black, white);
+
+        assert_eq!(low.pattern_at(&point), black);
+        assert_eq!(middle.pattern_at(&point), Color::new(0.5, 0.5, 0.5));
+        assert_eq!(high.pattern_at(&point), white);
+    }
+
+    #[test]
+    fn pattern_at_with_range() {
+        let pattern = NoisePattern::new(Constant(0.25), Color::new(0, 0, 0), Color::new(1, 1, 1))
+            .with_range(0.0, 1.0);
+
+        let c = pattern.pattern_at(&Tuple::new_point(0, 0, 0));
+
+        assert_eq!(c, Color::new(0.25, 0.25, 0.25));
+    }
+
+    #[test]
+    fn pattern_at_is_deterministic() {
+        let a = NoisePattern::new(Perlin::new(4), Color::new(0, 0, 0), Color::new(1, 1, 1));
+        let b = NoisePattern::new(Perlin::new(4), Color::new(0, 0, 0), Color::new(1, 1, 1));
+        let point = Tuple::new_point(0.4, 1.3, 2.2);
+
+        assert_eq!(a.pattern_at(&point), b.pattern_at(&point));
+    }
+
+    #[test]
+    fn pattern_at_worley_cells() {
+        let pattern = NoisePattern::new(Worley::new(1), Color::new(0, 0, 0), Color::new(1, 1, 1))
+            .with_range(0.0, 1.0);
+
+        let c = pattern.pattern_at(&Tuple::new_point(0.5, 0.5, 0.5));
+
+        assert!(c.red() >= 0.0 && c.red() <= 1.0);
+        assert_eq!(c.red(), c.green());
+    }
+}
@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    noise::Noise,
+    objects::{Shape, TangentFrame},
+    Color,
+};
+
+use super::{pattern::to_pattern_point, NoisePattern, Pattern, Texture, UvMapping};
+
+/// The distance used to estimate the slope of a height field.
+const SLOPE_STEP: f64 = 1e-4;
+
+/// A normal map tilts the normal used to shade a surface, which adds detail
+/// like bricks or ripples without adding geometry.
+///
+/// Only the shading is affected. Shadows and refraction still use the actual
+/// surface of the object.
+pub trait NormalMap: std::fmt::Debug + Send + Sync {
+    /// Find the shading normal at a point on an object.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object the normal map is applied to.
+    /// * `world_point` - The point on the object's surface, in world space.
+    /// * `frame` - The object's tangent frame at the point, in world space.
+    fn normal_at_shape(
+        &self,
+        object: &dyn Shape,
+        world_point: &Tuple,
+        frame: &TangentFrame,
+    ) -> Tuple;
+}
+
+/// A normal map that treats a pattern as a height field, tilting the normal
+/// away from the direction that the surface rises in.
+///
+/// The height at each point is the average of the pattern's red, green, and
+/// blue components, so grayscale patterns and images work best.
+#[derive(Clone, Debug)]
+pub struct BumpMap {
+    height: Arc<dyn Pattern>,
+    strength: f64,
+}
+
+impl BumpMap {
+    /// Create a bump map from a pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - The pattern giving the height of the surface.
+    /// * `strength` - How much the normal is tilted for each unit that the
+    ///   height changes per unit along the surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{patterns::{BumpMap, Stripe}, Color, Material};
+    /// let ridges = BumpMap::new(Stripe::new(Color::new(0, 0, 0), Color::new(1, 1, 1)), 0.5);
+    ///
+    /// let m = Material::default().with_normal_map(ridges);
+    /// ```
+    pub fn new<P: Pattern + 'static>(height: P, strength: f64) -> Self {
+        Self {
+            height: Arc::new(height),
+            strength,
+        }
+    }
+
+    /// Create a bump map from a noise source.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise giving the height of the surface. Like
+    ///   [`NoisePattern`], the noise is assumed to fall within `[-1, 1]`.
+    /// * `strength` - How much the normal is tilted for each unit that the
+    ///   noise changes per unit along the surface.
+    pub fn from_noise<N: Noise + 'static>(noise: N, strength: f64) -> Self {
+        Self::new(
+            NoisePattern::new(noise, Color::new(0, 0, 0), Color::new(1, 1, 1)),
+            strength,
+        )
+    }
+
+    pub fn strength(&self) -> f64 {
+        self.strength
+    }
+
+    fn height_at(&self, object: &dyn Shape, world_point: &Tuple) -> f64 {
+        let c = self.height.pattern_at_shape(object, world_point);
+
+        (c.red() + c.green() + c.blue()) / 3.0
+    }
+}
+
+impl NormalMap for BumpMap {
+    fn normal_at_shape(
+        &self,
+        object: &dyn Shape,
+        world_point: &Tuple,
+        frame: &TangentFrame,
+    ) -> Tuple {
+        let slope = |direction: Tuple| {
+            let offset = direction * SLOPE_STEP;
+            let ahead = self.height_at(object, &(*world_point + offset));
+            let behind = self.height_at(object, &(*world_point - offset));
+
+            (ahead - behind) / (2.0 * SLOPE_STEP)
+        };
+
+        let rise =
+            frame.tangent() * slope(frame.tangent()) + frame.bitangent() * slope(frame.bitangent());
+
+        (frame.normal() - rise * self.strength).normalized()
+    }
+}
+
+/// A normal map read from an image, with the normals stored in tangent space.
+///
+/// The red, green, and blue channels give the x, y, and z components of the
+/// normal, scaled from `[-1, 1]` to `[0, 1]`. The x-axis points along the
+/// surface's tangent, the y-axis along its bitangent, and the z-axis along the
+/// surface's normal, so the flat color `(0.5, 0.5, 1)` leaves the normal
+/// unchanged. This matches the normal maps exported by most other tools.
+#[derive(Clone, Debug)]
+pub struct NormalTexture {
+    texture: Texture,
+    mapping: UvMapping,
+    transform: Matrix,
+    inverse_transform: Matrix,
+}
+
+impl NormalTexture {
+    /// Create a normal map from a texture.
+    ///
+    /// # Arguments
+    ///
+    /// * `texture` - The texture containing the normals.
+    /// * `mapping` - How points on the object are converted into UV
+    ///   coordinates. This should match the mapping of any color texture
+    ///   that the normal map was made for.
+    pub fn new(texture: Texture, mapping: UvMapping) -> Self {
+        Self {
+            texture,
+            mapping,
+            transform: Matrix::identity_4(),
+            inverse_transform: Matrix::identity_4(),
+        }
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn mapping(&self) -> UvMapping {
+        self.mapping
+    }
+
+    /// Retrieve the transform applied to the texture mapping.
+    pub fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    /// Set the transform applied to the texture mapping, which works the same
+    /// way as a pattern's transform.
+    pub fn set_transform(&mut self, transform: Matrix) {
+        self.inverse_transform = transform.inverted();
+        self.transform = transform;
+    }
+}
+
+impl NormalMap for NormalTexture {
+    fn normal_at_shape(
+        &self,
+        object: &dyn Shape,
+        world_point: &Tuple,
+        frame: &TangentFrame,
+    ) -> Tuple {
+        let texture_point = to_pattern_point(object, &self.inverse_transform, world_point);
+        let (u, v) = self.mapping.map(&texture_point);
+
+        let c = self.texture.color_at_uv(u, v);
+        let tangent_normal = Tuple::new_vector(
+            c.red() * 2.0 - 1.0,
+            c.green() * 2.0 - 1.0,
+            c.blue() * 2.0 - 1.0,
+        );
+
+        frame.to_frame_space(&tangent_normal).normalized()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{
+        canvas::Canvas,
+        objects::{Plane, Sphere},
+        patterns::{Gradient, Solid, TextureFilter},
+    };
+
+    fn flat_frame() -> TangentFrame {
+        TangentFrame::new(Tuple::new_vector(1, 0, 0), Tuple::new_vector(0, 1, 0))
+    }
+
+    fn solid_texture(color: Color) -> Texture {
+        let mut image = Canvas::new(2, 2);
+        for x in 0..2 {
+            for y in 0..2 {
+                image.write_pixel(x, y, color);
+            }
+        }
+
+        Texture::new(image)
+    }
+
+    #[test]
+    fn bump_map_flat_height_keeps_normal() {
+        let plane = Plane::default();
+        let bump = BumpMap::new(Solid::new(Color::new(0.5, 0.5, 0.5)), 1.0);
+
+        let n = bump.normal_at_shape(&plane, &Tuple::new_point(0.3, 0.0, 0.7), &flat_frame());
+
+        assert_eq!(n, Tuple::new_vector(0, 1, 0));
+    }
+
+    #[test]
+    fn bump_map_tilts_away_from_slope() {
+        // A gradient rises by one unit along the x-axis.
+        let plane = Plane::default();
+        let bump = BumpMap::new(Gradient::new(Color::new(0, 0, 0), Color::new(1, 1, 1)), 1.0);
+
+        let n = bump.normal_at_shape(&plane, &Tuple::new_point(0.5, 0.0, 0.5), &flat_frame());
+
+        assert_eq!(n, Tuple::new_vector(-1, 1, 0).normalized());
+    }
+
+    #[test]
+    fn bump_map_strength_scales_tilt() {
+        let plane = Plane::default();
+        let bump = BumpMap::new(Gradient::new(Color::new(0, 0, 0), Color::new(1, 1, 1)), 0.0);
+
+        let n = bump.normal_at_shape(&plane, &Tuple::new_point(0.5, 0.0, 0.5), &flat_frame());
+
+        assert_eq!(n, Tuple::new_vector(0, 1, 0));
+    }
+
+    #[test]
+    fn normal_texture_flat_color_keeps_normal() {
+        let sphere = Sphere::default();
+        let map = NormalTexture::new(
+            solid_texture(Color::new(0.5, 0.5, 1.0)),
+            UvMapping::Spherical,
+        );
+        let point = Tuple::new_point(0, 0, -1);
+
+        let n = map.normal_at_shape(&sphere, &point, &sphere.tangent_frame_at(&point));
+
+        assert_eq!(n, Tuple::new_vector(0, 0, -1));
+    }
+
+    #[test]
+    fn normal_texture_uses_transform() {
+        let plane = Plane::default();
+        // The left half of the texture tilts the normal, and the right half
+        // leaves it alone.
+        let mut image = Canvas::new(2, 1);
+        image.write_pixel(0, 0, Color::new(1.0, 0.5, 1.0));
+        image.write_pixel(1, 0, Color::new(0.5, 0.5, 1.0));
+        let mut map = NormalTexture::new(
+            Texture::new(image).with_filter(TextureFilter::Nearest),
+            UvMapping::Planar,
+        );
+        let point = Tuple::new_point(0.25, 0.0, 0.25);
+        let frame = plane.tangent_frame_at(&point);
+
+        let before = map.normal_at_shape(&plane, &point, &frame);
+        map.set_transform(Matrix::translation(-0.5, 0.0, 0.0));
+        let after = map.normal_at_shape(&plane, &point, &frame);
+
+        assert_eq!(before, Tuple::new_vector(1, 1, 0).normalized());
+        assert_eq!(after, Tuple::new_vector(0, 1, 0));
+    }
+
+    #[test]
+    fn normal_texture_uses_tangent_space() {
+        let plane = Plane::default();
+        // Tilted halfway towards the tangent and halfway towards the normal.
+        let map = NormalTexture::new(solid_texture(Color::new(1.0, 0.5, 1.0)), UvMapping::Planar);
+        let point = Tuple::new_point(0.25, 0.0, 0.25);
+
+        let n = map.normal_at_shape(&plane, &point, &plane.tangent_frame_at(&point));
+
+        assert_eq!(n, Tuple::new_vector(1, 1, 0).normalized());
+    }
+}
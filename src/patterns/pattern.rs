@@ -0,0 +1,211 @@
+use crate::{
+    linear::{Matrix, Tuple},
+    objects::Shape,
+    Color,
+};
+
+/// A pattern assigns a color to every point in space.
+///
+/// Patterns have their own transform, which is applied on top of the transform
+/// of the object the pattern is attached to. This allows a pattern to be
+/// scaled or rotated independently of the object.
+pub trait Pattern: std::fmt::Debug + Send + Sync {
+    /// Get the color of the pattern at a point in pattern space.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to sample the pattern at. It is treated as being
+    ///   in pattern space (as opposed to object or world space).
+    fn pattern_at(&self, point: &Tuple) -> Color;
+
+    /// Retrieve the pattern's transform.
+    fn transform(&self) -> &Matrix;
+
+    /// Set the pattern's transform.
+    fn set_transform(&mut self, transform: Matrix);
+
+    /// Get the color of the pattern at a point in the space of whatever the
+    /// pattern is attached to.
+    ///
+    /// The point is converted into pattern space using the pattern's transform
+    /// before being passed to [`pattern_at`][Self::pattern_at]. Patterns that
+    /// are built from other patterns use this to sample them, so that each
+    /// sub-pattern's transform is relative to its parent pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to sample the pattern at, in the parent's space.
+    fn sample(&self, point: &Tuple) -> Color {
+        let pattern_point = &self.transform().inverted() * *point;
+
+        self.pattern_at(&pattern_point)
+    }
+
+    /// Get the color of the pattern at a point on an object.
+    ///
+    /// By default, the point is converted from world space into object space
+    /// using the object's transform, and then sampled using
+    /// [`sample`][Self::sample].
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object the pattern is applied to.
+    /// * `world_point` - The point in world space to sample the pattern at.
+    fn pattern_at_shape(&self, object: &dyn Shape, world_point: &Tuple) -> Color {
+        let object_point = &object.transform().inverted() * *world_point;
+
+        self.sample(&object_point)
+    }
+}
+
+/// Convert a point on an object from world space into a pattern's space.
+///
+/// This is the same as [`to_pattern_space`], for patterns and maps that don't
+/// need to know which way the surface faces. Finding the normal can be
+/// expensive, such as for meshes, so it's skipped.
+///
+/// # Arguments
+///
+/// * `object` - The object the pattern is applied to.
+/// * `inverse_transform` - The inverse of the pattern's transform.
+/// * `world_point` - The point in world space to sample the pattern at.
+pub(crate) fn to_pattern_point(
+    object: &dyn Shape,
+    inverse_transform: &Matrix,
+    world_point: &Tuple,
+) -> Tuple {
+    let object_point = &object.transform().inverted() * *world_point;
+
+    inverse_transform * object_point
+}
+
+/// Convert a point on an object from world space into a pattern's space,
+/// along with the object's normal at that point.
+///
+/// Patterns that depend on which way the surface faces use this to implement
+/// [`pattern_at_shape`][Pattern::pattern_at_shape]. The pattern's inverse
+/// transform is passed in so that patterns can compute it once up front,
+/// rather than on every sample.
+///
+/// # Arguments
+///
+/// * `object` - The object the pattern is applied to.
+/// * `transform` - The pattern's transform.
+/// * `inverse_transform` - The inverse of the pattern's transform.
+/// * `world_point` - The point in world space to sample the pattern at.
+pub(crate) fn to_pattern_space(
+    object: &dyn Shape,
+    transform: &Matrix,
+    inverse_transform: &Matrix,
+    world_point: &Tuple,
+) -> (Tuple, Tuple) {
+    let object_point = &object.transform().inverted() * *world_point;
+    let pattern_point = inverse_transform * object_point;
+
+    // Normals are transformed by the transpose of the inverse, so going from
+    // object space back to pattern space uses the transpose.
+    let normal = &transform.transposed() * object.normal_at_local(&object_point);
+    let normal = Tuple::new_vector(normal.x(), normal.y(), normal.z()).normalized();
+
+    (pattern_point, normal)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::objects::Sphere;
+
+    /// A pattern that returns the point it was sampled at as a color, which
+    /// lets us check the transforms applied by the default implementation.
+    #[derive(Debug)]
+    struct TestPattern {
+        transform: Matrix,
+    }
+
+    impl Default for TestPattern {
+        fn default() -> Self {
+            Self {
+                transform: Matrix::identity_4(),
+            }
+        }
+    }
+
+    impl Pattern for TestPattern {
+        fn pattern_at(&self, point: &Tuple) -> Color {
+            Color::new(point.x(), point.y(), point.z())
+        }
+
+        fn transform(&self) -> &Matrix {
+            &self.transform
+        }
+
+        fn set_transform(&mut self, transform: Matrix) {
+            self.transform = transform;
+        }
+    }
+
+    #[test]
+    fn pattern_at_shape_object_transform() {
+        let mut shape = Sphere::default();
+        shape.set_transform(Matrix::scaling(2, 2, 2));
+        let pattern = TestPattern::default();
+
+        let c = pattern.pattern_at_shape(&shape, &Tuple::new_point(2, 3, 4));
+
+        assert_eq!(c, Color::new(1.0, 1.5, 2.0));
+    }
+
+    #[test]
+    fn pattern_at_shape_pattern_transform() {
+        let shape = Sphere::default();
+        let mut pattern = TestPattern::default();
+        pattern.set_transform(Matrix::scaling(2, 2, 2));
+
+        let c = pattern.pattern_at_shape(&shape, &Tuple::new_point(2, 3, 4));
+
+        assert_eq!(c, Color::new(1.0, 1.5, 2.0));
+    }
+
+    #[test]
+    fn pattern_at_shape_object_and_pattern_transform() {
+        let mut shape = Sphere::default();
+        shape.set_transform(Matrix::scaling(2, 2, 2));
+        let mut pattern = TestPattern::default();
+        pattern.set_transform(Matrix::translation(0.5, 1.0, 1.5));
+
+        let c = pattern.pattern_at_shape(&shape, &Tuple::new_point(2.5, 3.0, 3.5));
+
+        assert_eq!(c, Color::new(0.75, 0.5, 0.25));
+    }
+
+    #[test]
+    fn to_pattern_space_object_and_pattern_transform() {
+        let mut shape = Sphere::default();
+        shape.set_transform(Matrix::scaling(2, 2, 2));
+        let transform = Matrix::scaling(1, 4, 1);
+
+        let (point, normal) = to_pattern_space(
+            &shape,
+            &transform,
+            &transform.inverted(),
+            &Tuple::new_point(0, 2, 0),
+        );
+
+        assert_eq!(point, Tuple::new_point(0.0, 0.25, 0.0));
+        assert_eq!(normal, Tuple::new_vector(0, 1, 0));
+    }
+
+    #[test]
+    fn to_pattern_space_skewed_normal() {
+        let shape = Sphere::default();
+        let transform = Matrix::scaling(2, 1, 1);
+        let world_point = Tuple::new_point(2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0, 0.0);
+
+        let (_, normal) = to_pattern_space(&shape, &transform, &transform.inverted(), &world_point);
+
+        // Stretching the pattern along x squashes the normal towards the
+        // x-axis.
+        assert_eq!(normal, Tuple::new_vector(2, 1, 0).normalized());
+    }
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    noise::{Noise, Perlin},
+    Color,
+};
+
+use super::Pattern;
+
+/// A pattern that jitters the point another pattern is sampled at using 3D
+/// noise.
+///
+/// Perturbing regular patterns like stripes or rings gives them a natural
+/// look, such as the veins in marble or the grain in wood.
+#[derive(Clone, Debug)]
+pub struct Perturbed {
+    pattern: Arc<dyn Pattern>,
+    scale: f64,
+    noise: Arc<dyn Noise>,
+    transform: Matrix,
+}
+
+impl Perturbed {
+    /// Create a new perturbed pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to perturb.
+    /// * `scale` - The maximum distance (roughly) that a point is moved before
+    ///   sampling the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{patterns::{Perturbed, Stripe}, Color, Material};
+    /// let marble = Perturbed::new(
+    ///     Stripe::new(Color::new(1, 1, 1), Color::new(0.2, 0.2, 0.25)),
+    ///     0.3,
+    /// );
+    /// let m = Material::default().with_pattern(marble);
+    /// ```
+    pub fn new<P: Pattern + 'static>(pattern: P, scale: f64) -> Self {
+        Self {
+            pattern: Arc::new(pattern),
+            scale,
+            noise: Arc::new(Perlin::default()),
+            transform: Matrix::identity_4(),
+        }
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Create a copy of the pattern that is perturbed using a different noise
+    /// source.
+    ///
+    /// By default, the pattern is perturbed using [`Perlin`] noise.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - The noise used to offset the sampled points.
+    pub fn with_noise<N: Noise + 'static>(&self, noise: N) -> Self {
+        Self {
+            noise: Arc::new(noise),
+            ..self.clone()
+        }
+    }
+}
+
+impl Pattern for Perturbed {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        // Offset the samples for each axis so the jitter isn't the same in
+        // every direction.
+        let offset = Tuple::new_vector(
+            self.noise.noise_at(point),
+            self.noise
+                .noise_at(&(*point + Tuple::new_vector(31.4, 15.9, 26.5))),
+            self.noise
+                .noise_at(&(*point - Tuple::new_vector(27.1, 82.8, 18.3))),
+        ) * self.scale;
+
+        self.pattern.sample(&(*point + offset))
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{noise::Worley, patterns::Stripe};
+
+    fn stripes() -> Stripe {
+        Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0))
+    }
+
+    #[test]
+    fn pattern_at_no_scale_matches_original() {
+        let original = stripes();
+        let pattern = Perturbed::new(stripes(), 0.0);
+
+        for i in 0..20 {
+            let point = Tuple::new_point(i as f64 * 0.23, 0.4, 0.7);
+
+            assert_eq!(pattern.pattern_at(&point), original.pattern_at(&point));
+        }
+    }
+
+    #[test]
+    fn pattern_at_moves_stripe_edges() {
+        let original = stripes();
+        let pattern = Perturbed::new(stripes(), 1.0);
+
+        let differs = (0..100).any(|i| {
+            let point = Tuple::new_point(i as f64 * 0.097, 0.4, 0.7);
+
+            pattern.pattern_at(&point) != original.pattern_at(&point)
+        });
+
+        assert!(differs);
+    }
+
+    #[test]
+    fn pattern_at_with_noise() {
+        let original = stripes();
+        let pattern = Perturbed::new(stripes(), 1.0).with_noise(Worley::new(3));
+
+        let differs = (0..100).any(|i| {
+            let point = Tuple::new_point(i as f64 * 0.097, 0.4, 0.7);
+
+            pattern.pattern_at(&point) != original.pattern_at(&point)
+        });
+
+        assert!(differs);
+    }
+
+    #[test]
+    fn pattern_at_is_deterministic() {
+        let a = Perturbed::new(stripes(), 0.5);
+        let b = Perturbed::new(stripes(), 0.5);
+        let point = Tuple::new_point(0.9, 0.3, 1.2);
+
+        assert_eq!(a.pattern_at(&point), b.pattern_at(&point));
+    }
+}
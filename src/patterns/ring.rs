@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    Color,
+};
+
+use super::{Pattern, Solid};
+
+/// A pattern of concentric rings around the y-axis that alternate between two
+/// colors.
+#[derive(Clone, Debug)]
+pub struct Ring {
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    transform: Matrix,
+}
+
+impl Ring {
+    /// Create a new ring pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The color of the innermost ring and every other ring after it.
+    /// * `b` - The color of the remaining rings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, patterns::{Pattern, Ring}, Color};
+    /// let white = Color::new(1, 1, 1);
+    /// let black = Color::new(0, 0, 0);
+    /// let pattern = Ring::new(white, black);
+    ///
+    /// assert_eq!(pattern.pattern_at(&Tuple::new_point(0.5, 0.0, 0.0)), white);
+    /// assert_eq!(pattern.pattern_at(&Tuple::new_point(0.0, 0.0, 1.5)), black);
+    /// ```
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Create a new ring pattern that alternates between two other patterns.
+    ///
+    /// The sub-patterns are sampled in this pattern's space, so their
+    /// transforms are relative to this pattern's transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The pattern used for the innermost ring and every other ring
+    ///   after it.
+    /// * `b` - The pattern used for the remaining rings.
+    pub fn from_patterns<A, B>(a: A, b: B) -> Self
+    where
+        A: Pattern + 'static,
+        B: Pattern + 'static,
+    {
+        Self {
+            a: Arc::new(a),
+            b: Arc::new(b),
+            transform: Matrix::identity_4(),
+        }
+    }
+}
+
+impl Pattern for Ring {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        let distance = (point.x().powi(2) + point.z().powi(2)).sqrt();
+
+        if (distance.floor() as i64) % 2 == 0 {
+            self.a.sample(point)
+        } else {
+            self.b.sample(point)
+        }
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pattern_at_extends_in_x_and_z() {
+        let white = Color::new(1, 1, 1);
+        let black = Color::new(0, 0, 0);
+        let pattern = Ring::new(white, black);
+
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0, 0, 0)), white);
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(1, 0, 0)), black);
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0, 0, 1)), black);
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.708, 0.0, 0.708)),
+            black
+        );
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(2, 5, 0)), white);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    linear::{Matrix, Tuple},
+    Color,
+};
+
+use super::Pattern;
+
+/// A pattern that is the same color everywhere.
+///
+/// This is mostly useful as an input to patterns that are built from other
+/// patterns.
+#[derive(Clone, Debug)]
+pub struct Solid {
+    color: Color,
+    transform: Matrix,
+}
+
+impl Solid {
+    /// Create a new solid pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, patterns::{Pattern, Solid}, Color};
+    /// let pattern = Solid::new(Color::new(1, 0, 0));
+    ///
+    /// assert_eq!(pattern.pattern_at(&Tuple::new_point(1, 2, 3)), Color::new(1, 0, 0));
+    /// ```
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            transform: Matrix::identity_4(),
+        }
+    }
+}
+
+impl Pattern for Solid {
+    fn pattern_at(&self, _point: &Tuple) -> Color {
+        self.color
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use crate::{
+    linear::{Matrix, Tuple},
+    Color,
+};
+
+use super::{Pattern, Solid};
+
+/// A pattern that alternates between two colors every unit along the x-axis.
+#[derive(Clone, Debug)]
+pub struct Stripe {
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    transform: Matrix,
+}
+
+impl Stripe {
+    /// Create a new stripe pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The color of the stripes starting at even x values.
+    /// * `b` - The color of the stripes starting at odd x values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, patterns::{Pattern, Stripe}, Color};
+    /// let white = Color::new(1, 1, 1);
+    /// let black = Color::new(0, 0, 0);
+    /// let pattern = Stripe::new(white, black);
+    ///
+    /// assert_eq!(pattern.pattern_at(&Tuple::new_point(0.5, 0.0, 0.0)), white);
+    /// assert_eq!(pattern.pattern_at(&Tuple::new_point(1.5, 0.0, 0.0)), black);
+    /// ```
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Create a new stripe pattern that alternates between two other patterns.
+    ///
+    /// The sub-patterns are sampled in this pattern's space, so their
+    /// transforms are relative to this pattern's transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The pattern used for the stripes starting at even x values.
+    /// * `b` - The pattern used for the stripes starting at odd x values.
+    pub fn from_patterns<A, B>(a: A, b: B) -> Self
+    where
+        A: Pattern + 'static,
+        B: Pattern + 'static,
+    {
+        Self {
+            a: Arc::new(a),
+            b: Arc::new(b),
+            transform: Matrix::identity_4(),
+        }
+    }
+}
+
+impl Pattern for Stripe {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        if (point.x().floor() as i64).rem_euclid(2) == 0 {
+            self.a.sample(point)
+        } else {
+            self.b.sample(point)
+        }
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn white() -> Color {
+        Color::new(1, 1, 1)
+    }
+
+    fn black() -> Color {
+        Color::new(0, 0, 0)
+    }
+
+    #[test]
+    fn pattern_at_constant_in_y_and_z() {
+        let pattern = Stripe::new(white(), black());
+
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0, 1, 0)), white());
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0, 2, 0)), white());
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0, 0, 1)), white());
+        assert_eq!(pattern.pattern_at(&Tuple::new_point(0, 0, 2)), white());
+    }
+
+    #[test]
+    fn pattern_at_alternates_in_x() {
+        let pattern = Stripe::new(white(), black());
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.0, 0.0, 0.0)),
+            white()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.9, 0.0, 0.0)),
+            white()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(1.0, 0.0, 0.0)),
+            black()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(-0.1, 0.0, 0.0)),
+            black()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(-1.0, 0.0, 0.0)),
+            black()
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(-1.1, 0.0, 0.0)),
+            white()
+        );
+    }
+}
@@ -0,0 +1,550 @@
+use std::{f64::consts::PI, sync::Arc};
+
+use crate::{
+    canvas::Canvas,
+    linear::{Matrix, Tuple},
+    Color,
+};
+
+use super::Pattern;
+
+/// How a texture is sampled between the centers of its pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextureFilter {
+    /// Use the color of the closest pixel. This keeps hard edges, which suits
+    /// pixel art and labels viewed up close.
+    Nearest,
+
+    /// Blend the colors of the four closest pixels, which smooths out
+    /// textures that are magnified.
+    Bilinear,
+}
+
+/// An image that can be sampled using UV coordinates.
+///
+/// UV coordinates range from 0 to 1, with `(0, 0)` at the bottom left of the
+/// image and `(1, 1)` at the top right.
+#[derive(Clone, Debug)]
+pub struct Texture {
+    image: Arc<Canvas>,
+    filter: TextureFilter,
+}
+
+impl Texture {
+    /// Create a texture from an image.
+    ///
+    /// Textures use bilinear filtering by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to sample. Images can be read from files using
+    ///   [`load_ppm`][crate::loaders::load_ppm].
+    pub fn new(image: Canvas) -> Self {
+        Self {
+            image: Arc::new(image),
+            filter: TextureFilter::Bilinear,
+        }
+    }
+
+    /// Create a copy of the texture that uses a different filter.
+    ///
+    /// The image is shared between the copies rather than duplicated.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter to use when sampling the texture.
+    pub fn with_filter(&self, filter: TextureFilter) -> Self {
+        Self {
+            filter,
+            ..self.clone()
+        }
+    }
+
+    pub fn filter(&self) -> TextureFilter {
+        self.filter
+    }
+
+    pub fn image(&self) -> &Canvas {
+        &self.image
+    }
+
+    /// Get the color of the texture at a UV coordinate.
+    ///
+    /// Coordinates outside of `[0, 1]` are clamped to the edge of the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The horizontal coordinate, increasing to the right.
+    /// * `v` - The vertical coordinate, increasing upwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{canvas::Canvas, patterns::Texture, Color};
+    /// let mut image = Canvas::new(2, 1);
+    /// image.write_pixel(1, 0, Color::new(1, 1, 1));
+    ///
+    /// let texture = Texture::new(image);
+    ///
+    /// assert_eq!(texture.color_at_uv(0.5, 0.5), Color::new(0.5, 0.5, 0.5));
+    /// ```
+    pub fn color_at_uv(&self, u: f64, v: f64) -> Color {
+        let width = self.image.width();
+        let height = self.image.height();
+        if width == 0 || height == 0 {
+            return Color::new(0, 0, 0);
+        }
+
+        // Pixel rows start at the top of the image, but v starts at the
+        // bottom, so flip it.
+        let x = u.clamp(0.0, 1.0) * (width - 1) as f64;
+        let y = (1.0 - v.clamp(0.0, 1.0)) * (height - 1) as f64;
+
+        match self.filter {
+            TextureFilter::Nearest => self.image.pixel_at(x.round() as usize, y.round() as usize),
+            TextureFilter::Bilinear => {
+                let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+                let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+                let (tx, ty) = (x - x0 as f64, y - y0 as f64);
+
+                let top = lerp(self.image.pixel_at(x0, y0), self.image.pixel_at(x1, y0), tx);
+                let bottom = lerp(self.image.pixel_at(x0, y1), self.image.pixel_at(x1, y1), tx);
+
+                lerp(top, bottom, ty)
+            }
+        }
+    }
+}
+
+fn lerp(a: Color, b: Color, t: f64) -> Color {
+    a + (b - a) * t
+}
+
+/// Ways of converting a point on a surface into UV coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UvMapping {
+    /// Wrap the texture around a sphere centered at the origin, like the
+    /// lines of latitude and longitude on a globe. Suits
+    /// [`Sphere`][crate::objects::Sphere].
+    Spherical,
+
+    /// Tile the texture across the xz-plane, repeating every unit. Suits
+    /// [`Plane`][crate::objects::Plane].
+    Planar,
+
+    /// Wrap the texture around the y-axis, repeating every unit vertically.
+    Cylindrical,
+}
+
+impl UvMapping {
+    /// Convert a point into UV coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to convert, in the space of the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, patterns::UvMapping};
+    /// let (u, v) = UvMapping::Planar.map(&Tuple::new_point(1.25, 0.0, -0.25));
+    ///
+    /// assert_eq!((u, v), (0.25, 0.75));
+    /// ```
+    pub fn map(&self, point: &Tuple) -> (f64, f64) {
+        match self {
+            Self::Spherical => {
+                let radius = (point.x().powi(2) + point.y().powi(2) + point.z().powi(2)).sqrt();
+                if radius == 0.0 {
+                    return (0.5, 0.5);
+                }
+
+                // The azimuthal angle goes from -π to π around the y-axis.
+                // Flip it so the texture isn't mirrored when viewed from
+                // outside.
+                let theta = point.x().atan2(point.z());
+                let u = 1.0 - (theta / (2.0 * PI) + 0.5);
+
+                // The polar angle goes from 0 at the north pole to π at the
+                // south pole.
+                let phi = (point.y() / radius).clamp(-1.0, 1.0).acos();
+                let v = 1.0 - phi / PI;
+
+                (u, v)
+            }
+            Self::Planar => (point.x().rem_euclid(1.0), point.z().rem_euclid(1.0)),
+            Self::Cylindrical => {
+                let theta = point.x().atan2(point.z());
+                let u = 1.0 - (theta / (2.0 * PI) + 0.5);
+
+                (u, point.y().rem_euclid(1.0))
+            }
+        }
+    }
+}
+
+/// A pattern that wraps an image texture around an object using a UV
+/// mapping.
+#[derive(Clone, Debug)]
+pub struct TextureMap {
+    texture: Texture,
+    mapping: UvMapping,
+    transform: Matrix,
+}
+
+impl TextureMap {
+    /// Create a new texture map.
+    ///
+    /// # Arguments
+    ///
+    /// * `texture` - The texture to apply.
+    /// * `mapping` - How points on the object are converted into UV
+    ///   coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// # use raytracer::{
+    /// #     loaders::load_ppm,
+    /// #     objects::{Shape, Sphere},
+    /// #     patterns::{Texture, TextureMap, UvMapping},
+    /// #     Material,
+    /// # };
+    /// let image = load_ppm(File::open("earth.ppm").unwrap()).unwrap();
+    /// let earth = TextureMap::new(Texture::new(image), UvMapping::Spherical);
+    ///
+    /// let mut globe = Sphere::default();
+    /// globe.set_material(Material::default().with_pattern(earth));
+    /// ```
+    pub fn new(texture: Texture, mapping: UvMapping) -> Self {
+        Self {
+            texture,
+            mapping,
+            transform: Matrix::identity_4(),
+        }
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn mapping(&self) -> UvMapping {
+        self.mapping
+    }
+}
+
+impl Pattern for TextureMap {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        let (u, v) = self.mapping.map(point);
+
+        self.texture.color_at_uv(u, v)
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+/// A face of a cube map.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CubeFace {
+    Left,
+    Front,
+    Right,
+    Back,
+    Up,
+    Down,
+}
+
+impl CubeFace {
+    /// Find the face of the cube that a point is on, along with the point's
+    /// UV coordinates on that face.
+    ///
+    /// The cube spans from -1 to 1 along each axis. The front of the cube
+    /// faces the positive z-axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to map, in the space of the pattern.
+    pub fn map(point: &Tuple) -> (Self, f64, f64) {
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        let largest = x.abs().max(y.abs()).max(z.abs());
+
+        // Each face is two units across, so the offset from one edge is halved
+        // to get a coordinate between 0 and 1.
+        let uv = |a: f64| (a.rem_euclid(2.0)) / 2.0;
+
+        if largest == x {
+            (Self::Right, uv(1.0 - z), uv(y + 1.0))
+        } else if largest == -x {
+            (Self::Left, uv(z + 1.0), uv(y + 1.0))
+        } else if largest == y {
+            (Self::Up, uv(x + 1.0), uv(1.0 - z))
+        } else if largest == -y {
+            (Self::Down, uv(x + 1.0), uv(z + 1.0))
+        } else if largest == z {
+            (Self::Front, uv(x + 1.0), uv(y + 1.0))
+        } else {
+            (Self::Back, uv(1.0 - x), uv(y + 1.0))
+        }
+    }
+}
+
+/// A pattern that applies a separate texture to each face of a cube, such as
+/// for dice or skyboxes.
+#[derive(Clone, Debug)]
+pub struct CubeMap {
+    faces: [Texture; 6],
+    transform: Matrix,
+}
+
+impl CubeMap {
+    /// Create a new cube map.
+    ///
+    /// # Arguments
+    ///
+    /// * `left`, `front`, `right`, `back`, `up`, `down` - The texture for
+    ///   each face of the cube, as seen from outside the cube with the front
+    ///   facing the positive z-axis.
+    pub fn new(
+        left: Texture,
+        front: Texture,
+        right: Texture,
+        back: Texture,
+        up: Texture,
+        down: Texture,
+    ) -> Self {
+        Self {
+            faces: [left, front, right, back, up, down],
+            transform: Matrix::identity_4(),
+        }
+    }
+
+    pub fn face(&self, face: CubeFace) -> &Texture {
+        &self.faces[face as usize]
+    }
+}
+
+impl Pattern for CubeMap {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        let (face, u, v) = CubeFace::map(point);
+
+        self.face(face).color_at_uv(u, v)
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+
+    /// Build a 2x2 image with a different color in each corner.
+    fn corners() -> Canvas {
+        let mut image = Canvas::new(2, 2);
+        image.write_pixel(0, 0, Color::new(1, 0, 0));
+        image.write_pixel(1, 0, Color::new(0, 1, 0));
+        image.write_pixel(0, 1, Color::new(0, 0, 1));
+        image.write_pixel(1, 1, Color::new(1, 1, 1));
+
+        image
+    }
+
+    fn solid(color: Color) -> Texture {
+        let mut image = Canvas::new(1, 1);
+        image.write_pixel(0, 0, color);
+
+        Texture::new(image)
+    }
+
+    fn assert_uv(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+            "Expected {:?} but found {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn color_at_uv_nearest() {
+        let texture = Texture::new(corners()).with_filter(TextureFilter::Nearest);
+
+        assert_eq!(texture.color_at_uv(0.0, 1.0), Color::new(1, 0, 0));
+        assert_eq!(texture.color_at_uv(0.9, 0.9), Color::new(0, 1, 0));
+        assert_eq!(texture.color_at_uv(0.1, 0.1), Color::new(0, 0, 1));
+        assert_eq!(texture.color_at_uv(1.0, 0.0), Color::new(1, 1, 1));
+    }
+
+    #[test]
+    fn color_at_uv_bilinear() {
+        let texture = Texture::new(corners());
+
+        assert_eq!(texture.color_at_uv(0.0, 1.0), Color::new(1, 0, 0));
+        assert_eq!(texture.color_at_uv(0.5, 1.0), Color::new(0.5, 0.5, 0.0));
+        assert_eq!(texture.color_at_uv(0.5, 0.5), Color::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn color_at_uv_clamps() {
+        let texture = Texture::new(corners()).with_filter(TextureFilter::Nearest);
+
+        assert_eq!(texture.color_at_uv(-1.0, 2.0), Color::new(1, 0, 0));
+        assert_eq!(texture.color_at_uv(2.0, -1.0), Color::new(1, 1, 1));
+    }
+
+    #[test]
+    fn color_at_uv_empty_image() {
+        let texture = Texture::new(Canvas::new(0, 0));
+
+        assert_eq!(texture.color_at_uv(0.5, 0.5), Color::new(0, 0, 0));
+    }
+
+    #[test]
+    fn map_spherical() {
+        let cases = [
+            (Tuple::new_point(0, 0, -1), (0.0, 0.5)),
+            (Tuple::new_point(1, 0, 0), (0.25, 0.5)),
+            (Tuple::new_point(0, 0, 1), (0.5, 0.5)),
+            (Tuple::new_point(-1, 0, 0), (0.75, 0.5)),
+            (Tuple::new_point(0, 1, 0), (0.5, 1.0)),
+            (Tuple::new_point(0, -1, 0), (0.5, 0.0)),
+            (
+                Tuple::new_point(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0),
+                (0.25, 0.75),
+            ),
+        ];
+
+        for (point, expected) in cases.iter() {
+            assert_uv(UvMapping::Spherical.map(point), *expected);
+        }
+    }
+
+    #[test]
+    fn map_planar() {
+        let cases = [
+            (Tuple::new_point(0.25, 0.0, 0.5), (0.25, 0.5)),
+            (Tuple::new_point(0.25, 0.0, -0.25), (0.25, 0.75)),
+            (Tuple::new_point(0.25, 0.5, -0.25), (0.25, 0.75)),
+            (Tuple::new_point(1.25, 0.0, 0.5), (0.25, 0.5)),
+            (Tuple::new_point(-0.25, 0.0, -1.75), (0.75, 0.25)),
+        ];
+
+        for (point, expected) in cases.iter() {
+            assert_uv(UvMapping::Planar.map(point), *expected);
+        }
+    }
+
+    #[test]
+    fn map_cylindrical() {
+        let cases = [
+            (Tuple::new_point(0, 0, -1), (0.0, 0.0)),
+            (Tuple::new_point(0.0, 0.5, -1.0), (0.0, 0.5)),
+            (Tuple::new_point(0, 1, -1), (0.0, 0.0)),
+            (
+                Tuple::new_point(FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2),
+                (0.125, 0.5),
+            ),
+            (Tuple::new_point(1.0, 0.5, 0.0), (0.25, 0.5)),
+            (Tuple::new_point(0.0, -0.25, 1.0), (0.5, 0.75)),
+        ];
+
+        for (point, expected) in cases.iter() {
+            assert_uv(UvMapping::Cylindrical.map(point), *expected);
+        }
+    }
+
+    #[test]
+    fn cube_face_map() {
+        let cases = [
+            (
+                Tuple::new_point(-1.0, 0.5, -0.25),
+                CubeFace::Left,
+                (0.375, 0.75),
+            ),
+            (
+                Tuple::new_point(1.1, -0.75, 0.8),
+                CubeFace::Right,
+                (0.1, 0.125),
+            ),
+            (
+                Tuple::new_point(0.1, 0.6, 0.9),
+                CubeFace::Front,
+                (0.55, 0.8),
+            ),
+            (
+                Tuple::new_point(-0.7, 0.0, -2.0),
+                CubeFace::Back,
+                (0.85, 0.5),
+            ),
+            (Tuple::new_point(0.5, 1.0, 0.9), CubeFace::Up, (0.75, 0.05)),
+            (
+                Tuple::new_point(-0.2, -1.3, 1.1),
+                CubeFace::Down,
+                (0.4, 0.05),
+            ),
+        ];
+
+        for (point, face, expected) in cases.iter() {
+            let (actual_face, u, v) = CubeFace::map(point);
+
+            assert_eq!(actual_face, *face);
+            assert_uv((u, v), *expected);
+        }
+    }
+
+    #[test]
+    fn texture_map_pattern_at() {
+        let pattern = TextureMap::new(
+            Texture::new(corners()).with_filter(TextureFilter::Nearest),
+            UvMapping::Planar,
+        );
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.1, 0.0, 0.9)),
+            Color::new(1, 0, 0)
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(1.9, 0.0, 0.1)),
+            Color::new(1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn cube_map_pattern_at() {
+        let pattern = CubeMap::new(
+            solid(Color::new(1, 0, 0)),
+            solid(Color::new(0, 1, 0)),
+            solid(Color::new(0, 0, 1)),
+            solid(Color::new(1, 1, 0)),
+            solid(Color::new(0, 1, 1)),
+            solid(Color::new(1, 0, 1)),
+        );
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(-1.0, 0.0, 0.0)),
+            Color::new(1, 0, 0)
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.0, 0.0, 1.0)),
+            Color::new(0, 1, 0)
+        );
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.0, -1.0, 0.0)),
+            Color::new(1, 0, 1)
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    linear::{Matrix, Tuple},
+    objects::Shape,
+    Color,
+};
+
+use super::{pattern::to_pattern_space, Pattern, Texture};
+
+/// A pattern that projects a texture along each of the x, y, and z axes, and
+/// blends the three projections based on which way the surface faces.
+///
+/// Unlike a [`TextureMap`][super::TextureMap], triplanar mapping doesn't need
+/// any UV coordinates, so it works on any shape, including meshes and shapes
+/// that have been stretched by their transform. The texture repeats every unit
+/// along each axis, and is applied in the object's space so that it moves
+/// with the object.
+#[derive(Clone, Debug)]
+pub struct Triplanar {
+    texture: Texture,
+    sharpness: f64,
+    transform: Matrix,
+    inverse_transform: Matrix,
+}
+
+impl Triplanar {
+    /// Create a new triplanar mapping.
+    ///
+    /// The projections are blended with a sharpness of 4.
+    ///
+    /// # Arguments
+    ///
+    /// * `texture` - The texture to project.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// # use raytracer::{
+    /// #     loaders::{load_ppm, load_stl},
+    /// #     objects::Shape,
+    /// #     patterns::{Texture, Triplanar},
+    /// #     Material,
+    /// # };
+    /// let image = load_ppm(File::open("rock.ppm").unwrap()).unwrap();
+    /// let mut boulder = load_stl(File::open("boulder.stl").unwrap()).unwrap();
+    ///
+    /// boulder.set_material(Material::default().with_pattern(Triplanar::new(Texture::new(image))));
+    /// ```
+    pub fn new(texture: Texture) -> Self {
+        Self {
+            texture,
+            sharpness: 4.0,
+            transform: Matrix::identity_4(),
+            inverse_transform: Matrix::identity_4(),
+        }
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn sharpness(&self) -> f64 {
+        self.sharpness
+    }
+
+    /// Create a copy of the mapping with a different blend sharpness.
+    ///
+    /// # Arguments
+    ///
+    /// * `sharpness` - How quickly one projection gives way to the next as
+    ///   the surface turns. A sharpness of 1 blends smoothly but smears the
+    ///   texture on diagonal surfaces, while large values give hard seams.
+    pub fn with_sharpness(&self, sharpness: f64) -> Self {
+        Self {
+            sharpness,
+            ..self.clone()
+        }
+    }
+
+    /// Blend the projections of the texture for a surface facing along a
+    /// normal.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point to sample, in pattern space.
+    /// * `normal` - The surface's normal, in pattern space.
+    fn blend(&self, point: &Tuple, normal: &Tuple) -> Color {
+        let weight = |n: f64| n.abs().powf(self.sharpness);
+        let (wx, wy, wz) = (weight(normal.x()), weight(normal.y()), weight(normal.z()));
+        let total = wx + wy + wz;
+        if total == 0.0 {
+            return Color::new(0, 0, 0);
+        }
+
+        let project = |u: f64, v: f64| {
+            self.texture
+                .color_at_uv(u.rem_euclid(1.0), v.rem_euclid(1.0))
+        };
+        let (x, y, z) = (point.x(), point.y(), point.z());
+
+        (project(z, y) * wx + project(x, z) * wy + project(x, y) * wz) * (1.0 / total)
+    }
+}
+
+impl Pattern for Triplanar {
+    /// Sample the mapping without knowing which way the surface faces.
+    ///
+    /// The direction from the origin stands in for the surface's normal, which
+    /// is exact for spheres centered at the origin.
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        let normal = Tuple::new_vector(point.x(), point.y(), point.z());
+
+        self.blend(point, &normal)
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse_transform = transform.inverted();
+        self.transform = transform;
+    }
+
+    fn pattern_at_shape(&self, object: &dyn Shape, world_point: &Tuple) -> Color {
+        let (pattern_point, normal) = to_pattern_space(
+            object,
+            &self.transform,
+            &self.inverse_transform,
+            world_point,
+        );
+
+        self.blend(&pattern_point, &normal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{canvas::Canvas, objects::Sphere, patterns::TextureFilter};
+
+    /// Build a texture that is red on its left half and blue on its right
+    /// half.
+    fn halves() -> Texture {
+        let mut image = Canvas::new(2, 1);
+        image.write_pixel(0, 0, Color::new(1, 0, 0));
+        image.write_pixel(1, 0, Color::new(0, 0, 1));
+
+        Texture::new(image).with_filter(TextureFilter::Nearest)
+    }
+
+    #[test]
+    fn pattern_at_shape_projects_along_dominant_axis() {
+        let sphere = Sphere::default();
+        let pattern = Triplanar::new(halves()).with_sharpness(50.0);
+        let sample = |x, y, z| pattern.pattern_at_shape(&sphere, &Tuple::new_point(x, y, z));
+
+        // Projecting along the x-axis, u follows z. Projecting along the y- or
+        // z-axis, u follows x.
+        assert_eq!(sample(0.8, 0.0, -0.6), Color::new(1, 0, 0));
+        assert_eq!(sample(0.6, 0.8, 0.0), Color::new(0, 0, 1));
+        assert_eq!(sample(0.6, 0.0, -0.8), Color::new(0, 0, 1));
+    }
+
+    #[test]
+    fn pattern_at_shape_blends_diagonal_surfaces() {
+        let sphere = Sphere::default();
+        let pattern = Triplanar::new(halves()).with_sharpness(1.0);
+
+        // The projection along the x-axis samples (z, y) = (0, 0.8), which is
+        // red, and the one along the y-axis samples (x, z) = (0.6, 0), which
+        // is blue.
+        let c = pattern.pattern_at_shape(&sphere, &Tuple::new_point(0.6, 0.8, 0.0));
+
+        assert_eq!(c, Color::new(0.6 / 1.4, 0.0, 0.8 / 1.4));
+    }
+
+    #[test]
+    fn pattern_at_shape_uses_object_space() {
+        let sphere = Sphere::default();
+        let mut stretched = Sphere::default();
+        stretched.set_transform(&Matrix::translation(10, 0, 0) * &Matrix::scaling(2, 1, 1));
+        let pattern = Triplanar::new(halves());
+
+        let expected = pattern.pattern_at_shape(&sphere, &Tuple::new_point(0.6, 0.8, 0.0));
+        let c = pattern.pattern_at_shape(&stretched, &Tuple::new_point(11.2, 0.8, 0.0));
+
+        assert_eq!(c, expected);
+    }
+
+    #[test]
+    fn pattern_at_uses_direction_from_origin() {
+        let pattern = Triplanar::new(halves()).with_sharpness(1.0);
+
+        assert_eq!(
+            pattern.pattern_at(&Tuple::new_point(0.6, 0.8, 0.0)),
+            Color::new(0.6 / 1.4, 0.0, 0.8 / 1.4)
+        );
+    }
+}
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use crate::{
     intersections::{IntersectionInfo, Intersections},
-    lights::PointLight,
+    lights::{Light, PointLight},
     linear::{Matrix, Tuple},
     objects::{Shape, Sphere},
-    Color, Material, Ray,
+    Color, Material, Preset, Ray,
 };
 
 lazy_static! {
@@ -33,58 +35,381 @@ lazy_static! {
 
 #[derive(Debug)]
 pub struct World<'a> {
-    pub objects: Vec<&'a (dyn Shape + Send + Sync)>,
-    pub light: Option<&'a PointLight>,
+    // The objects in the world. An object's ID is its index in this list, so
+    // objects can only be added through `add_object` to keep IDs stable.
+    objects: Vec<&'a (dyn Shape + Send + Sync)>,
+
+    // The ID of each object, keyed by the object's address.
+    object_ids: HashMap<usize, usize>,
+
+    /// The light sources illuminating the world.
+    pub lights: Vec<&'a dyn Light>,
+
+    /// The color and brightness of the ambient light, which stands in for
+    /// light that has bounced around the scene. It lights every surface once,
+    /// whether or not it's shadowed, no matter how many light sources there
+    /// are. Defaults to white, which matches a scene lit by a single white
+    /// light.
+    pub ambient: Color,
+
+    /// The number of times a ray may bounce off reflective surfaces or pass
+    /// through transparent ones before the world stops tracing it. This stops
+    /// rays from bouncing forever between surfaces that reflect each other.
+    pub max_depth: usize,
+
+    /// Materials that objects in the world can refer to by name. Prefer
+    /// [`World::register_material`] and [`World::material`] over using this
+    /// directly.
+    pub materials: HashMap<String, Material>,
 }
 
+/// Find the address of an object, which identifies it within a world.
+fn address_of(object: &dyn Shape) -> usize {
+    object as *const dyn Shape as *const () as usize
+}
+
+/// The recursion depth used for worlds that don't specify one.
+const DEFAULT_MAX_DEPTH: usize = 5;
+
 impl<'a> World<'a> {
-    /// Create an empty world with no light source.
+    /// Create an empty world with no light sources.
+    ///
+    /// Every [material preset][Preset] is registered under its name.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use raytracer::World;
+    /// # use raytracer::{Color, World};
     /// let world = World::new();
     ///
-    /// assert_eq!(world.objects.len(), 0);
-    /// assert!(world.light.is_none());
+    /// assert!(world.objects().is_empty());
+    /// assert!(world.lights.is_empty());
+    /// assert_eq!(world.ambient, Color::new(1, 1, 1));
+    /// assert_eq!(world.max_depth, 5);
+    /// assert!(world.material("gold").is_some());
     /// ```
     pub fn new() -> Self {
+        let materials = Preset::ALL
+            .iter()
+            .map(|preset| (preset.name().to_owned(), preset.material()))
+            .collect();
+
         Self {
             objects: Vec::new(),
-            light: None,
+            object_ids: HashMap::new(),
+            lights: Vec::new(),
+            ambient: Color::new(1, 1, 1),
+            max_depth: DEFAULT_MAX_DEPTH,
+            materials,
+        }
+    }
+
+    /// Register a material that can be looked up by name.
+    ///
+    /// Registering a material under a name that is already in use replaces
+    /// the existing material, including presets.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to register the material under.
+    /// * `material` - The material.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{Color, Material, World};
+    /// let mut world = World::new();
+    /// let brick = Material::default().with_color(Color::new(0.6, 0.2, 0.1));
+    ///
+    /// world.register_material("brick", brick.clone());
+    ///
+    /// assert_eq!(world.material("brick"), Some(&brick));
+    /// assert_eq!(world.material("marble"), None);
+    /// ```
+    pub fn register_material(&mut self, name: &str, material: Material) {
+        self.materials.insert(name.to_owned(), material);
+    }
+
+    /// Find a registered material by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the material to find.
+    pub fn material(&self, name: &str) -> Option<&Material> {
+        self.materials.get(name)
+    }
+
+    /// Retrieve the objects in the world, in the order they were added.
+    pub fn objects(&self) -> &[&'a (dyn Shape + Send + Sync)] {
+        &self.objects
+    }
+
+    /// Add an object to the world.
+    ///
+    /// The object's ID is its position in the world's list of objects, so the
+    /// same scene always produces the same IDs regardless of the order the
+    /// objects were constructed in. The ID belongs to the world rather than
+    /// the object, so the same object can be part of several worlds. Adding
+    /// an object that is already in the world does nothing, and returns the
+    /// ID it already has.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object to add.
+    ///
+    /// # Returns
+    ///
+    /// The ID of the object within the world.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{objects::Sphere, World};
+    /// let s1 = Sphere::default();
+    /// let s2 = Sphere::default();
+    /// let mut world = World::new();
+    ///
+    /// assert_eq!(world.add_object(&s1), 0);
+    /// assert_eq!(world.add_object(&s2), 1);
+    /// assert_eq!(world.object_id(&s2), Some(1));
+    /// ```
+    pub fn add_object(&mut self, object: &'a (dyn Shape + Send + Sync)) -> usize {
+        if let Some(id) = self.object_id(object) {
+            return id;
         }
+
+        self.objects.push(object);
+        let id = self.objects.len() - 1;
+        self.object_ids.insert(address_of(object), id);
+
+        id
+    }
+
+    /// Find the ID of an object in the world.
+    ///
+    /// Objects are matched by identity rather than by value, so two identical
+    /// objects in the same world have different IDs.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object to find the ID of.
+    ///
+    /// # Returns
+    ///
+    /// The object's index in [`objects`][Self::objects], or `None` if the
+    /// object isn't part of the world.
+    pub fn object_id(&self, object: &dyn Shape) -> Option<usize> {
+        self.object_ids.get(&address_of(object)).copied()
+    }
+
+    /// Add a light source to the world.
+    ///
+    /// Every light contributes to the lighting of each surface, so scenes
+    /// can be lit with a bright key light and dimmer fill and rim lights.
+    ///
+    /// # Arguments
+    ///
+    /// * `light` - The light to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{lights::PointLight, linear::Tuple, Color, World};
+    /// let key = PointLight::new(Tuple::new_point(-10, 10, -10), Color::new(1, 1, 1));
+    /// let fill = PointLight::new(Tuple::new_point(10, 5, -10), Color::new(0.3, 0.3, 0.3));
+    /// let mut world = World::new();
+    ///
+    /// world.add_light(&key);
+    /// world.add_light(&fill);
+    ///
+    /// assert_eq!(world.lights.len(), 2);
+    /// ```
+    pub fn add_light(&mut self, light: &'a dyn Light) {
+        self.lights.push(light);
     }
 
-    /// Determine if a point is in shadow or not.
+    /// Find an object in the world by its name.
     ///
-    /// A point is shadowed if the ray from the point to the light source
-    /// intersects with any objects.
+    /// If multiple objects share the same name, the first one added to the
+    /// world is returned.
     ///
     /// # Arguments
     ///
+    /// * `name` - The name of the object to find.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{objects::{Shape, Sphere}, World};
+    /// let mut sphere = Sphere::default();
+    /// sphere.set_name("left_sphere");
+    ///
+    /// let mut world = World::new();
+    /// world.add_object(&sphere);
+    ///
+    /// assert!(world.find("left_sphere").is_some());
+    /// assert!(world.find("right_sphere").is_none());
+    /// ```
+    pub fn find(&self, name: &str) -> Option<&'a (dyn Shape + Send + Sync)> {
+        self.objects
+            .iter()
+            .find(|object| object.name() == Some(name))
+            .copied()
+    }
+
+    /// Determine if a point is in the shadow of a light source.
+    ///
+    /// A point is shadowed if the ray from the point to the light source is
+    /// completely blocked. Opaque objects block all of the light, while
+    /// transparent objects only dim it, as described in
+    /// [`visibility`][Self::visibility]. Emissive objects that a light is
+    /// inside of don't cast shadows from that light, so that a light source
+    /// can be placed inside a glowing bulb or lamp shade. Other emissive
+    /// objects, like neon signs, cast shadows as usual. Invisible shadow
+    /// catchers never cast shadows.
+    ///
+    /// # Arguments
+    ///
+    /// * `light` - The light source to check for a shadow from.
     /// * `point` - The location in the world to perform a shadow check for.
-    pub fn is_shadowed(&self, point: &Tuple) -> bool {
-        let light = match self.light {
-            Some(light) => light,
-            None => return true,
-        };
+    pub fn is_shadowed(&self, light: &dyn Light, point: &Tuple) -> bool {
+        let transmitted = self.transmission(
+            point,
+            light.direction_from(point),
+            light.distance_from(point),
+        );
+
+        transmitted == Color::new(0, 0, 0)
+    }
 
-        let to_light = light.position() - *point;
-        let distance = to_light.magnitude();
-        let direction = to_light.normalized();
+    /// Find how much of a light source reaches a point, from black for a
+    /// point in its shadow to white for a fully lit point.
+    ///
+    /// Transparent objects let some of the light through. Each of their
+    /// surfaces that the light passes through dims it by the material's
+    /// transparency, and the light is tinted by the material's
+    /// [absorption][Material::with_absorption] over the distance it travels
+    /// inside the object. Each of the light's [samples][Light::samples_from]
+    /// is checked separately and the results are averaged.
+    ///
+    /// # Arguments
+    ///
+    /// * `light` - The light source to check the visibility of.
+    /// * `point` - The location in the world to check from.
+    pub fn visibility(&self, light: &dyn Light, point: &Tuple) -> Color {
+        let samples = light.samples_from(point);
+        if samples.is_empty() {
+            return Color::new(0, 0, 0);
+        }
 
+        let total = samples
+            .iter()
+            .fold(Color::new(0, 0, 0), |total, (direction, distance)| {
+                total + self.transmission(point, *direction, *distance)
+            });
+
+        total * (1.0 / samples.len() as f64)
+    }
+
+    /// Find the fraction of each color channel of light that makes it along a
+    /// path from a point without being blocked.
+    fn transmission(&self, point: &Tuple, direction: Tuple, distance: f64) -> Color {
         let r = Ray::new(*point, direction);
         let intersections = self.intersect(&r);
 
-        match intersections.hit() {
-            // We can compare t to distance because our ray was normalized, so
-            // it travels one world unit per unit time.
-            Some(hit) => hit.t() < distance,
+        // The light is inside an object if the path leaves the object more
+        // times than it enters it after passing the light.
+        let contains_light = |object: &dyn Shape| {
+            let beyond_light = intersections
+                .iter()
+                .filter(|i| i.t() >= distance && i.object() == object)
+                .count();
+
+            distance.is_finite() && beyond_light % 2 == 1
+        };
+        let casts_shadow = |object: &dyn Shape| {
+            let material = object.material();
+            let is_ignored =
+                material.is_shadow_catcher() || (material.is_emissive() && contains_light(object));
+
+            !is_ignored
+        };
+
+        let mut transmitted = Color::new(1, 1, 1);
+
+        // The objects that the path is inside of, along with the time it
+        // entered them. Objects the point is already inside of are tracked
+        // from intersections behind the point.
+        let mut containers: Vec<(&dyn Shape, f64)> = Vec::new();
+
+        // We can compare t to distance because our ray was normalized, so it
+        // travels one world unit per unit time. Lights that are infinitely far
+        // away are blocked by anything in front of the point.
+        for i in intersections
+            .iter()
+            .filter(|i| i.t() < distance && casts_shadow(i.object()))
+        {
+            let material = i.object().material();
+
+            match containers
+                .iter()
+                .position(|(object, _)| *object == i.object())
+            {
+                Some(index) => {
+                    let (_, entered) = containers.remove(index);
+                    if i.t() > 0.0 {
+                        transmitted =
+                            transmitted * material.transmittance(i.t() - entered.max(0.0));
+                    }
+                }
+                None => containers.push((i.object(), i.t())),
+            }
 
-            // No hit means no obstacle
-            None => false,
+            if i.t() >= 0.0 {
+                if material.transparency() == 0.0 {
+                    return Color::new(0, 0, 0);
+                }
+
+                transmitted = transmitted * material.transparency();
+            }
         }
+
+        // The light is inside any objects that the path hasn't left yet.
+        containers
+            .iter()
+            .fold(transmitted, |transmitted, (object, entered)| {
+                transmitted * object.material().transmittance(distance - entered.max(0.0))
+            })
+    }
+
+    /// Find how much of the light falling on a point is blocked by other
+    /// objects, from 0 for a fully lit point to 1 for a point in the shadow
+    /// of every light.
+    ///
+    /// Each light is weighted by its intensity, so a point in the shadow of
+    /// a dim fill light is barely shadowed at all.
+    fn shadow_at(&self, point: &Tuple) -> f64 {
+        let brightness = |color: Color| (color.red() + color.green() + color.blue()) / 3.0;
+
+        let total: f64 = self
+            .lights
+            .iter()
+            .map(|light| brightness(light.intensity_at(point)))
+            .sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let blocked: f64 = self
+            .lights
+            .iter()
+            .map(|light| {
+                brightness(light.intensity_at(point))
+                    * (1.0 - brightness(self.visibility(*light, point)))
+            })
+            .sum();
+
+        blocked / total
     }
 
     /// Compute the color of the world by casting a ray into it.
@@ -96,57 +421,255 @@ impl<'a> World<'a> {
     ///
     /// * `ray` - The ray to cast into the world.
     pub fn color_at(&self, ray: &Ray) -> Color {
-        let intersections = self.intersect(ray);
-        let hit = intersections.hit();
+        self.color_at_depth(ray, self.max_depth)
+    }
 
-        match hit {
-            Some(intersection) => {
-                let info = intersection.prepare_info(ray);
+    /// Compute the color of the world by casting a ray into it, along with
+    /// how opaque the world is along the ray.
+    ///
+    /// Rays that hit an object are opaque, while rays that miss every object
+    /// are transparent. Rays passing through a
+    /// [shadow catcher][Material::with_shadow_catcher] are as opaque as the
+    /// catcher is shadowed, so that the shadow darkens whatever the render is
+    /// composited over.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray to cast into the world.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use raytracer::{linear::Tuple, Ray, World};
+    /// let world = World::default();
+    /// let miss = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 1, 0));
+    ///
+    /// assert_eq!(world.color_and_alpha_at(&miss).1, 0.0);
+    /// ```
+    pub fn color_and_alpha_at(&self, ray: &Ray) -> (Color, f64) {
+        let trace = self.trace(ray, self.max_depth);
 
-                self.shade_hit(&info)
+        (trace.color, trace.alpha)
+    }
+
+    fn color_at_depth(&self, ray: &Ray, remaining: usize) -> Color {
+        self.trace(ray, remaining).color
+    }
+
+    /// Find what is seen along a ray.
+    fn trace(&self, ray: &Ray, remaining: usize) -> Trace {
+        let intersections = self.intersect(ray);
+        let hit = match intersections.hit() {
+            Some(hit) => hit,
+            None => {
+                return Trace {
+                    color: Color::new(0, 0, 0),
+                    alpha: 0.0,
+                    distance: f64::INFINITY,
+                }
             }
-            None => Color::new(0, 0, 0),
+        };
+
+        let info = hit.prepare_info_with(ray, &intersections);
+
+        if hit.object().material().is_shadow_catcher() {
+            // Shadow catchers are invisible, so look at whatever is behind
+            // them and darken it by the catcher's shadow.
+            let shadow = self.shadow_at(&info.over_point());
+            let behind = self.trace(&Ray::new(info.under_point(), ray.direction()), remaining);
+
+            return Trace {
+                color: behind.color * (1.0 - shadow),
+                alpha: behind.alpha + (1.0 - behind.alpha) * shadow,
+                distance: hit.t() + behind.distance,
+            };
+        }
+
+        Trace {
+            color: self.shade_hit_depth(&info, remaining),
+            alpha: 1.0,
+            distance: hit.t(),
         }
     }
 
     /// Find the world objects hit by a given ray.
     ///
+    /// Intersections with parts of a surface that have been cut away by its
+    /// material's [opacity mask][Material::with_opacity_mask] are discarded,
+    /// as if the surface wasn't there.
+    ///
     /// # Arguments
     ///
     /// * `ray` - The ray to cast into the world.
-    pub fn intersect(&self, ray: &Ray) -> Intersections {
+    pub fn intersect(&self, ray: &Ray) -> Intersections<'a> {
         let mut intersections = Intersections::default();
 
         for object in self.objects.iter() {
             intersections.add_intersections(object.intersect(ray));
         }
 
+        let is_cut_out = |object: &dyn Shape, t: f64| {
+            object.material().opacity_mask().is_some()
+                && !object.material().is_opaque_at(object, &ray.position_at(t))
+        };
+
+        if intersections.iter().any(|i| is_cut_out(i.object(), i.t())) {
+            intersections = Intersections::new(
+                intersections
+                    .iter()
+                    .filter(|i| !is_cut_out(i.object(), i.t()))
+                    .copied()
+                    .collect(),
+            );
+        }
+
         intersections
     }
 
     /// Compute the lighting for a specific intersection.
     ///
+    /// The world's ambient light is added once, and then each light that
+    /// isn't blocked by another object adds its diffuse and specular
+    /// reflections.
+    ///
     /// # Arguments
     ///
     /// * `hit_info` - Information about the intersection to shade.
     pub fn shade_hit(&self, hit_info: &IntersectionInfo) -> Color {
-        let light = match self.light {
-            Some(light) => *light,
-            None => PointLight::new(Tuple::new_point(0, 0, 0), Color::new(0, 0, 0)),
-        };
+        self.shade_hit_depth(hit_info, self.max_depth)
+    }
+
+    fn shade_hit_depth(&self, hit_info: &IntersectionInfo, remaining: usize) -> Color {
+        let object = hit_info.object();
+        let material = object.material();
+
+        // Ambient light doesn't come from any particular light source, so
+        // it's only added once. Each light then adds its own direct lighting,
+        // unless something blocks it.
+        let ambient = material.ambient_light(object, &hit_info.point(), self.ambient);
+        let surface = self.lights.iter().fold(ambient, |surface, light| {
+            let visibility = self.visibility(*light, &hit_info.over_point());
+            if visibility == Color::new(0, 0, 0) {
+                return surface;
+            }
+
+            let direct = material.direct_light(
+                object,
+                *light,
+                &hit_info.point(),
+                &hit_info.eye_vec(),
+                &hit_info.shading_normal_vec(),
+            );
 
-        let is_shadowed = self.is_shadowed(&hit_info.over_point());
+            surface + direct * visibility
+        });
 
-        hit_info.object().material().light(
-            &light,
-            &hit_info.point(),
-            &hit_info.eye_vec(),
-            &hit_info.normal_vec(),
-            is_shadowed,
-        )
+        let reflected = self.reflected_color(hit_info, remaining);
+        let refracted = self.refracted_color(hit_info, remaining);
+
+        // Emitted light doesn't depend on any light source, so it's added
+        // whether or not the point is shadowed.
+        let surface = surface + material.emissive();
+
+        // Surfaces that are both reflective and transparent reflect more light
+        // when viewed at a shallow angle, and let more through when viewed
+        // head on. Thin films reflect some colors more than others, and let
+        // the rest through. A film on an opaque surface still tints its
+        // reflections, even though nothing is let through.
+        if material.reflective() > 0.0 && material.transparency() > 0.0 {
+            let reflectance = hit_info.spectral_reflectance();
+
+            surface + reflected * reflectance + refracted * (Color::new(1, 1, 1) - reflectance)
+        } else if material.thin_film().is_some() {
+            surface + reflected * hit_info.spectral_reflectance() + refracted
+        } else {
+            surface + reflected + refracted
+        }
+    }
+
+    /// Compute the color reflected by the surface at an intersection.
+    ///
+    /// # Arguments
+    ///
+    /// * `hit_info` - Information about the intersection to find the reflected
+    ///   color for.
+    /// * `remaining` - The number of further reflections that may be traced.
+    ///   Once this reaches zero, no more reflections are traced and the
+    ///   reflected color is black.
+    pub fn reflected_color(&self, hit_info: &IntersectionInfo, remaining: usize) -> Color {
+        let reflective = hit_info.object().material().reflective();
+        if remaining == 0 || reflective == 0.0 {
+            return Color::new(0, 0, 0);
+        }
+
+        // Start the reflected ray from the offset point so that it doesn't
+        // immediately hit the surface it's reflecting off.
+        let reflect_ray = Ray::new(hit_info.over_point(), hit_info.reflect_vec());
+
+        self.color_at_depth(&reflect_ray, remaining - 1) * reflective
+    }
+
+    /// Compute the color of the light refracted through the surface at an
+    /// intersection.
+    ///
+    /// # Arguments
+    ///
+    /// * `hit_info` - Information about the intersection to find the refracted
+    ///   color for.
+    /// * `remaining` - The number of further reflections or refractions that
+    ///   may be traced. Once this reaches zero, the refracted color is black.
+    pub fn refracted_color(&self, hit_info: &IntersectionInfo, remaining: usize) -> Color {
+        let transparency = hit_info.object().material().transparency();
+        if remaining == 0 || transparency == 0.0 {
+            return Color::new(0, 0, 0);
+        }
+
+        // Find the angle of the refracted ray using Snell's law.
+        let n_ratio = hit_info.n1() / hit_info.n2();
+        let cos_i = hit_info.eye_vec().dot(hit_info.normal_vec());
+        let sin2_t = n_ratio.powi(2) * (1.0 - cos_i.powi(2));
+
+        // Past the critical angle, all of the light is reflected and none is
+        // refracted.
+        if sin2_t > 1.0 {
+            return Color::new(0, 0, 0);
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+        let direction =
+            hit_info.normal_vec() * (n_ratio * cos_i - cos_t) - hit_info.eye_vec() * n_ratio;
+
+        // Start the refracted ray from beneath the surface so that it doesn't
+        // immediately hit the surface it's passing through.
+        let refract_ray = Ray::new(hit_info.under_point(), direction);
+        let Trace {
+            color, distance, ..
+        } = self.trace(&refract_ray, remaining - 1);
+
+        // Light is absorbed by the medium it travels through on its way to
+        // the surface, so the further it travels the more it's tinted. The
+        // direction is a unit vector, so t is the distance travelled.
+        let transmittance = match hit_info.refracted_medium() {
+            Some(medium) => medium.material().transmittance(distance),
+            None => Color::new(1, 1, 1),
+        };
+
+        color * transmittance * transparency
     }
 }
 
+/// What is seen along a ray cast into the world.
+struct Trace {
+    color: Color,
+
+    // How opaque the world is along the ray.
+    alpha: f64,
+
+    // The distance to the surface that was seen, which is infinite if the ray
+    // doesn't hit anything.
+    distance: f64,
+}
+
 impl<'a> Default for World<'a> {
     /// Create the default world with two concentric spheres.
     ///
@@ -156,13 +679,15 @@ impl<'a> Default for World<'a> {
     /// # use raytracer::World;
     /// let w = World::default();
     ///
-    /// assert!(w.light.is_some());
-    /// assert_eq!(w.objects.len(), 2);
+    /// assert_eq!(w.lights.len(), 1);
+    /// assert_eq!(w.objects().len(), 2);
     /// ```
     fn default() -> Self {
-        Self {
-            light: Some(&DEFAULT_LIGHT),
-            objects: vec![&*DEFAULT_SPHERE_1, &*DEFAULT_SPHERE_2],
-        }
+        let mut world = Self::new();
+        world.add_object(&*DEFAULT_SPHERE_1);
+        world.add_object(&*DEFAULT_SPHERE_2);
+        world.add_light(&*DEFAULT_LIGHT);
+
+        world
     }
 }
@@ -2,8 +2,10 @@ use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
 
 use raytracer::{
     camera::{view_transform, Camera},
+    canvas::{GeometrySample, Outline},
     linear::{Matrix, Tuple},
-    Color, World,
+    objects::{Plane, Shape, Sphere},
+    Color, Material, World,
 };
 
 #[test]
@@ -74,7 +76,7 @@ fn camera_ray_camera_has_transform() {
 
     let r = c.ray_for_pixel(100, 50);
 
-    let sqrt_2_over_2 = (2.0 as f64).sqrt() / 2.0;
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
 
     assert_eq!(r.origin(), Tuple::new_point(0, 2, -5));
     assert_eq!(
@@ -96,3 +98,82 @@ pub fn camera_render_sanity_check() {
 
     assert_eq!(image.pixel_at(5, 5), Color::new(0.38066, 0.47583, 0.2855));
 }
+
+#[test]
+pub fn camera_render_geometry() {
+    let w = World::default();
+    let from = Tuple::new_point(0, 0, -5);
+    let to = Tuple::new_point(0, 0, 0);
+    let up = Tuple::new_vector(0, 1, 0);
+
+    let c = Camera::new(11, 11, FRAC_PI_2).with_transform(view_transform(&from, &to, &up));
+
+    let geometry = c.render_geometry(&w);
+
+    assert_eq!((geometry.width(), geometry.height()), (11, 11));
+    assert_eq!(
+        geometry.sample_at(5, 5),
+        Some(GeometrySample::new(
+            4.0,
+            Tuple::new_vector(0, 0, -1),
+            Some(0)
+        ))
+    );
+    assert_eq!(geometry.sample_at(0, 0), None);
+}
+
+#[test]
+pub fn camera_render_geometry_skips_shadow_catchers() {
+    let mut floor = Plane::default();
+    floor.set_material(Material::default().with_shadow_catcher(true));
+    let mut ball = Sphere::default();
+    ball.set_transform(Matrix::translation(0, -3, 0));
+    let mut w = World::new();
+    w.add_object(&floor);
+    let ball_id = w.add_object(&ball);
+    let from = Tuple::new_point(0, 5, 0);
+    let to = Tuple::new_point(0, 0, 0);
+    let up = Tuple::new_vector(0, 0, 1);
+
+    let c = Camera::new(11, 11, FRAC_PI_2).with_transform(view_transform(&from, &to, &up));
+
+    let geometry = c.render_geometry(&w);
+
+    // The ball is seen through the invisible floor, and the floor alone isn't
+    // seen at all.
+    assert_eq!(
+        geometry.sample_at(5, 5),
+        Some(GeometrySample::new(
+            7.0,
+            Tuple::new_vector(0, 1, 0),
+            Some(ball_id)
+        ))
+    );
+    assert_eq!(geometry.sample_at(0, 0), None);
+}
+
+#[test]
+pub fn camera_render_with_outline() {
+    let w = World::default();
+    let from = Tuple::new_point(0, 0, -5);
+    let to = Tuple::new_point(0, 0, 0);
+    let up = Tuple::new_vector(0, 1, 0);
+    let ink = Color::new(1, 0, 1);
+
+    let c = Camera::new(101, 101, FRAC_PI_2).with_transform(view_transform(&from, &to, &up));
+    let mut image = c.render(&w);
+
+    Outline::new(ink).apply(&mut image, &c.render_geometry(&w));
+
+    // The center of the sphere faces the camera, but its silhouette is
+    // outlined. The background is left alone.
+    assert_eq!(image.pixel_at(50, 50), Color::new(0.38066, 0.47583, 0.2855));
+    assert_eq!(image.pixel_at(0, 0), Color::new(0, 0, 0));
+
+    let outlined_row: Vec<_> = (0..101).filter(|&x| image.pixel_at(x, 50) == ink).collect();
+    let first = outlined_row[0];
+    let last = outlined_row[outlined_row.len() - 1];
+    assert!(first > 0);
+    assert_eq!(first + last, 100);
+    assert!(image.pixel_at(first - 1, 50) == Color::new(0, 0, 0));
+}
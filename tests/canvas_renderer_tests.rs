@@ -1,4 +1,7 @@
-use raytracer::canvas::{renderers::render_as_ppm, Canvas};
+use raytracer::canvas::{
+    renderers::{render_as_pam, render_as_ppm},
+    Canvas,
+};
 use raytracer::Color;
 
 #[test]
@@ -6,10 +9,9 @@ fn ppm_header() {
     let c = Canvas::new(5, 3);
     let mut output = Vec::new();
 
-    match render_as_ppm(&c, &mut output) {
-        Err(err) => panic!(err),
-        _ => (),
-    };
+    if let Err(err) = render_as_ppm(&c, &mut output) {
+        panic!("{}", err);
+    }
 
     let string_output = String::from_utf8(output).unwrap();
 
@@ -43,9 +45,8 @@ fn ppm_body() {
 0 0 0 0 0 0 0 0 0 0 0 0 0 0 255";
 
     let mut output = Vec::new();
-    match render_as_ppm(&c, &mut output) {
-        Err(err) => panic!(err),
-        _ => (),
+    if let Err(err) = render_as_ppm(&c, &mut output) {
+        panic!("{}", err);
     }
 
     let string_output = String::from_utf8(output).unwrap();
@@ -77,9 +78,8 @@ fn ppm_wrap_lines() {
         .trim();
 
     let mut output = Vec::new();
-    match render_as_ppm(&canvas, &mut output) {
-        Err(err) => panic!(err),
-        _ => (),
+    if let Err(err) = render_as_ppm(&canvas, &mut output) {
+        panic!("{}", err);
     }
 
     let string_output = String::from_utf8(output).unwrap();
@@ -97,10 +97,9 @@ fn ppm_ends_with_newline() {
     let canvas = Canvas::new(5, 3);
     let mut output = Vec::new();
 
-    match render_as_ppm(&canvas, &mut output) {
-        Err(err) => panic!(err),
-        _ => (),
-    };
+    if let Err(err) = render_as_ppm(&canvas, &mut output) {
+        panic!("{}", err);
+    }
 
     let string_output = String::from_utf8(output).unwrap();
 
@@ -109,3 +108,39 @@ fn ppm_ends_with_newline() {
         "Output does not end with a newline."
     );
 }
+
+#[test]
+fn pam_header() {
+    let c = Canvas::new(5, 3);
+    let mut output = Vec::new();
+
+    render_as_pam(&c, &mut output).unwrap();
+
+    let want_header = b"P7
+WIDTH 5
+HEIGHT 3
+DEPTH 4
+MAXVAL 255
+TUPLTYPE RGB_ALPHA
+ENDHDR
+";
+    assert!(output.starts_with(want_header));
+    assert_eq!(output.len(), want_header.len() + 5 * 3 * 4);
+}
+
+#[test]
+fn pam_body() {
+    let mut c = Canvas::new(2, 2);
+    c.write_pixel(1, 0, Color::new(1.5, 0.5, 0));
+    c.write_alpha(1, 0, 0.5);
+    c.write_alpha(0, 1, 0.0);
+
+    let mut output = Vec::new();
+    render_as_pam(&c, &mut output).unwrap();
+
+    let body = &output[output.len() - 16..];
+    assert_eq!(
+        body,
+        &[0, 0, 0, 255, 255, 128, 0, 128, 0, 0, 0, 0, 0, 0, 0, 255]
+    );
+}
@@ -26,3 +26,31 @@ fn write_pixel() {
 
     assert_eq!(c.pixel_at(2, 3), red);
 }
+
+#[test]
+fn write_alpha() {
+    let mut c = Canvas::new(10, 20);
+
+    c.write_alpha(2, 3, 0.25);
+
+    assert_eq!(c.alpha_at(2, 3), 0.25);
+    assert_eq!(c.alpha_at(3, 2), 1.0);
+}
+
+#[test]
+fn from_pixels_with_alpha() {
+    let c = Canvas::from_pixels_with_alpha(
+        vec![vec![Color::new(1, 0, 0)], vec![Color::new(0, 1, 0)]],
+        vec![vec![0.0], vec![1.0]],
+    );
+
+    assert_eq!(c.width(), 2);
+    assert_eq!(c.alpha_at(0, 0), 0.0);
+    assert_eq!(c.alpha_at(1, 0), 1.0);
+}
+
+#[test]
+#[should_panic]
+fn from_pixels_with_alpha_mismatched() {
+    Canvas::from_pixels_with_alpha(vec![vec![Color::new(1, 0, 0)]], vec![vec![1.0, 1.0]]);
+}
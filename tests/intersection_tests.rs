@@ -1,8 +1,10 @@
 use raytracer::{
     intersections::{Intersection, Intersections},
     linear::{Matrix, Tuple},
-    objects::{Shape, Sphere},
-    Ray,
+    noise::Perlin,
+    objects::{Mesh, Plane, Shape, Sphere},
+    patterns::{BumpMap, Gradient},
+    Color, Fresnel, Material, Ray, ThinFilm,
 };
 
 #[test]
@@ -81,7 +83,7 @@ fn prepare_info_inside_hit() {
 
     assert_eq!(info.point(), Tuple::new_point(0, 0, 1));
     assert_eq!(info.eye_vec(), Tuple::new_vector(0, 0, -1));
-    assert_eq!(info.inside(), true);
+    assert!(info.inside());
     assert_eq!(info.normal_vec(), Tuple::new_vector(0, 0, -1));
 }
 
@@ -97,3 +99,340 @@ fn prepare_info_offset_point() {
     assert!(info.over_point().z() < -1e-5_f64 / 2.0);
     assert!(info.point().z() > info.over_point().z());
 }
+
+#[test]
+fn prepare_info_reflect_vec() {
+    let shape = Plane::default();
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0, 1, -1),
+        Tuple::new_vector(0.0, -sqrt_2_over_2, sqrt_2_over_2),
+    );
+    let i = Intersection::new(2.0_f64.sqrt(), &shape);
+
+    let info = i.prepare_info(&r);
+
+    assert_eq!(
+        info.reflect_vec(),
+        Tuple::new_vector(0.0, sqrt_2_over_2, sqrt_2_over_2)
+    );
+}
+
+fn glass_sphere() -> Sphere {
+    let mut sphere = Sphere::default();
+    sphere.set_material(
+        Material::default()
+            .with_transparency(1.0)
+            .with_refractive_index(1.5),
+    );
+
+    sphere
+}
+
+#[test]
+fn prepare_info_with_refractive_indices() {
+    let mut a = glass_sphere();
+    a.set_transform(Matrix::scaling(2, 2, 2));
+    let mut b = glass_sphere();
+    b.set_transform(Matrix::translation(0.0, 0.0, -0.25));
+    b.set_material(b.material().with_refractive_index(2.0));
+    let mut c = glass_sphere();
+    c.set_transform(Matrix::translation(0.0, 0.0, 0.25));
+    c.set_material(c.material().with_refractive_index(2.5));
+
+    let r = Ray::new(Tuple::new_point(0, 0, -4), Tuple::new_vector(0, 0, 1));
+    let intersections = Intersections::new(vec![
+        Intersection::new(2.0, &a),
+        Intersection::new(2.75, &b),
+        Intersection::new(3.25, &c),
+        Intersection::new(4.75, &b),
+        Intersection::new(5.25, &c),
+        Intersection::new(6.0, &a),
+    ]);
+
+    let expected = [
+        (1.0, 1.5),
+        (1.5, 2.0),
+        (2.0, 2.5),
+        (2.5, 2.5),
+        (2.5, 1.5),
+        (1.5, 1.0),
+    ];
+
+    for (index, (n1, n2)) in expected.iter().enumerate() {
+        let info = intersections[index].prepare_info_with(&r, &intersections);
+
+        assert_eq!(info.n1(), *n1, "n1 at intersection {}", index);
+        assert_eq!(info.n2(), *n2, "n2 at intersection {}", index);
+    }
+}
+
+#[test]
+fn prepare_info_single_intersection_indices() {
+    let shape = glass_sphere();
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let i = Intersection::new(4.0, &shape);
+
+    let info = i.prepare_info(&r);
+
+    assert_eq!(info.n1(), 1.0);
+    assert_eq!(info.n2(), 1.5);
+}
+
+#[test]
+fn prepare_info_under_point() {
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let mut shape = glass_sphere();
+    shape.set_transform(Matrix::translation(0, 0, 1));
+    let i = Intersection::new(5.0, &shape);
+    let intersections = Intersections::new(vec![i]);
+
+    let info = i.prepare_info_with(&r, &intersections);
+
+    assert!(info.under_point().z() > 1e-5_f64 / 2.0);
+    assert!(info.point().z() < info.under_point().z());
+}
+
+#[test]
+fn schlick_total_internal_reflection() {
+    let shape = glass_sphere();
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0.0, 0.0, sqrt_2_over_2),
+        Tuple::new_vector(0, 1, 0),
+    );
+    let intersections = Intersections::new(vec![
+        Intersection::new(-sqrt_2_over_2, &shape),
+        Intersection::new(sqrt_2_over_2, &shape),
+    ]);
+
+    let info = intersections[1].prepare_info_with(&r, &intersections);
+
+    assert_eq!(info.schlick(), 1.0);
+    assert_eq!(info.fresnel(), 1.0);
+}
+
+#[test]
+fn schlick_perpendicular_view() {
+    let shape = glass_sphere();
+    let r = Ray::new(Tuple::new_point(0, 0, 0), Tuple::new_vector(0, 1, 0));
+    let intersections = Intersections::new(vec![
+        Intersection::new(-1.0, &shape),
+        Intersection::new(1.0, &shape),
+    ]);
+
+    let info = intersections[1].prepare_info_with(&r, &intersections);
+
+    assert!((info.schlick() - 0.04).abs() < 1e-5);
+    assert!((info.fresnel() - 0.04).abs() < 1e-5);
+}
+
+#[test]
+fn schlick_small_angle_denser_material() {
+    let shape = glass_sphere();
+    let r = Ray::new(
+        Tuple::new_point(0.0, 0.99, -2.0),
+        Tuple::new_vector(0, 0, 1),
+    );
+    let intersections = Intersections::new(vec![Intersection::new(1.8589, &shape)]);
+
+    let info = intersections[0].prepare_info_with(&r, &intersections);
+
+    assert!((info.schlick() - 0.48873).abs() < 1e-5);
+}
+
+#[test]
+fn fresnel_increases_towards_grazing_angles() {
+    let shape = glass_sphere();
+    let reflectance_at = |height: f64| {
+        let r = Ray::new(
+            Tuple::new_point(0.0, height, -2.0),
+            Tuple::new_vector(0, 0, 1),
+        );
+        let t = 2.0 - (1.0 - height * height).sqrt();
+        let intersections = Intersections::new(vec![Intersection::new(t, &shape)]);
+
+        intersections[0]
+            .prepare_info_with(&r, &intersections)
+            .fresnel()
+    };
+
+    let head_on = reflectance_at(0.0);
+    let angled = reflectance_at(0.7);
+    let grazing = reflectance_at(0.99);
+
+    assert!(head_on < angled && angled < grazing);
+    assert!(grazing < 1.0);
+}
+
+#[test]
+fn reflectance_uses_material_model() {
+    let mut shape = glass_sphere();
+    let r = Ray::new(
+        Tuple::new_point(0.0, 0.99, -2.0),
+        Tuple::new_vector(0, 0, 1),
+    );
+    let intersections = Intersections::new(vec![Intersection::new(1.8589, &shape)]);
+    let schlick = intersections[0].prepare_info_with(&r, &intersections);
+    assert_eq!(schlick.reflectance(), schlick.schlick());
+
+    shape.set_material(shape.material().with_fresnel(Fresnel::Exact));
+    let intersections = Intersections::new(vec![Intersection::new(1.8589, &shape)]);
+    let exact = intersections[0].prepare_info_with(&r, &intersections);
+
+    assert_eq!(exact.reflectance(), exact.fresnel());
+}
+
+#[test]
+fn spectral_reflectance_without_thin_film() {
+    let shape = glass_sphere();
+    let r = Ray::new(
+        Tuple::new_point(0.0, 0.99, -2.0),
+        Tuple::new_vector(0, 0, 1),
+    );
+    let intersections = Intersections::new(vec![Intersection::new(1.8589, &shape)]);
+
+    let info = intersections[0].prepare_info_with(&r, &intersections);
+    let reflectance = info.reflectance();
+
+    assert_eq!(
+        info.spectral_reflectance(),
+        Color::new(reflectance, reflectance, reflectance)
+    );
+}
+
+#[test]
+fn spectral_reflectance_thin_film_shifts_with_view_angle() {
+    let mut shape = glass_sphere();
+    shape.set_material(shape.material().with_thin_film(ThinFilm::new(400.0, 1.33)));
+    let reflectance_at = |height: f64| {
+        let r = Ray::new(
+            Tuple::new_point(0.0, height, -2.0),
+            Tuple::new_vector(0, 0, 1),
+        );
+        let t = 2.0 - (1.0 - height * height).sqrt();
+        let intersections = Intersections::new(vec![Intersection::new(t, &shape)]);
+
+        intersections[0]
+            .prepare_info_with(&r, &intersections)
+            .spectral_reflectance()
+    };
+
+    let head_on = reflectance_at(0.0);
+    let angled = reflectance_at(0.7);
+
+    assert_ne!(head_on.red(), head_on.blue());
+    assert_ne!(head_on, angled);
+}
+
+fn bumpy_plane() -> Plane {
+    // The height rises by one unit along the x-axis, so the shading normal
+    // tilts halfway towards the negative x-axis.
+    let mut plane = Plane::default();
+    plane.set_material(Material::default().with_normal_map(BumpMap::new(
+        Gradient::new(Color::new(0, 0, 0), Color::new(1, 1, 1)),
+        1.0,
+    )));
+
+    plane
+}
+
+#[test]
+fn prepare_info_shading_normal_without_normal_map() {
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let shape = Sphere::default();
+    let i = Intersection::new(4.0, &shape);
+
+    let info = i.prepare_info(&r);
+
+    assert_eq!(info.shading_normal_vec(), info.normal_vec());
+}
+
+#[test]
+fn prepare_info_shading_normal_uses_normal_map() {
+    let plane = bumpy_plane();
+    let r = Ray::new(Tuple::new_point(0.5, 1.0, 0.5), Tuple::new_vector(0, -1, 0));
+    let i = Intersection::new(1.0, &plane);
+
+    let info = i.prepare_info(&r);
+
+    assert_eq!(info.normal_vec(), Tuple::new_vector(0, 1, 0));
+    assert_eq!(
+        info.shading_normal_vec(),
+        Tuple::new_vector(-1, 1, 0).normalized()
+    );
+    assert_eq!(info.over_point(), Tuple::new_point(0.5, 1e-5, 0.5));
+}
+
+#[test]
+fn prepare_info_shading_normal_flipped_inside() {
+    let plane = bumpy_plane();
+    let r = Ray::new(Tuple::new_point(0.5, -1.0, 0.5), Tuple::new_vector(0, 1, 0));
+    let i = Intersection::new(1.0, &plane);
+
+    let info = i.prepare_info(&r);
+
+    assert_eq!(info.normal_vec(), Tuple::new_vector(0, -1, 0));
+    assert_eq!(
+        info.shading_normal_vec(),
+        Tuple::new_vector(1, -1, 0).normalized()
+    );
+}
+
+#[test]
+fn prepare_info_noise_only_tilts_shading_normal() {
+    let mut sphere = Sphere::default();
+    sphere.set_material(
+        Material::default().with_normal_map(BumpMap::from_noise(Perlin::new(3), 0.5)),
+    );
+    let r = Ray::new(Tuple::new_point(0.3, 0.2, -5.0), Tuple::new_vector(0, 0, 1));
+    let i = sphere.intersect(&r)[0];
+
+    let info = i.prepare_info(&r);
+
+    assert_eq!(info.normal_vec(), sphere.normal_at(&info.point()));
+    assert_ne!(info.shading_normal_vec(), info.normal_vec());
+}
+
+#[test]
+fn prepare_info_normal_map_uses_face_that_was_hit() {
+    // Two faces on top of each other, facing opposite ways, so only the
+    // intersection can tell them apart.
+    let vertices = vec![
+        Tuple::new_point(0, 0, 0),
+        Tuple::new_point(1, 0, 0),
+        Tuple::new_point(0, 1, 0),
+    ];
+    let mut mesh = Mesh::new(vertices, vec![[0, 1, 2], [0, 2, 1]]);
+    mesh.set_material(Material::default().with_normal_map(BumpMap::new(
+        Gradient::new(Color::new(0, 0, 0), Color::new(1, 1, 1)),
+        0.0,
+    )));
+    let r = Ray::new(
+        Tuple::new_point(0.25, 0.25, -1.0),
+        Tuple::new_vector(0, 0, 1),
+    );
+    let i = Intersection::with_face(1.0, &mesh, 1, 0.25, 0.25);
+
+    let info = i.prepare_info(&r);
+
+    // The flat bump map leaves the normal of the face that was hit alone.
+    assert_eq!(info.normal_vec(), Tuple::new_vector(0, 0, -1));
+    assert_eq!(info.shading_normal_vec(), info.normal_vec());
+}
+
+#[test]
+fn prepare_info_refracted_medium() {
+    let shape = glass_sphere();
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let intersections = Intersections::new(vec![
+        Intersection::new(4.0, &shape),
+        Intersection::new(6.0, &shape),
+    ]);
+
+    let entering = intersections[0].prepare_info_with(&r, &intersections);
+    let leaving = intersections[1].prepare_info_with(&r, &intersections);
+
+    assert!(entering.refracted_medium() == Some(&shape as &dyn Shape));
+    assert!(leaving.refracted_medium().is_none());
+}
@@ -0,0 +1,437 @@
+use std::io::ErrorKind;
+
+use raytracer::{
+    linear::{Matrix, Tuple},
+    loaders::{load_ply, load_ppm, load_stl},
+    objects::{Shape, Sphere},
+    patterns::{Pattern, Texture, TextureFilter, TextureMap, UvMapping},
+    Color, Ray, World,
+};
+
+const ASCII_STL: &str = "solid two triangles
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 1 1 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 1 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid two triangles
+";
+
+fn binary_stl(triangles: &[[[f32; 3]; 3]]) -> Vec<u8> {
+    // Binary files may start with "solid" too, which must not confuse the
+    // loader into parsing them as ASCII.
+    let mut data = b"solid binary".to_vec();
+    data.resize(80, 0);
+    data.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+
+    for triangle in triangles.iter() {
+        data.extend_from_slice(&[0; 12]);
+        for vertex in triangle.iter() {
+            for component in vertex.iter() {
+                data.extend_from_slice(&component.to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&[0; 2]);
+    }
+
+    data
+}
+
+#[test]
+fn load_stl_ascii() {
+    let mesh = load_stl(ASCII_STL.as_bytes()).unwrap();
+
+    assert_eq!(mesh.face_count(), 2);
+    assert!(mesh.degenerate_faces().is_empty());
+}
+
+#[test]
+fn load_stl_binary() {
+    let data = binary_stl(&[
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+        [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
+    ]);
+
+    let mesh = load_stl(data.as_slice()).unwrap();
+
+    assert_eq!(mesh.face_count(), 2);
+    assert_eq!(mesh.degenerate_faces(), &[1]);
+}
+
+#[test]
+fn load_stl_binary_is_intersectable() {
+    let data = binary_stl(&[[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]]]);
+    let mesh = load_stl(data.as_slice()).unwrap();
+    let r = Ray::new(
+        Tuple::new_point(0.75, 0.25, -2.0),
+        Tuple::new_vector(0, 0, 1),
+    );
+
+    let intersections = mesh.intersect(&r);
+
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].t(), 2.0);
+}
+
+#[test]
+fn load_stl_truncated() {
+    let truncated = &ASCII_STL[..ASCII_STL.len() / 2];
+
+    let err = load_stl(truncated.as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_stl_invalid_number() {
+    let stl = ASCII_STL.replacen("vertex 1 0 0", "vertex 1 zero 0", 1);
+
+    let err = load_stl(stl.as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_stl_not_stl() {
+    let err = load_stl("hello world".as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_ply_ascii_with_normals() {
+    let ply = "ply
+format ascii 1.0
+comment a single triangle with normals
+element vertex 3
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+element face 1
+property list uchar int vertex_index
+end_header
+0 0 0 0 0 1
+1 0 0 0 0 1
+0 1 0 0 0 1
+3 0 1 2
+";
+
+    let mesh = load_ply(ply.as_bytes()).unwrap();
+    let n = mesh.normal_at(&Tuple::new_point(0.25, 0.25, 0.0));
+
+    assert_eq!(mesh.face_count(), 1);
+    assert_eq!(n, Tuple::new_vector(0, 0, 1));
+}
+
+#[test]
+fn load_ply_zero_length_normal() {
+    let ply = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+element face 1
+property list uchar int vertex_index
+end_header
+0 0 0 0 0 0
+1 0 0 0 0 2
+0 1 0 0 0 1
+3 0 1 2
+";
+
+    let mesh = load_ply(ply.as_bytes()).unwrap();
+    let n = mesh.normal_at(&Tuple::new_point(0.0, 0.0, 0.0));
+
+    assert_eq!(n, Tuple::new_vector(0, 0, 1));
+}
+
+#[test]
+fn load_ply_skips_unknown_elements_and_properties() {
+    let ply = "ply
+format ascii 1.0
+element material 1
+property uchar red
+element vertex 3
+property float x
+property float y
+property float z
+property uchar red
+element face 1
+property list uchar int vertex_indices
+property uchar flags
+element edge 1
+property int vertex1
+property int vertex2
+end_header
+255
+0 0 0 1
+1 0 0 2
+0 1 0 3
+3 0 1 2 7
+0 1
+";
+
+    let mesh = load_ply(ply.as_bytes()).unwrap();
+
+    assert_eq!(mesh.face_count(), 1);
+}
+
+#[test]
+fn load_ply_binary_little_endian() {
+    let mut data = b"ply
+format binary_little_endian 1.0
+element vertex 3
+property double x
+property double y
+property double z
+element face 1
+property list uchar uint vertex_indices
+end_header
+"
+    .to_vec();
+    for vertex in [[0.0_f64, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]].iter() {
+        for component in vertex.iter() {
+            data.extend_from_slice(&component.to_le_bytes());
+        }
+    }
+    data.push(3);
+    for index in [0_u32, 1, 2].iter() {
+        data.extend_from_slice(&index.to_le_bytes());
+    }
+
+    let mesh = load_ply(data.as_slice()).unwrap();
+
+    assert_eq!(mesh.face_count(), 1);
+}
+
+#[test]
+fn load_ply_binary_big_endian() {
+    let mut data = b"ply
+format binary_big_endian 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar short vertex_indices
+end_header
+"
+    .to_vec();
+    for vertex in [[0.0_f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]].iter() {
+        for component in vertex.iter() {
+            data.extend_from_slice(&component.to_be_bytes());
+        }
+    }
+    data.push(3);
+    for index in [0_i16, 1, 2].iter() {
+        data.extend_from_slice(&index.to_be_bytes());
+    }
+
+    let mesh = load_ply(data.as_slice()).unwrap();
+
+    assert_eq!(mesh.face_count(), 1);
+}
+
+#[test]
+fn load_ply_truncated_binary() {
+    let data = b"ply
+format binary_little_endian 1.0
+element vertex 3
+property float x
+property float y
+property float z
+end_header
+\x00\x00\x00\x00";
+
+    let err = load_ply(&data[..]).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_ply_vertex_index_out_of_range() {
+    let ply = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+1 0 0
+0 1 0
+3 0 1 3
+";
+
+    let err = load_ply(ply.as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_ply_invalid_header_line() {
+    let ply = "ply
+format ascii 1.0
+end_header_is_not_this
+element vertex 0
+property float x
+property float y
+property float z
+end_header
+";
+
+    let err = load_ply(ply.as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("end_header_is_not_this"));
+}
+
+#[test]
+fn load_ply_missing_format() {
+    let ply = "ply
+element vertex 0
+property float x
+property float y
+property float z
+end_header
+";
+
+    let err = load_ply(ply.as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn loaded_mesh_in_world() {
+    let mut mesh = load_stl(ASCII_STL.as_bytes()).unwrap();
+    mesh.set_transform(Matrix::translation(0, 0, 5));
+
+    let mut world = World::new();
+    world.add_object(&mesh);
+
+    let r = Ray::new(Tuple::new_point(0.5, 0.5, 0.0), Tuple::new_vector(0, 0, 1));
+    let intersections = world.intersect(&r);
+
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].t(), 5.0);
+}
+
+#[test]
+fn load_ppm_plain() {
+    let ppm = "P3
+# A comment before the dimensions.
+3 2 # and one after them
+255
+255 0 0   0 255 0   0 0 255
+255 255 255   0 0 0   127 127 127
+";
+
+    let canvas = load_ppm(ppm.as_bytes()).unwrap();
+
+    assert_eq!(canvas.width(), 3);
+    assert_eq!(canvas.height(), 2);
+    assert_eq!(canvas.pixel_at(0, 0), Color::new(1, 0, 0));
+    assert_eq!(canvas.pixel_at(2, 0), Color::new(0, 0, 1));
+    assert_eq!(canvas.pixel_at(0, 1), Color::new(1, 1, 1));
+    assert_eq!(
+        canvas.pixel_at(2, 1),
+        Color::new(127.0 / 255.0, 127.0 / 255.0, 127.0 / 255.0)
+    );
+}
+
+#[test]
+fn load_ppm_plain_max_value() {
+    let ppm = "P3 1 1 100 50 0 100";
+
+    let canvas = load_ppm(ppm.as_bytes()).unwrap();
+
+    assert_eq!(canvas.pixel_at(0, 0), Color::new(0.5, 0.0, 1.0));
+}
+
+#[test]
+fn load_ppm_raw() {
+    let mut data = b"P6\n# raw pixels\n2 1\n255\n".to_vec();
+    data.extend_from_slice(&[255, 0, 0, 0, 51, 255]);
+
+    let canvas = load_ppm(data.as_slice()).unwrap();
+
+    assert_eq!(canvas.width(), 2);
+    assert_eq!(canvas.pixel_at(0, 0), Color::new(1, 0, 0));
+    assert_eq!(canvas.pixel_at(1, 0), Color::new(0.0, 0.2, 1.0));
+}
+
+#[test]
+fn load_ppm_raw_sixteen_bit() {
+    let mut data = b"P6 1 1 65535\n".to_vec();
+    data.extend_from_slice(&[0xff, 0xff, 0x80, 0x00, 0x00, 0x00]);
+
+    let canvas = load_ppm(data.as_slice()).unwrap();
+
+    assert_eq!(
+        canvas.pixel_at(0, 0),
+        Color::new(1.0, 32768.0 / 65535.0, 0.0)
+    );
+}
+
+#[test]
+fn load_ppm_raw_truncated() {
+    let mut data = b"P6 2 2 255\n".to_vec();
+    data.extend_from_slice(&[0; 9]);
+
+    let err = load_ppm(data.as_slice()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_ppm_sample_too_large() {
+    let err = load_ppm("P3 1 1 15 16 0 0".as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_ppm_unsupported_format() {
+    let err = load_ppm("P2 1 1 255 0".as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("P2"));
+}
+
+#[test]
+fn loaded_texture_on_sphere() {
+    // The left half of the image is red and the right half is blue.
+    let image = load_ppm("P3 2 1 1 1 0 0 0 0 1".as_bytes()).unwrap();
+    let pattern = TextureMap::new(
+        Texture::new(image).with_filter(TextureFilter::Nearest),
+        UvMapping::Spherical,
+    );
+    let mut sphere = Sphere::default();
+    sphere.set_transform(Matrix::scaling(2, 2, 2));
+
+    // Points on the sphere's surface facing the negative x-axis map to the
+    // right of the image, and vice versa.
+    let left = pattern.pattern_at_shape(&sphere, &Tuple::new_point(-2, 0, 0));
+    let right = pattern.pattern_at_shape(&sphere, &Tuple::new_point(2, 0, 0));
+
+    assert_eq!(left, Color::new(0, 0, 1));
+    assert_eq!(right, Color::new(1, 0, 0));
+}
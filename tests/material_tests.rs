@@ -1,7 +1,14 @@
-use raytracer::{lights::PointLight, linear::Tuple, Color, Material};
+use raytracer::{
+    lights::{DirectionalLight, PointLight, SpotLight},
+    linear::{Matrix, Tuple},
+    objects::{Plane, Shape, Sphere},
+    patterns::Stripe,
+    Color, DiffuseModel, Material, ShadingModel, SpecularModel,
+};
 
 #[test]
 fn light_eye_between_light_and_surface() {
+    let object = Sphere::default();
     let m = Material::default();
     let position = Tuple::new_point(0, 0, 0);
 
@@ -9,29 +16,67 @@ fn light_eye_between_light_and_surface() {
     let normal_v = Tuple::new_vector(0, 0, -1);
     let light = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
 
-    let result = m.light(&light, &position, &eye_v, &normal_v, false);
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, false);
 
     assert_eq!(result, Color::new(1.9, 1.9, 1.9));
 }
 
+#[test]
+fn light_directional_light() {
+    let object = Sphere::default();
+    let m = Material::default();
+    let position = Tuple::new_point(3, 0, 0);
+
+    let eye_v = Tuple::new_vector(0, 0, -1);
+    let normal_v = Tuple::new_vector(0, 0, -1);
+    let light = DirectionalLight::new(Tuple::new_vector(0, 0, 1), Color::new(1, 1, 1));
+
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, false);
+
+    assert_eq!(result, Color::new(1.9, 1.9, 1.9));
+}
+
+#[test]
+fn light_spot_light_outside_cone() {
+    let object = Sphere::default();
+    let m = Material::default();
+    let position = Tuple::new_point(0, 0, 0);
+
+    let eye_v = Tuple::new_vector(0, 0, -1);
+    let normal_v = Tuple::new_vector(0, 0, -1);
+    let light = SpotLight::new(
+        Tuple::new_point(0, 0, -10),
+        Tuple::new_vector(0, 1, 0),
+        0.1,
+        0.2,
+        Color::new(1, 1, 1),
+    );
+
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, false);
+
+    assert_eq!(result, Color::new(0.1, 0.1, 0.1));
+}
+
 #[test]
 fn light_eye_between_light_and_surface_with_eye_45_offset() {
+    let object = Sphere::default();
     let m = Material::default();
     let position = Tuple::new_point(0, 0, 0);
 
-    let sqrt_2_over_2 = (2.0 as f64).sqrt() / 2.0;
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
 
     let eye_v = Tuple::new_vector(0.0, sqrt_2_over_2, -sqrt_2_over_2);
     let normal_v = Tuple::new_vector(0, 0, -1);
     let light = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
 
-    let result = m.light(&light, &position, &eye_v, &normal_v, false);
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, false);
 
     assert_eq!(result, Color::new(1.0, 1.0, 1.0));
 }
 
 #[test]
 fn light_eye_opposite_surface_with_light_45_offset() {
+    let object = Sphere::default();
     let m = Material::default();
     let position = Tuple::new_point(0, 0, 0);
 
@@ -39,29 +84,31 @@ fn light_eye_opposite_surface_with_light_45_offset() {
     let normal_v = Tuple::new_vector(0, 0, -1);
     let light = PointLight::new(Tuple::new_point(0, 10, -10), Color::new(1, 1, 1));
 
-    let result = m.light(&light, &position, &eye_v, &normal_v, false);
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, false);
 
     assert_eq!(result, Color::new(0.7364, 0.7364, 0.7364));
 }
 
 #[test]
 fn light_eye_in_path_of_reflection_vector() {
+    let object = Sphere::default();
     let m = Material::default();
     let position = Tuple::new_point(0, 0, 0);
 
-    let sqrt_2_over_2 = (2.0 as f64).sqrt() / 2.0;
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
 
     let eye_v = Tuple::new_vector(0.0, -sqrt_2_over_2, -sqrt_2_over_2);
     let normal_v = Tuple::new_vector(0, 0, -1);
     let light = PointLight::new(Tuple::new_point(0, 10, -10), Color::new(1, 1, 1));
 
-    let result = m.light(&light, &position, &eye_v, &normal_v, false);
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, false);
 
     assert_eq!(result, Color::new(1.6364, 1.6364, 1.6364));
 }
 
 #[test]
 fn light_with_light_behind_surface() {
+    let object = Sphere::default();
     let m = Material::default();
     let position = Tuple::new_point(0, 0, 0);
 
@@ -69,13 +116,14 @@ fn light_with_light_behind_surface() {
     let normal_v = Tuple::new_vector(0, 0, -1);
     let light = PointLight::new(Tuple::new_point(0, 0, 10), Color::new(1, 1, 1));
 
-    let result = m.light(&light, &position, &eye_v, &normal_v, false);
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, false);
 
     assert_eq!(result, Color::new(0.1, 0.1, 0.1));
 }
 
 #[test]
 fn light_surface_in_shadow() {
+    let object = Sphere::default();
     let m = Material::default();
     let position = Tuple::new_point(0, 0, 0);
 
@@ -83,7 +131,384 @@ fn light_surface_in_shadow() {
     let normal_v = Tuple::new_vector(0, 0, -1);
     let light = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
 
-    let result = m.light(&light, &position, &eye_v, &normal_v, true);
+    let result = m.light(&object, &light, &position, &eye_v, &normal_v, true);
 
     assert_eq!(result, Color::new(0.1, 0.1, 0.1));
 }
+
+#[test]
+fn light_with_pattern() {
+    let object = Sphere::default();
+    let m = Material::default()
+        .with_pattern(Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0)))
+        .with_ambient(1.0)
+        .with_diffuse(0.0)
+        .with_specular(0.0);
+
+    let eye_v = Tuple::new_vector(0, 0, -1);
+    let normal_v = Tuple::new_vector(0, 0, -1);
+    let light = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
+
+    let c1 = m.light(
+        &object,
+        &light,
+        &Tuple::new_point(0.9, 0.0, 0.0),
+        &eye_v,
+        &normal_v,
+        false,
+    );
+    let c2 = m.light(
+        &object,
+        &light,
+        &Tuple::new_point(1.1, 0.0, 0.0),
+        &eye_v,
+        &normal_v,
+        false,
+    );
+
+    assert_eq!(c1, Color::new(1, 1, 1));
+    assert_eq!(c2, Color::new(0, 0, 0));
+}
+
+#[test]
+fn light_with_pattern_uses_object_transform() {
+    let mut object = Sphere::default();
+    object.set_transform(Matrix::scaling(2, 2, 2));
+    let m = Material::default()
+        .with_pattern(Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0)))
+        .with_ambient(1.0)
+        .with_diffuse(0.0)
+        .with_specular(0.0);
+
+    let eye_v = Tuple::new_vector(0, 0, -1);
+    let normal_v = Tuple::new_vector(0, 0, -1);
+    let light = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
+
+    // The world point is at x = 0.75 in object space, which is in the first
+    // stripe.
+    let c = m.light(
+        &object,
+        &light,
+        &Tuple::new_point(1.5, 0.0, 0.0),
+        &eye_v,
+        &normal_v,
+        false,
+    );
+
+    assert_eq!(c, Color::new(1, 1, 1));
+}
+
+#[test]
+fn materials_with_cloned_pattern_are_equal() {
+    let m1 =
+        Material::default().with_pattern(Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0)));
+    let m2 = m1.with_ambient(0.1);
+    let m3 =
+        Material::default().with_pattern(Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0)));
+
+    assert_eq!(m1, m2);
+    assert_ne!(m1, m3);
+}
+
+fn metallic_roughness(color: Color, metallic: f64, roughness: f64) -> Material {
+    Material::default()
+        .with_color(color)
+        .with_ambient(0.0)
+        .with_shading_model(ShadingModel::MetallicRoughness)
+        .with_metallic(metallic)
+        .with_roughness(roughness)
+}
+
+fn light_head_on(m: &Material, eye_v: Tuple) -> Color {
+    let object = Sphere::default();
+    let light = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
+
+    m.light(
+        &object,
+        &light,
+        &Tuple::new_point(0, 0, 0),
+        &eye_v,
+        &Tuple::new_vector(0, 0, -1),
+        false,
+    )
+}
+
+#[test]
+fn light_metallic_roughness_rough_dielectric_matches_phong_brightness() {
+    let m = metallic_roughness(Color::new(1, 1, 1), 0.0, 1.0);
+
+    let result = light_head_on(&m, Tuple::new_vector(0, 0, -1));
+
+    assert!(result.red() > 0.9 && result.red() < 1.1);
+    assert_eq!(result.red(), result.green());
+    assert_eq!(result.green(), result.blue());
+}
+
+#[test]
+fn light_metallic_roughness_metal_tints_highlight() {
+    let gold = Color::new(1.0, 0.78, 0.34);
+    let m = metallic_roughness(gold, 1.0, 0.3);
+
+    let result = light_head_on(&m, Tuple::new_vector(0, 0, -1));
+
+    // Metals have no diffuse reflection, so the color only comes from the
+    // highlight, which is tinted by the base color.
+    let ratio = result.green() / result.red();
+    assert!((ratio - 0.78).abs() < 1e-5);
+    assert!(result.red() > 1.0);
+}
+
+#[test]
+fn light_metallic_roughness_metal_no_diffuse() {
+    let m = metallic_roughness(Color::new(1, 1, 1), 1.0, 0.1);
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+
+    // Looking from far away from the reflection of the light leaves only the
+    // diffuse reflection, which metals don't have.
+    let result = light_head_on(&m, Tuple::new_vector(0.0, sqrt_2_over_2, -sqrt_2_over_2));
+
+    assert!(result.red() < 0.01);
+}
+
+#[test]
+fn light_metallic_roughness_smoother_surface_has_sharper_highlight() {
+    let smooth = metallic_roughness(Color::new(1, 1, 1), 1.0, 0.2);
+    let rough = metallic_roughness(Color::new(1, 1, 1), 1.0, 0.8);
+    let head_on = Tuple::new_vector(0, 0, -1);
+    let off_axis = Tuple::new_vector(0.0, 0.5, -1.0).normalized();
+
+    assert!(light_head_on(&smooth, head_on).red() > light_head_on(&rough, head_on).red());
+    assert!(light_head_on(&smooth, off_axis).red() < light_head_on(&rough, off_axis).red());
+}
+
+#[test]
+fn light_metallic_roughness_conserves_energy() {
+    // Integrate the reflected light over the hemisphere above the surface. An
+    // energy conserving model can never reflect more light than it receives,
+    // give or take the error from the integration. Very smooth surfaces have
+    // highlights too narrow to integrate accurately this way.
+    for &roughness in &[0.3, 0.6, 1.0] {
+        for &metallic in &[0.0, 1.0] {
+            let m = metallic_roughness(Color::new(1, 1, 1), metallic, roughness);
+            let steps = 200;
+            let mut reflected = 0.0;
+
+            for i in 0..steps {
+                let theta = (i as f64 + 0.5) / steps as f64 * std::f64::consts::FRAC_PI_2;
+                for j in 0..steps {
+                    let phi = (j as f64 + 0.5) / steps as f64 * 2.0 * std::f64::consts::PI;
+                    let eye_v = Tuple::new_vector(
+                        theta.sin() * phi.cos(),
+                        theta.sin() * phi.sin(),
+                        -theta.cos(),
+                    );
+
+                    let radiance = light_head_on(&m, eye_v).red() / std::f64::consts::PI;
+                    let solid_angle = theta.sin()
+                        * (std::f64::consts::FRAC_PI_2 / steps as f64)
+                        * (2.0 * std::f64::consts::PI / steps as f64);
+
+                    reflected += radiance * theta.cos() * solid_angle;
+                }
+            }
+
+            assert!(
+                reflected <= 1.005,
+                "roughness {} metallic {} reflected {}",
+                roughness,
+                metallic,
+                reflected
+            );
+        }
+    }
+}
+
+#[test]
+fn light_metallic_roughness_light_behind_surface() {
+    let object = Sphere::default();
+    let m = metallic_roughness(Color::new(1, 1, 1), 0.0, 0.5).with_ambient(0.1);
+    let light = PointLight::new(Tuple::new_point(0, 0, 10), Color::new(1, 1, 1));
+
+    let result = m.light(
+        &object,
+        &light,
+        &Tuple::new_point(0, 0, 0),
+        &Tuple::new_vector(0, 0, -1),
+        &Tuple::new_vector(0, 0, -1),
+        false,
+    );
+
+    assert_eq!(result, Color::new(0.1, 0.1, 0.1));
+}
+
+fn toon(bands: u32) -> Material {
+    Material::default()
+        .with_ambient(0.0)
+        .with_specular(0.0)
+        .with_shading_model(ShadingModel::Toon { bands })
+}
+
+fn light_from_angle(m: &Material, degrees: f64) -> Color {
+    let object = Sphere::default();
+    let radians = degrees.to_radians();
+    let light = PointLight::new(
+        Tuple::new_point(0.0, 10.0 * radians.sin(), -10.0 * radians.cos()),
+        Color::new(1, 1, 1),
+    );
+
+    m.light(
+        &object,
+        &light,
+        &Tuple::new_point(0, 0, 0),
+        &Tuple::new_vector(0, 0, -1),
+        &Tuple::new_vector(0, 0, -1),
+        false,
+    )
+}
+
+#[test]
+fn light_toon_quantizes_diffuse() {
+    let m = toon(3);
+
+    // cos(10°) ≈ 0.98, cos(40°) ≈ 0.77, cos(60°) = 0.5, and cos(80°) ≈ 0.17.
+    assert_eq!(light_from_angle(&m, 10.0), Color::new(0.9, 0.9, 0.9));
+    assert_eq!(light_from_angle(&m, 40.0), Color::new(0.9, 0.9, 0.9));
+    assert_eq!(light_from_angle(&m, 60.0), Color::new(0.6, 0.6, 0.6));
+    assert_eq!(light_from_angle(&m, 80.0), Color::new(0.3, 0.3, 0.3));
+}
+
+#[test]
+fn light_toon_unlit_side() {
+    let m = toon(3).with_ambient(0.1);
+
+    assert_eq!(light_from_angle(&m, 120.0), Color::new(0.1, 0.1, 0.1));
+}
+
+#[test]
+fn light_toon_hard_specular_cutoff() {
+    let m = toon(2).with_diffuse(0.0).with_specular(0.5);
+
+    // The highlight is at full strength until it suddenly disappears.
+    assert_eq!(light_from_angle(&m, 0.0), Color::new(0.5, 0.5, 0.5));
+    assert_eq!(light_from_angle(&m, 2.0), Color::new(0.5, 0.5, 0.5));
+    assert_eq!(light_from_angle(&m, 20.0), Color::new(0, 0, 0));
+}
+
+fn light_plane(m: &Material, light_position: Tuple, eye_v: Tuple) -> Color {
+    let object = Plane::default();
+    let light = PointLight::new(light_position, Color::new(1, 1, 1));
+
+    m.light(
+        &object,
+        &light,
+        &Tuple::new_point(0, 0, 0),
+        &eye_v.normalized(),
+        &Tuple::new_vector(0, 1, 0),
+        false,
+    )
+}
+
+#[test]
+fn light_blinn_phong_head_on_matches_phong() {
+    let m = Material::default().with_specular_model(SpecularModel::BlinnPhong);
+
+    let result = light_plane(&m, Tuple::new_point(0, 10, 0), Tuple::new_vector(0, 1, 0));
+
+    assert_eq!(result, Color::new(1.9, 1.9, 1.9));
+}
+
+#[test]
+fn light_blinn_phong_not_clipped() {
+    // With the light and eye on the same side, the reflected light points
+    // away from the eye, so a broad Phong highlight is clipped entirely.
+    let phong = Material::default()
+        .with_ambient(0.0)
+        .with_diffuse(0.0)
+        .with_shininess(1.0);
+    let blinn = phong.with_specular_model(SpecularModel::BlinnPhong);
+    let light_position = Tuple::new_point(0.0, 10.0, -10.0 * 3.0_f64.sqrt());
+    let eye_v = Tuple::new_vector(0.0, 1.0, -(3.0_f64.sqrt()));
+
+    assert_eq!(
+        light_plane(&phong, light_position, eye_v),
+        Color::new(0, 0, 0)
+    );
+    assert_eq!(
+        light_plane(&blinn, light_position, eye_v),
+        Color::new(0.45, 0.45, 0.45)
+    );
+}
+
+#[test]
+fn light_ward_head_on() {
+    let m = Material::default().with_specular_model(SpecularModel::Ward {
+        roughness_x: 0.5,
+        roughness_y: 0.5,
+    });
+
+    let result = light_plane(&m, Tuple::new_point(0, 10, 0), Tuple::new_vector(0, 1, 0));
+
+    assert_eq!(result, Color::new(1.9, 1.9, 1.9));
+}
+
+#[test]
+fn light_ward_anisotropic_highlight() {
+    // The plane's tangent is the x-axis, which the highlight is stretched
+    // along.
+    let m = Material::default()
+        .with_ambient(0.0)
+        .with_diffuse(0.0)
+        .with_specular_model(SpecularModel::Ward {
+            roughness_x: 0.4,
+            roughness_y: 0.05,
+        });
+    let light_position = Tuple::new_point(0, 10, 0);
+
+    let along_tangent = light_plane(&m, light_position, Tuple::new_vector(0.3, 1.0, 0.0));
+    let along_bitangent = light_plane(&m, light_position, Tuple::new_vector(0.0, 1.0, 0.3));
+
+    assert!(along_tangent.red() > 0.1);
+    assert!(along_bitangent.red() < 0.01);
+}
+
+#[test]
+fn light_oren_nayar_no_roughness_matches_lambert() {
+    let m = Material::default().with_diffuse_model(DiffuseModel::OrenNayar { roughness: 0.0 });
+
+    let result = light_plane(&m, Tuple::new_point(0, 10, -10), Tuple::new_vector(0, 1, 1));
+
+    let lambert = light_plane(
+        &Material::default(),
+        Tuple::new_point(0, 10, -10),
+        Tuple::new_vector(0, 1, 1),
+    );
+    assert_eq!(result, lambert);
+}
+
+#[test]
+fn light_oren_nayar_flattens_rough_surface() {
+    let m = Material::default()
+        .with_ambient(0.0)
+        .with_specular(0.0)
+        .with_diffuse_model(DiffuseModel::OrenNayar { roughness: 0.5 });
+    let lambert = m.with_diffuse_model(DiffuseModel::Lambert);
+
+    // Lit and viewed head-on, a rough surface is darker than a Lambertian
+    // one.
+    let head_on = (Tuple::new_point(0, 10, 0), Tuple::new_vector(0, 1, 0));
+    assert!(
+        light_plane(&m, head_on.0, head_on.1).red()
+            < light_plane(&lambert, head_on.0, head_on.1).red()
+    );
+
+    // Lit and viewed from a grazing angle, light is scattered back towards
+    // the light, so it's brighter.
+    let grazing = (
+        Tuple::new_point(0.0, 10.0, -10.0 * 3.0_f64.sqrt()),
+        Tuple::new_vector(0.0, 1.0, -(3.0_f64.sqrt())),
+    );
+    assert!(
+        light_plane(&m, grazing.0, grazing.1).red()
+            > light_plane(&lambert, grazing.0, grazing.1).red()
+    );
+}
@@ -33,7 +33,7 @@ fn normalize_arbitrary_vector() {
 
     // The vector has a magnitude of sqrt(14), so the normalized vector's
     // components should be scaled by that factor.
-    let magnitude = (14 as f64).sqrt();
+    let magnitude = 14_f64.sqrt();
     let want = Tuple::new_vector(1.0 / magnitude, 2.0 / magnitude, 3.0 / magnitude);
 
     assert_eq!(vector.normalized(), want);
@@ -42,7 +42,7 @@ fn normalize_arbitrary_vector() {
 #[test]
 fn magnitude() {
     let vector = Tuple::new_vector(1, 2, 3);
-    let want = (14 as f64).sqrt();
+    let want = 14_f64.sqrt();
 
     assert!(approx_eq!(f64, vector.magnitude(), want));
 }
@@ -50,7 +50,7 @@ fn magnitude() {
 #[test]
 fn magnitude_negative_components() {
     let vector = Tuple::new_vector(-1, -2, -3);
-    let want = (14 as f64).sqrt();
+    let want = 14_f64.sqrt();
 
     assert!(approx_eq!(f64, vector.magnitude(), want));
 }
@@ -68,7 +68,7 @@ fn reflected_over_45deg_approach() {
 #[test]
 fn reflected_over_slanted_surface() {
     let v = Tuple::new_vector(0, -1, 0);
-    let sqrt_2_over_2 = (2.0 as f64).sqrt() / 2.0;
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
     let n = Tuple::new_vector(sqrt_2_over_2, sqrt_2_over_2, 0.0);
 
     let r = v.reflected_over(&n);
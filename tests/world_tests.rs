@@ -1,9 +1,13 @@
+use std::f64::consts::PI;
+
 use raytracer::{
-    intersections::Intersection,
-    lights::PointLight,
+    intersections::{Intersection, Intersections},
+    lights::{DirectionalLight, Light, PointLight, SpotLight},
     linear::{Matrix, Tuple},
-    objects::{Shape, Sphere},
-    Color, Ray, World, DEFAULT_SPHERE_1, DEFAULT_SPHERE_2,
+    objects::{Plane, Shape, Sphere},
+    patterns::{BumpMap, Gradient, Pattern, Stripe},
+    Color, Material, Preset, Ray, ThinFilm, World, DEFAULT_LIGHT, DEFAULT_SPHERE_1,
+    DEFAULT_SPHERE_2,
 };
 
 #[test]
@@ -28,7 +32,7 @@ fn is_shadowed_no_obstruction() {
     let w = World::default();
     let p = Tuple::new_point(0, 10, 0);
 
-    assert!(!w.is_shadowed(&p));
+    assert!(!w.is_shadowed(&*DEFAULT_LIGHT, &p));
 }
 
 #[test]
@@ -36,7 +40,7 @@ fn is_shadowed_obstruction() {
     let w = World::default();
     let p = Tuple::new_point(10, -10, 10);
 
-    assert!(w.is_shadowed(&p))
+    assert!(w.is_shadowed(&*DEFAULT_LIGHT, &p))
 }
 
 #[test]
@@ -44,7 +48,7 @@ fn is_shadowed_object_behind_light() {
     let w = World::default();
     let p = Tuple::new_point(-20, 20, -20);
 
-    assert!(!w.is_shadowed(&p));
+    assert!(!w.is_shadowed(&*DEFAULT_LIGHT, &p));
 }
 
 #[test]
@@ -52,14 +56,14 @@ fn is_shadowed_object_behind_point() {
     let w = World::default();
     let p = Tuple::new_point(-2, 2, -2);
 
-    assert!(!w.is_shadowed(&p));
+    assert!(!w.is_shadowed(&*DEFAULT_LIGHT, &p));
 }
 
 #[test]
 fn shade_hit() {
     let w = World::default();
     let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
-    let shape = w.objects[0];
+    let shape = w.objects()[0];
     let i = Intersection::new(4.0, shape);
 
     let info = i.prepare_info(&r);
@@ -72,9 +76,9 @@ fn shade_hit() {
 fn shade_hit_inside() {
     let mut w = World::default();
     let light = PointLight::new(Tuple::new_point(0.0, 0.25, 0.0), Color::new(1, 1, 1));
-    w.light = Some(&light);
+    w.lights = vec![&light];
     let r = Ray::new(Tuple::new_point(0, 0, 0), Tuple::new_vector(0, 0, 1));
-    let shape = w.objects[1];
+    let shape = w.objects()[1];
     let i = Intersection::new(0.5, shape);
 
     let info = i.prepare_info(&r);
@@ -92,8 +96,9 @@ fn shade_hit_in_shadow() {
 
     let w = {
         let mut world = World::new();
-        world.light = Some(&light);
-        world.objects = vec![&s1, &s2];
+        world.add_light(&light);
+        world.add_object(&s1);
+        world.add_object(&s2);
 
         world
     };
@@ -107,6 +112,82 @@ fn shade_hit_in_shadow() {
     assert_eq!(c, Color::new(0.1, 0.1, 0.1));
 }
 
+#[test]
+fn shade_hit_multiple_lights() {
+    let mut w = World::default();
+    w.add_light(&*DEFAULT_LIGHT);
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let shape = w.objects()[0];
+    let i = Intersection::new(4.0, shape);
+
+    let info = i.prepare_info(&r);
+    let c = w.shade_hit(&info);
+
+    // The diffuse and specular reflections are doubled, but the ambient
+    // reflection of (0.08, 0.1, 0.06) is only counted once.
+    assert_eq!(c, Color::new(0.68132, 0.85166, 0.511));
+}
+
+#[test]
+fn shade_hit_shadow_per_light() {
+    let blocked = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
+    let visible = PointLight::new(Tuple::new_point(0, 0, 5), Color::new(1, 1, 1));
+    let s1 = Sphere::default();
+    let mut s2 = Sphere::default();
+    s2.set_transform(Matrix::translation(0, 0, 10));
+
+    let mut w = World::new();
+    w.add_light(&blocked);
+    w.add_light(&visible);
+    w.add_object(&s1);
+    w.add_object(&s2);
+
+    let r = Ray::new(Tuple::new_point(0, 0, 5), Tuple::new_vector(0, 0, 1));
+    let i = Intersection::new(4.0, &s2);
+
+    let info = i.prepare_info(&r);
+    let c = w.shade_hit(&info);
+
+    assert_eq!(c, Color::new(1.9, 1.9, 1.9));
+}
+
+#[test]
+fn ambient_is_independent_of_lights() {
+    let key = PointLight::new(Tuple::new_point(-10, 10, -10), Color::new(1, 1, 1));
+    let fill = PointLight::new(Tuple::new_point(10, 5, -10), Color::new(0.5, 0.3, 0.1));
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+    let mut key_only = World::new();
+    key_only.add_light(&key);
+    key_only.add_object(&*DEFAULT_SPHERE_1);
+    let mut key_and_fill = World::new();
+    key_and_fill.add_light(&key);
+    key_and_fill.add_light(&fill);
+    key_and_fill.add_object(&*DEFAULT_SPHERE_1);
+
+    // Behind the sphere, neither light reaches the surface, so only the
+    // ambient reflection remains. Adding a fill light doesn't change it.
+    let behind = Intersection::new(6.0, &*DEFAULT_SPHERE_1);
+    let info = behind.prepare_info(&r);
+
+    assert_eq!(key_only.shade_hit(&info), key_and_fill.shade_hit(&info));
+}
+
+#[test]
+fn shade_hit_explicit_ambient() {
+    let mut w = World::new();
+    w.ambient = Color::new(0.5, 0.5, 0.5);
+    w.add_object(&*DEFAULT_SPHERE_1);
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let shape = w.objects()[0];
+    let i = Intersection::new(4.0, shape);
+
+    let info = i.prepare_info(&r);
+
+    // Without any lights, only the ambient reflection remains.
+    assert_eq!(w.shade_hit(&info), Color::new(0.04, 0.05, 0.03));
+}
+
 #[test]
 fn color_at_miss() {
     let w = World::default();
@@ -129,14 +210,12 @@ fn color_at_hit() {
 
 #[test]
 fn color_with_intersection_behind_ray() {
-    let mut w = World::default();
-
     let mut outer = DEFAULT_SPHERE_1.clone();
     outer.set_material(DEFAULT_SPHERE_1.material().with_ambient(1.0));
     let mut inner = DEFAULT_SPHERE_2.clone();
     inner.set_material(DEFAULT_SPHERE_2.material().with_ambient(1.0));
 
-    w.objects = vec![&outer, &inner];
+    let w = default_world_with(&outer, &inner);
 
     let r = Ray::new(
         Tuple::new_point(0.0, 0.0, 0.75),
@@ -147,3 +226,977 @@ fn color_with_intersection_behind_ray() {
 
     assert_eq!(c, inner.material().color());
 }
+
+#[test]
+fn add_object_assigns_ids_in_order() {
+    let s1 = Sphere::default();
+    let s2 = Sphere::default();
+    let s3 = Sphere::default();
+    let mut w = World::new();
+
+    assert_eq!(w.add_object(&s1), 0);
+    assert_eq!(w.add_object(&s2), 1);
+    assert_eq!(w.add_object(&s3), 2);
+
+    assert_eq!(w.object_id(&s1), Some(0));
+    assert_eq!(w.object_id(&s2), Some(1));
+    assert_eq!(w.object_id(&s3), Some(2));
+}
+
+#[test]
+fn default_world_ids() {
+    let w = World::default();
+
+    assert_eq!(w.object_id(w.objects()[0]), Some(0));
+    assert_eq!(w.object_id(w.objects()[1]), Some(1));
+}
+
+#[test]
+fn object_id_not_in_world() {
+    let s = Sphere::default();
+    let w = World::default();
+
+    assert_eq!(w.object_id(&s), None);
+}
+
+#[test]
+fn object_id_is_per_world() {
+    let shared = Sphere::default();
+    let other = Sphere::default();
+    let mut w1 = World::new();
+    w1.add_object(&shared);
+    let mut w2 = World::new();
+    w2.add_object(&other);
+    w2.add_object(&shared);
+
+    assert_eq!(w1.object_id(&shared), Some(0));
+    assert_eq!(w2.object_id(&shared), Some(1));
+}
+
+#[test]
+fn object_id_of_object_added_twice() {
+    let s = Sphere::default();
+    let mut w = World::new();
+
+    assert_eq!(w.add_object(&s), 0);
+    assert_eq!(w.add_object(&s), 0);
+    assert_eq!(w.object_id(&s), Some(0));
+    assert_eq!(w.objects().len(), 1);
+}
+
+#[test]
+fn find_by_name() {
+    let mut left = Sphere::default();
+    left.set_name("left_sphere");
+    let mut right = Sphere::default();
+    right.set_name("right_sphere");
+
+    let mut w = World::new();
+    w.add_object(&left);
+    w.add_object(&right);
+
+    let found = w
+        .find("left_sphere")
+        .expect("Expected to find left sphere.");
+
+    assert_eq!(w.object_id(found), Some(0));
+    assert_eq!(found.name(), Some("left_sphere"));
+    assert!(w.find("middle_sphere").is_none());
+}
+
+#[test]
+fn reflected_color_nonreflective_material() {
+    let mut inner = DEFAULT_SPHERE_2.clone();
+    inner.set_material(DEFAULT_SPHERE_2.material().with_ambient(1.0));
+    let w = default_world_with(&DEFAULT_SPHERE_1, &inner);
+
+    let r = Ray::new(Tuple::new_point(0, 0, 0), Tuple::new_vector(0, 0, 1));
+    let i = Intersection::new(1.0, &inner);
+
+    let info = i.prepare_info(&r);
+    let c = w.reflected_color(&info, w.max_depth);
+
+    assert_eq!(c, Color::new(0, 0, 0));
+}
+
+/// Build a reflective plane below the default world's spheres.
+fn reflective_floor() -> Plane {
+    let mut plane = Plane::default();
+    plane.set_material(Material::default().with_reflective(0.5));
+    plane.set_transform(Matrix::translation(0, -1, 0));
+
+    plane
+}
+
+#[test]
+fn reflected_color_reflective_material() {
+    let mut w = World::default();
+    let plane = reflective_floor();
+    w.add_object(&plane);
+
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0, 0, -3),
+        Tuple::new_vector(0.0, -sqrt_2_over_2, sqrt_2_over_2),
+    );
+    let i = Intersection::new(2.0_f64.sqrt(), &plane);
+
+    let info = i.prepare_info(&r);
+    let c = w.reflected_color(&info, w.max_depth);
+
+    assert_eq!(c, Color::new(0.19033, 0.23791, 0.14274));
+}
+
+#[test]
+fn shade_hit_reflective_material() {
+    let mut w = World::default();
+    let plane = reflective_floor();
+    w.add_object(&plane);
+
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0, 0, -3),
+        Tuple::new_vector(0.0, -sqrt_2_over_2, sqrt_2_over_2),
+    );
+    let i = Intersection::new(2.0_f64.sqrt(), &plane);
+
+    let info = i.prepare_info(&r);
+    let c = w.shade_hit(&info);
+
+    assert_eq!(c, Color::new(0.87676, 0.92434, 0.82917));
+}
+
+#[test]
+fn color_at_mutually_reflective_surfaces() {
+    let light = PointLight::new(Tuple::new_point(0, 0, 0), Color::new(1, 1, 1));
+    let mut lower = Plane::default();
+    lower.set_material(Material::default().with_reflective(1.0));
+    lower.set_transform(Matrix::translation(0, -1, 0));
+    let mut upper = Plane::default();
+    upper.set_material(Material::default().with_reflective(1.0));
+    upper.set_transform(Matrix::translation(0, 1, 0));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&lower);
+    w.add_object(&upper);
+
+    let r = Ray::new(Tuple::new_point(0, 0, 0), Tuple::new_vector(0, 1, 0));
+
+    // The ray bounces between the planes until the depth limit is reached, so
+    // this must terminate rather than overflowing the stack.
+    let c = w.color_at(&r);
+
+    assert!(c.red() > 0.0);
+}
+
+#[test]
+fn reflected_color_at_max_depth() {
+    let mut w = World::default();
+    let plane = reflective_floor();
+    w.add_object(&plane);
+
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0, 0, -3),
+        Tuple::new_vector(0.0, -sqrt_2_over_2, sqrt_2_over_2),
+    );
+    let i = Intersection::new(2.0_f64.sqrt(), &plane);
+
+    let info = i.prepare_info(&r);
+    let c = w.reflected_color(&info, 0);
+
+    assert_eq!(c, Color::new(0, 0, 0));
+}
+
+#[test]
+fn color_at_respects_max_depth() {
+    let light = PointLight::new(Tuple::new_point(0, 0, 0), Color::new(1, 1, 1));
+    let mut lower = Plane::default();
+    lower.set_material(Material::default().with_reflective(1.0));
+    lower.set_transform(Matrix::translation(0, -1, 0));
+    let mut upper = Plane::default();
+    upper.set_material(Material::default().with_reflective(1.0));
+    upper.set_transform(Matrix::translation(0, 1, 0));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&lower);
+    w.add_object(&upper);
+
+    let r = Ray::new(Tuple::new_point(0, 0, 0), Tuple::new_vector(0, 1, 0));
+
+    // Each extra bounce adds the light from another surface, so a deeper
+    // limit gives a brighter result.
+    w.max_depth = 0;
+    let no_bounces = w.color_at(&r);
+    w.max_depth = 3;
+    let three_bounces = w.color_at(&r);
+
+    assert_eq!(no_bounces, Color::new(1.9, 1.9, 1.9));
+    assert!(three_bounces.red() > no_bounces.red());
+}
+
+/// A pattern that returns the point it's sampled at as a color, so the
+/// direction of refracted rays can be checked.
+#[derive(Debug)]
+struct PointPattern {
+    transform: Matrix,
+}
+
+impl Pattern for PointPattern {
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        Color::new(point.x(), point.y(), point.z())
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+/// Build the default world with its spheres replaced.
+fn default_world_with<'a>(outer: &'a Sphere, inner: &'a Sphere) -> World<'a> {
+    let mut w = World::new();
+    w.add_light(&*DEFAULT_LIGHT);
+    w.add_object(outer);
+    w.add_object(inner);
+
+    w
+}
+
+fn glass_material() -> Material {
+    Material::default()
+        .with_transparency(1.0)
+        .with_refractive_index(1.5)
+}
+
+#[test]
+fn refracted_color_opaque_surface() {
+    let w = World::default();
+    let shape = w.objects()[0];
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let intersections = Intersections::new(vec![
+        Intersection::new(4.0, shape),
+        Intersection::new(6.0, shape),
+    ]);
+
+    let info = intersections[0].prepare_info_with(&r, &intersections);
+    let c = w.refracted_color(&info, 5);
+
+    assert_eq!(c, Color::new(0, 0, 0));
+}
+
+#[test]
+fn refracted_color_at_max_depth() {
+    let mut outer = DEFAULT_SPHERE_1.clone();
+    outer.set_material(glass_material());
+    let w = default_world_with(&outer, &DEFAULT_SPHERE_2);
+
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+    let intersections = Intersections::new(vec![
+        Intersection::new(4.0, &outer),
+        Intersection::new(6.0, &outer),
+    ]);
+
+    let info = intersections[0].prepare_info_with(&r, &intersections);
+    let c = w.refracted_color(&info, 0);
+
+    assert_eq!(c, Color::new(0, 0, 0));
+}
+
+#[test]
+fn refracted_color_total_internal_reflection() {
+    let mut outer = DEFAULT_SPHERE_1.clone();
+    outer.set_material(glass_material());
+    let w = default_world_with(&outer, &DEFAULT_SPHERE_2);
+
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0.0, 0.0, sqrt_2_over_2),
+        Tuple::new_vector(0, 1, 0),
+    );
+    let intersections = Intersections::new(vec![
+        Intersection::new(-sqrt_2_over_2, &outer),
+        Intersection::new(sqrt_2_over_2, &outer),
+    ]);
+
+    // The ray starts inside the sphere, so the second intersection is the one
+    // that matters.
+    let info = intersections[1].prepare_info_with(&r, &intersections);
+    let c = w.refracted_color(&info, 5);
+
+    assert_eq!(c, Color::new(0, 0, 0));
+}
+
+#[test]
+fn refracted_color_refracted_ray() {
+    let mut a = DEFAULT_SPHERE_1.clone();
+    a.set_material(
+        DEFAULT_SPHERE_1
+            .material()
+            .with_ambient(1.0)
+            .with_pattern(PointPattern {
+                transform: Matrix::identity_4(),
+            }),
+    );
+    let mut b = DEFAULT_SPHERE_2.clone();
+    b.set_material(glass_material());
+    let w = default_world_with(&a, &b);
+
+    let r = Ray::new(Tuple::new_point(0.0, 0.0, 0.1), Tuple::new_vector(0, 1, 0));
+    let intersections = Intersections::new(vec![
+        Intersection::new(-0.9899, &a),
+        Intersection::new(-0.4899, &b),
+        Intersection::new(0.4899, &b),
+        Intersection::new(0.9899, &a),
+    ]);
+
+    let info = intersections[2].prepare_info_with(&r, &intersections);
+    let c = w.refracted_color(&info, 5);
+
+    assert_eq!(c, Color::new(0.0, 0.99888, 0.04722));
+}
+
+#[test]
+fn shade_hit_transparent_material() {
+    let mut w = World::default();
+    let mut floor = Plane::default();
+    floor.set_transform(Matrix::translation(0, -1, 0));
+    floor.set_material(
+        Material::default()
+            .with_transparency(0.5)
+            .with_refractive_index(1.5),
+    );
+    let mut ball = Sphere::default();
+    ball.set_material(
+        Material::default()
+            .with_color(Color::new(1, 0, 0))
+            .with_ambient(0.5),
+    );
+    ball.set_transform(Matrix::translation(0.0, -3.5, -0.5));
+    w.add_object(&floor);
+    w.add_object(&ball);
+
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0, 0, -3),
+        Tuple::new_vector(0.0, -sqrt_2_over_2, sqrt_2_over_2),
+    );
+    let intersections = Intersections::new(vec![Intersection::new(2.0_f64.sqrt(), &floor)]);
+
+    let info = intersections[0].prepare_info_with(&r, &intersections);
+    let c = w.shade_hit(&info);
+
+    // The floor is half transparent, so it only dims the light reaching the
+    // ball and some of the ball's diffuse lighting shows through.
+    assert_eq!(c, Color::new(1.12547, 0.68643, 0.68643));
+}
+
+#[test]
+fn is_shadowed_ignores_transparent_objects() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let mut glass = Sphere::default();
+    glass.set_material(glass_material());
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&glass);
+
+    assert!(!w.is_shadowed(&light, &Tuple::new_point(0, -5, 0)));
+}
+
+#[test]
+fn visibility_through_transparent_object() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let mut pane = Sphere::default();
+    pane.set_material(Material::default().with_transparency(0.5));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&pane);
+
+    // The light passes through both sides of the sphere.
+    assert_eq!(
+        w.visibility(&light, &Tuple::new_point(0, -5, 0)),
+        Color::new(0.25, 0.25, 0.25)
+    );
+    assert!(!w.is_shadowed(&light, &Tuple::new_point(0, -5, 0)));
+}
+
+#[test]
+fn visibility_absorbed_by_transparent_object() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let mut glass = Sphere::default();
+    glass.set_material(glass_material().with_absorption(Color::new(0.0, 0.5, 1.0)));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&glass);
+
+    // The light travels 2 units through the sphere, so the shadow is tinted
+    // red.
+    let v = w.visibility(&light, &Tuple::new_point(0, -5, 0));
+
+    assert_eq!(v, Color::new(1.0, (-1.0_f64).exp(), (-2.0_f64).exp()));
+}
+
+#[test]
+fn visibility_from_inside_absorbing_object() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let mut glass = Sphere::default();
+    glass.set_material(glass_material().with_absorption(Color::new(1, 1, 1)));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&glass);
+
+    // Only the distance from the point to the surface absorbs light.
+    let v = w.visibility(&light, &Tuple::new_point(0.0, 0.5, 0.0));
+    let expected = (-0.5_f64).exp();
+
+    assert_eq!(v, Color::new(expected, expected, expected));
+}
+
+#[test]
+fn shade_hit_reflective_transparent_material() {
+    let mut w = World::default();
+    let mut floor = Plane::default();
+    floor.set_transform(Matrix::translation(0, -1, 0));
+    floor.set_material(
+        Material::default()
+            .with_reflective(0.5)
+            .with_transparency(0.5)
+            .with_refractive_index(1.5),
+    );
+    let mut ball = Sphere::default();
+    ball.set_material(
+        Material::default()
+            .with_color(Color::new(1, 0, 0))
+            .with_ambient(0.5),
+    );
+    ball.set_transform(Matrix::translation(0.0, -3.5, -0.5));
+    w.add_object(&floor);
+    w.add_object(&ball);
+
+    let sqrt_2_over_2 = 2.0_f64.sqrt() / 2.0;
+    let r = Ray::new(
+        Tuple::new_point(0, 0, -3),
+        Tuple::new_vector(0.0, -sqrt_2_over_2, sqrt_2_over_2),
+    );
+    let intersections = Intersections::new(vec![Intersection::new(2.0_f64.sqrt(), &floor)]);
+
+    let info = intersections[0].prepare_info_with(&r, &intersections);
+    let c = w.shade_hit(&info);
+
+    // The green and blue channels match the book, but the red ball is brighter
+    // because the transparent floor only partly shadows it.
+    assert_eq!(c, Color::new(1.11500, 0.69643, 0.69243));
+}
+
+#[test]
+fn shade_hit_emissive_material() {
+    let mut glowing = Sphere::default();
+    glowing.set_material(
+        Material::default()
+            .with_color(Color::new(0, 0, 0))
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0)
+            .with_emissive(Color::new(0.2, 0.8, 0.4)),
+    );
+    let mut w = World::new();
+    w.add_light(&*DEFAULT_LIGHT);
+    w.add_object(&glowing);
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+    let c = w.color_at(&r);
+
+    assert_eq!(c, Color::new(0.2, 0.8, 0.4));
+}
+
+#[test]
+fn shade_hit_emissive_material_in_shadow() {
+    let light = PointLight::new(Tuple::new_point(0, 0, -10), Color::new(1, 1, 1));
+    let s1 = Sphere::default();
+    let mut s2 = Sphere::default();
+    s2.set_transform(Matrix::translation(0, 0, 10));
+    s2.set_material(Material::default().with_emissive(Color::new(0.5, 0.5, 0.5)));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&s1);
+    w.add_object(&s2);
+
+    let r = Ray::new(Tuple::new_point(0, 0, 5), Tuple::new_vector(0, 0, 1));
+    let i = Intersection::new(4.0, &s2);
+
+    let info = i.prepare_info(&r);
+    let c = w.shade_hit(&info);
+
+    assert_eq!(c, Color::new(0.6, 0.6, 0.6));
+}
+
+#[test]
+fn is_shadowed_ignores_emissive_objects() {
+    // A light inside a glowing bulb should still light the rest of the scene.
+    let light = PointLight::new(Tuple::new_point(0, 0, 0), Color::new(1, 1, 1));
+    let mut bulb = Sphere::default();
+    bulb.set_material(Material::default().with_emissive(Color::new(1.0, 0.9, 0.7)));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&bulb);
+
+    assert!(!w.is_shadowed(&light, &Tuple::new_point(0, -5, 0)));
+}
+
+#[test]
+fn is_shadowed_by_emissive_object_outside_light() {
+    // A glowing sign still casts a shadow from a light elsewhere in the scene.
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let mut sign = Sphere::default();
+    sign.set_material(Material::default().with_emissive(Color::new(1.0, 0.2, 0.6)));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&sign);
+
+    assert!(w.is_shadowed(&light, &Tuple::new_point(0, -5, 0)));
+}
+
+#[test]
+fn shade_hit_uses_shading_normal() {
+    let light = PointLight::new(Tuple::new_point(0.5, 10.0, 0.5), Color::new(1, 1, 1));
+    let mut floor = Plane::default();
+    floor.set_material(Material::default().with_normal_map(BumpMap::new(
+        Gradient::new(Color::new(0, 0, 0), Color::new(1, 1, 1)),
+        1.0,
+    )));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&floor);
+
+    let r = Ray::new(Tuple::new_point(0.5, 1.0, 0.5), Tuple::new_vector(0, -1, 0));
+    let info = Intersection::new(1.0, &floor).prepare_info(&r);
+    let c = w.shade_hit(&info);
+
+    // The light is straight above, but the shading normal is tilted by 45
+    // degrees, which dims the diffuse reflection and hides the highlight.
+    let diffuse = 0.9 * 2.0_f64.sqrt() / 2.0;
+    assert_eq!(c, Color::new(0.1 + diffuse, 0.1 + diffuse, 0.1 + diffuse));
+}
+
+#[test]
+fn material_presets_registered() {
+    let w = World::new();
+
+    for preset in Preset::ALL.iter() {
+        assert_eq!(w.material(preset.name()), Some(&preset.material()));
+    }
+}
+
+#[test]
+fn register_material_replaces_existing() {
+    let mut w = World::new();
+    let tinted = Preset::Glass
+        .material()
+        .with_color(Color::new(0.0, 0.1, 0.0));
+
+    w.register_material("glass", tinted.clone());
+
+    assert_eq!(w.material("glass"), Some(&tinted));
+}
+
+/// Look through a clear, absorbing sphere at a glowing white backdrop.
+fn color_through_absorbing_sphere(radius: f64) -> Color {
+    let mut ball = Sphere::default();
+    ball.set_transform(Matrix::scaling(radius, radius, radius));
+    ball.set_material(
+        Material::default()
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0)
+            .with_transparency(1.0)
+            .with_refractive_index(1.5)
+            .with_absorption(Color::new(0.5, 0.1, 0.5)),
+    );
+    let mut backdrop = Plane::default();
+    backdrop.set_transform(&Matrix::translation(0, 0, 5) * &Matrix::rotation_x(PI / 2.0));
+    backdrop.set_material(
+        Material::default()
+            .with_color(Color::new(0, 0, 0))
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0)
+            .with_emissive(Color::new(1, 1, 1)),
+    );
+
+    let mut w = World::new();
+    w.add_object(&ball);
+    w.add_object(&backdrop);
+
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+    w.color_at(&r)
+}
+
+#[test]
+fn refracted_color_absorbed_by_distance() {
+    let c = color_through_absorbing_sphere(1.0);
+
+    assert_eq!(
+        c,
+        Color::new((-1.0_f64).exp(), (-0.2_f64).exp(), (-1.0_f64).exp())
+    );
+}
+
+#[test]
+fn refracted_color_thick_glass_darker_than_thin() {
+    let thin = color_through_absorbing_sphere(0.5);
+    let thick = color_through_absorbing_sphere(1.0);
+
+    assert!(thick.red() < thin.red());
+    assert!(thick.green() < thin.green());
+}
+
+/// A ball floating above a shadow catching floor, lit from directly above.
+fn shadow_catcher_scene<'a>(
+    light: &'a PointLight,
+    floor: &'a mut Plane,
+    ball: &'a mut Sphere,
+) -> World<'a> {
+    floor.set_material(Material::default().with_shadow_catcher(true));
+    ball.set_transform(Matrix::translation(0, 2, 0));
+
+    let mut w = World::new();
+    w.add_light(light);
+    w.add_object(floor);
+    w.add_object(ball);
+
+    w
+}
+
+#[test]
+fn color_and_alpha_at_shadow_catcher_in_shadow() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let (mut floor, mut ball) = (Plane::default(), Sphere::default());
+    let w = shadow_catcher_scene(&light, &mut floor, &mut ball);
+    let r = Ray::new(
+        Tuple::new_point(0, 1, -5),
+        Tuple::new_vector(0, -1, 5).normalized(),
+    );
+
+    assert_eq!(w.color_and_alpha_at(&r), (Color::new(0, 0, 0), 1.0));
+}
+
+#[test]
+fn color_and_alpha_at_shadow_catcher_lit() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let (mut floor, mut ball) = (Plane::default(), Sphere::default());
+    let w = shadow_catcher_scene(&light, &mut floor, &mut ball);
+    let r = Ray::new(
+        Tuple::new_point(5, 1, -5),
+        Tuple::new_vector(0, -1, 5).normalized(),
+    );
+
+    assert_eq!(w.color_and_alpha_at(&r), (Color::new(0, 0, 0), 0.0));
+}
+
+#[test]
+fn color_and_alpha_at_object_above_shadow_catcher() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let (mut floor, mut ball) = (Plane::default(), Sphere::default());
+    let w = shadow_catcher_scene(&light, &mut floor, &mut ball);
+    let r = Ray::new(Tuple::new_point(0, 2, -5), Tuple::new_vector(0, 0, 1));
+
+    let (color, alpha) = w.color_and_alpha_at(&r);
+
+    assert_eq!(alpha, 1.0);
+    assert_eq!(color, w.color_at(&r));
+    assert_ne!(color, Color::new(0, 0, 0));
+}
+
+#[test]
+fn color_and_alpha_at_sees_through_shadow_catcher() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let (mut floor, mut ball) = (Plane::default(), Sphere::default());
+    let mut w = shadow_catcher_scene(&light, &mut floor, &mut ball);
+    let mut below = Sphere::default();
+    below.set_transform(Matrix::translation(5, -3, 0));
+    w.add_object(&below);
+
+    let mut without_floor = World::new();
+    without_floor.lights = w.lights.clone();
+    without_floor.add_object(&below);
+
+    // Looking down through a lit part of the floor shows the sphere
+    // underneath it, unchanged.
+    let r = Ray::new(Tuple::new_point(5, 5, 0), Tuple::new_vector(0, -1, 0));
+
+    let (color, alpha) = w.color_and_alpha_at(&r);
+
+    assert_eq!(alpha, 1.0);
+    assert_eq!(color, without_floor.color_at(&r));
+    assert_ne!(color, Color::new(0, 0, 0));
+}
+
+#[test]
+fn is_shadowed_ignores_shadow_catchers() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let (mut floor, mut ball) = (Plane::default(), Sphere::default());
+    let w = shadow_catcher_scene(&light, &mut floor, &mut ball);
+
+    assert!(!w.is_shadowed(&light, &Tuple::new_point(5, -1, 0)));
+}
+
+/// A floor whose opacity mask cuts away every other unit-wide strip along the
+/// x-axis.
+fn slatted_floor() -> Plane {
+    let mut floor = Plane::default();
+    floor.set_material(
+        Material::default()
+            .with_opacity_mask(Stripe::new(Color::new(1, 1, 1), Color::new(0, 0, 0))),
+    );
+
+    floor
+}
+
+#[test]
+fn intersect_discards_cut_out_hits() {
+    let floor = slatted_floor();
+    let mut w = World::new();
+    w.add_object(&floor);
+
+    let solid = Ray::new(Tuple::new_point(0.5, 5.0, 0.0), Tuple::new_vector(0, -1, 0));
+    let cut_out = Ray::new(Tuple::new_point(1.5, 5.0, 0.0), Tuple::new_vector(0, -1, 0));
+
+    assert_eq!(w.intersect(&solid).len(), 1);
+    assert!(w.intersect(&cut_out).is_empty());
+}
+
+#[test]
+fn intersect_hits_object_behind_cut_out_surface() {
+    let floor = slatted_floor();
+    let mut below = Sphere::default();
+    below.set_transform(Matrix::translation(1.5, -3.0, 0.0));
+    let mut w = World::new();
+    w.add_object(&floor);
+    w.add_object(&below);
+
+    let r = Ray::new(Tuple::new_point(1.5, 5.0, 0.0), Tuple::new_vector(0, -1, 0));
+
+    let intersections = w.intersect(&r);
+
+    assert_eq!(intersections.hit().unwrap().object(), &below);
+}
+
+#[test]
+fn is_shadowed_through_cut_out_surface() {
+    let light = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let floor = slatted_floor();
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&floor);
+
+    // Light reaching these points passes through the floor at x = 0.45 and
+    // x = 1.36, which are solid and cut away respectively.
+    assert!(w.is_shadowed(&light, &Tuple::new_point(0.5, -1.0, 0.0)));
+    assert!(!w.is_shadowed(&light, &Tuple::new_point(1.5, -1.0, 0.0)));
+}
+
+#[test]
+fn color_at_thin_film_tints_reflection() {
+    // A white wall behind the camera is reflected by a bubble in front of it.
+    let mut wall = Plane::default();
+    wall.set_transform(&Matrix::translation(0, 0, -10) * &Matrix::rotation_x(PI / 2.0));
+    wall.set_material(Material::default().with_ambient(1.0).with_diffuse(0.0));
+    let mut bubble = Sphere::default();
+    bubble.set_material(
+        Material::default()
+            .with_color(Color::new(0, 0, 0))
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0)
+            .with_reflective(1.0)
+            .with_transparency(1.0),
+    );
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+    let mut w = World::new();
+    w.add_light(&*DEFAULT_LIGHT);
+    w.add_object(&wall);
+    w.add_object(&bubble);
+    let plain = w.color_at(&r);
+
+    bubble.set_material(bubble.material().with_thin_film(ThinFilm::new(400.0, 1.33)));
+    let mut w = World::new();
+    w.add_light(&*DEFAULT_LIGHT);
+    w.add_object(&wall);
+    w.add_object(&bubble);
+    let coated = w.color_at(&r);
+
+    // Without a film, a bubble with the same index as the air around it
+    // doesn't reflect anything, and there's nothing to see through it.
+    assert_eq!(plain, Color::new(0, 0, 0));
+    assert_ne!(coated, Color::new(0, 0, 0));
+    assert_ne!(coated.red(), coated.green());
+    assert_ne!(coated.green(), coated.blue());
+}
+
+#[test]
+fn color_at_thin_film_tints_opaque_reflection() {
+    // A white wall behind the camera is reflected by a mirrored ball in front
+    // of it.
+    let mut wall = Plane::default();
+    wall.set_transform(&Matrix::translation(0, 0, -10) * &Matrix::rotation_x(PI / 2.0));
+    wall.set_material(Material::default().with_ambient(1.0).with_diffuse(0.0));
+    let mut ball = Sphere::default();
+    ball.set_material(
+        Material::default()
+            .with_color(Color::new(0, 0, 0))
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0)
+            .with_reflective(1.0),
+    );
+    let r = Ray::new(Tuple::new_point(0, 0, -5), Tuple::new_vector(0, 0, 1));
+
+    let mut w = World::new();
+    w.add_light(&*DEFAULT_LIGHT);
+    w.add_object(&wall);
+    w.add_object(&ball);
+    let plain = w.color_at(&r);
+
+    ball.set_material(ball.material().with_thin_film(ThinFilm::new(400.0, 1.33)));
+    let mut w = World::new();
+    w.add_light(&*DEFAULT_LIGHT);
+    w.add_object(&wall);
+    w.add_object(&ball);
+    let coated = w.color_at(&r);
+
+    // The bare mirror reflects the wall as it is, while the film tints it.
+    assert_eq!(plain, Color::new(1, 1, 1));
+    assert_ne!(coated, plain);
+    assert_ne!(coated.red(), coated.green());
+    assert_ne!(coated.green(), coated.blue());
+}
+
+#[test]
+fn color_and_alpha_at_shadow_catcher_partly_shadowed() {
+    let overhead = PointLight::new(Tuple::new_point(0, 10, 0), Color::new(1, 1, 1));
+    let (mut floor, mut ball) = (Plane::default(), Sphere::default());
+    let mut w = shadow_catcher_scene(&overhead, &mut floor, &mut ball);
+
+    // Light from the side reaches the floor under the ball.
+    let side = PointLight::new(Tuple::new_point(10, 10, 0), Color::new(1, 1, 1));
+    w.add_light(&side);
+
+    let r = Ray::new(
+        Tuple::new_point(0, 1, -5),
+        Tuple::new_vector(0, -1, 5).normalized(),
+    );
+
+    assert_eq!(w.color_and_alpha_at(&r), (Color::new(0, 0, 0), 0.5));
+}
+
+#[test]
+fn is_shadowed_directional_light() {
+    // The sun is infinitely far away, so even distant objects cast shadows.
+    let sun = DirectionalLight::new(Tuple::new_vector(0, -1, 0), Color::new(1, 1, 1));
+    let mut cloud = Sphere::default();
+    cloud.set_transform(Matrix::translation(0, 1000, 0));
+
+    let mut w = World::new();
+    w.add_light(&sun);
+    w.add_object(&cloud);
+
+    assert!(w.is_shadowed(&sun, &Tuple::new_point(0, 0, 0)));
+    assert!(!w.is_shadowed(&sun, &Tuple::new_point(5, 0, 0)));
+}
+
+#[test]
+fn shade_hit_spot_light_cone() {
+    let spot = SpotLight::new(
+        Tuple::new_point(0, 10, 0),
+        Tuple::new_vector(0, -1, 0),
+        PI / 16.0,
+        PI / 12.0,
+        Color::new(1, 1, 1),
+    );
+    let floor = Plane::default();
+    let mut w = World::new();
+    w.add_light(&spot);
+    w.add_object(&floor);
+
+    let shade_at = |x: f64| {
+        let r = Ray::new(Tuple::new_point(x, 1.0, 0.0), Tuple::new_vector(0, -1, 0));
+
+        w.color_at(&r)
+    };
+
+    // Outside of the cone, only the ambient light remains.
+    assert!(shade_at(0.0).red() > 1.0);
+    assert_eq!(shade_at(5.0), Color::new(0.1, 0.1, 0.1));
+}
+
+/// A light made of two points, so that it can be partly blocked.
+#[derive(Debug)]
+struct PairOfLights {
+    left: PointLight,
+    right: PointLight,
+}
+
+impl Light for PairOfLights {
+    fn direction_from(&self, point: &Tuple) -> Tuple {
+        self.left.direction_from(point)
+    }
+
+    fn distance_from(&self, point: &Tuple) -> f64 {
+        self.left.distance_from(point)
+    }
+
+    fn intensity(&self) -> Color {
+        self.left.intensity()
+    }
+
+    fn samples_from(&self, point: &Tuple) -> Vec<(Tuple, f64)> {
+        vec![
+            (
+                self.left.direction_from(point),
+                self.left.distance_from(point),
+            ),
+            (
+                self.right.direction_from(point),
+                self.right.distance_from(point),
+            ),
+        ]
+    }
+}
+
+#[test]
+fn visibility_partly_blocked_light() {
+    let light = PairOfLights {
+        left: PointLight::new(Tuple::new_point(-5, 10, 0), Color::new(1, 1, 1)),
+        right: PointLight::new(Tuple::new_point(5, 10, 0), Color::new(1, 1, 1)),
+    };
+    let mut blocker = Sphere::default();
+    blocker.set_transform(Matrix::translation(-2.5, 5.0, 0.0));
+
+    let mut w = World::new();
+    w.add_light(&light);
+    w.add_object(&blocker);
+
+    assert_eq!(
+        w.visibility(&light, &Tuple::new_point(0, 0, 0)),
+        Color::new(0.5, 0.5, 0.5)
+    );
+    assert_eq!(
+        w.visibility(&light, &Tuple::new_point(0, 0, 10)),
+        Color::new(1, 1, 1)
+    );
+}